@@ -0,0 +1,93 @@
+//! Runs one optimization from a request file without the server:
+//!
+//!     scheduler-cli request.json [output.json]
+//!
+//! The request has the same shape as the body of POST /optimize; the result
+//! (fitness, quality, seed, schedule and violations) is written as JSON to
+//! `output.json` or stdout.
+
+use std::{fs, process::ExitCode};
+
+use scheduler_core::algorithms::{
+    finalize::finalize_schedule,
+    models::{OptimizationRequest, ScheduleChecker, PSO},
+    presets::Preset,
+};
+use serde_json::json;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some(input) = args.first() else {
+        eprintln!("usage: scheduler-cli <request.json> [output.json]");
+        return ExitCode::from(2);
+    };
+
+    match run(input, args.get(1).map(String::as_str)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("scheduler-cli: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(input: &str, output: Option<&str>) -> Result<(), String> {
+    let body = fs::read_to_string(input).map_err(|e| format!("cannot read {}: {}", input, e))?;
+    let mut request: OptimizationRequest =
+        serde_json::from_str(&body).map_err(|e| format!("invalid request {}: {}", input, e))?;
+    if request.courses.is_empty() {
+        return Err("courses must contain at least one course".to_string());
+    }
+
+    // Sama seperti server: override di request menimpa config
+    let mut config = request.config.clone().unwrap_or_default();
+    if let Some(mode) = request.mode.take() {
+        config.mode = mode;
+    }
+    if let Some(rule) = request.per_day_sks_rule.take() {
+        config.per_day_sks_rule = rule;
+    }
+    if let Some(policy) = request.kelas_waktu_policy.take() {
+        config.kelas_waktu_policy = policy;
+    }
+    config.validate().map_err(|e| format!("invalid config: {}", e))?;
+    let parameters = request
+        .parameters
+        .clone()
+        .or_else(|| request.preset.map(Preset::parameters))
+        .unwrap_or_default();
+    request.config = Some(config.clone());
+
+    let checker = ScheduleChecker::for_request(&request, config);
+    let mut pso = PSO::new(request.courses.clone(), checker.clone(), parameters);
+    pso.algorithm = request.algorithm;
+    let seed = pso.seed;
+    let mut optimizer = pso.stepper();
+    let report = optimizer.run_to_end();
+    let (position, _) = optimizer.best();
+
+    let result = finalize_schedule(&request, &checker, &position);
+    let fitness = checker.evaluate(&result.schedule);
+    let (conflicts, preferences, bookings, soft) = checker.evaluate_messages(&result.schedule);
+    let out = json!({
+        "fitness": fitness,
+        "quality": checker.config.quality.quality(fitness),
+        "seed": seed,
+        "iterations": report.iteration,
+        "termination_reason": report.finished,
+        "schedule": result.schedule,
+        "unplaced": result.unplaced,
+        "unassigned_rooms": result.unassigned_rooms,
+        "message": [conflicts, preferences, bookings, soft],
+    });
+
+    let text = serde_json::to_string_pretty(&out).map_err(|e| e.to_string())?;
+    match output {
+        Some(path) => fs::write(path, text).map_err(|e| format!("cannot write {}: {}", path, e)),
+        None => {
+            println!("{}", text);
+            Ok(())
+        }
+    }
+}
+
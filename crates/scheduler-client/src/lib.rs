@@ -0,0 +1,112 @@
+//! Typed HTTP client for the scheduler server. Requests are the
+//! `scheduler_core` models, so callers build them with the same types the
+//! server deserializes.
+
+use std::time::Duration;
+
+use scheduler_core::algorithms::models::OptimizationRequest;
+use serde::Deserialize;
+use serde_json::Value;
+
+pub use reqwest::Error;
+
+const TENANT_HEADER: &str = "x-tenant-id";
+const API_KEY_HEADER: &str = "x-api-key";
+
+/// Reply of POST /optimize without `wait`
+#[derive(Debug, Clone, Deserialize)]
+pub struct Submitted {
+    pub job_id: u64,
+    pub status_url: String,
+    pub result_url: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct SchedulerClient {
+    base_url: String,
+    tenant: Option<String>,
+    api_key: Option<String>,
+    http: reqwest::Client,
+}
+
+impl SchedulerClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        SchedulerClient {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            tenant: None,
+            api_key: None,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// Sends every request as this tenant (`x-tenant-id`)
+    pub fn with_tenant(mut self, tenant: impl Into<String>) -> Self {
+        self.tenant = Some(tenant.into());
+        self
+    }
+
+    /// Sends the tenant's API key (`x-api-key`), required once the server
+    /// has tenant keys configured
+    pub fn with_api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_key = Some(key.into());
+        self
+    }
+
+    /// Queues a run and returns its job id
+    pub async fn submit(&self, request: &OptimizationRequest) -> Result<Submitted, Error> {
+        self.send(self.http.post(self.url("/optimize")).json(request)).await?.json().await
+    }
+
+    /// Runs to completion and returns the result body (`?wait=true`)
+    pub async fn optimize(&self, request: &OptimizationRequest) -> Result<Value, Error> {
+        self.send(self.http.post(self.url("/optimize?wait=true")).json(request)).await?.json().await
+    }
+
+    pub async fn status(&self, job_id: u64) -> Result<Value, Error> {
+        self.get(&format!("/jobs/{}/status", job_id)).await
+    }
+
+    pub async fn result(&self, job_id: u64) -> Result<Value, Error> {
+        self.get(&format!("/jobs/{}/result", job_id)).await
+    }
+
+    /// Stable-schema quality summary of a finished job
+    pub async fn summary(&self, job_id: u64) -> Result<Value, Error> {
+        self.get(&format!("/jobs/{}/summary.json", job_id)).await
+    }
+
+    pub async fn cancel(&self, job_id: u64) -> Result<Value, Error> {
+        self.send(self.http.post(self.url(&format!("/jobs/{}/cancel", job_id)))).await?.json().await
+    }
+
+    /// Polls the job status until it is no longer running
+    pub async fn wait(&self, job_id: u64, interval: Duration) -> Result<Value, Error> {
+        loop {
+            let status = self.status(job_id).await?;
+            if status.pointer("/status/state").and_then(Value::as_str) != Some("running") {
+                return Ok(status);
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    async fn get(&self, path: &str) -> Result<Value, Error> {
+        self.send(self.http.get(self.url(path))).await?.json().await
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response, Error> {
+        let request = match &self.tenant {
+            Some(tenant) => request.header(TENANT_HEADER, tenant),
+            None => request,
+        };
+        let request = match &self.api_key {
+            Some(key) => request.header(API_KEY_HEADER, key),
+            None => request,
+        };
+        request.send().await?.error_for_status()
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+}
@@ -0,0 +1,80 @@
+use rand::Rng;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{float::Float, models::PSO};
+
+/// Simulated-annealing local search that polishes the global best after the
+/// swarm has finished
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(default)]
+pub struct RefinementParameters {
+    #[schemars(range(min = 0.0))]
+    pub initial_temperature: f32,
+    /// Temperature multiplier per iteration
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub cooling_rate: f32,
+    pub iterations: usize,
+}
+
+impl Default for RefinementParameters {
+    fn default() -> Self {
+        Self {
+            initial_temperature: 10.0,
+            cooling_rate: 0.995,
+            iterations: 1000,
+        }
+    }
+}
+
+impl<F: Float> PSO<F> {
+    /// Anneals from the global best by redrawing one course's (day, time)
+    /// pair per move; worse moves are accepted with probability
+    /// exp(-delta / temperature). Returns whether the global best improved.
+    pub(super) fn refine(&mut self, params: &RefinementParameters) -> bool {
+        let genes = self.global_best_position.len() / 2;
+        if genes == 0 || !self.global_best_fitness.is_finite() {
+            return false;
+        }
+        let selection = self.parameters.selection;
+
+        let mut current = self.global_best_position.clone();
+        let mut current_score = (self.global_best_hard_penalty, self.global_best_fitness);
+        let mut best_score = current_score;
+        let mut best: Option<Vec<F>> = None;
+        let mut temperature = params.initial_temperature;
+
+        for step in 0..params.iterations {
+            if step % 64 == 0 && self.cancelled() {
+                break;
+            }
+
+            let gene = self.rng.random_range(0..genes);
+            let mut candidate = current.clone();
+            candidate[gene * 2] = F::random_range(&mut self.rng, F::ZERO, F::from_f32(1.0));
+            candidate[gene * 2 + 1] = F::random_range(&mut self.rng, F::ZERO, F::from_f32(1.0));
+            let score = self.evaluate_position(&candidate);
+
+            let delta = score.1 - current_score.1;
+            let accept = delta <= 0.0
+                || (temperature > 0.0 && self.rng.random::<f32>() < (-delta / temperature).exp());
+            if accept {
+                if selection.is_better(score, best_score) {
+                    best = Some(candidate.clone());
+                    best_score = score;
+                }
+                current = candidate;
+                current_score = score;
+            }
+
+            temperature *= params.cooling_rate;
+        }
+
+        let Some(position) = best else {
+            return false;
+        };
+        self.global_best_position = position;
+        (self.global_best_hard_penalty, self.global_best_fitness) = best_score;
+        true
+    }
+}
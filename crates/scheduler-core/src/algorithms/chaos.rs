@@ -0,0 +1,41 @@
+use std::time::Duration;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{float::Float, models::PSO};
+
+/// Faults injected into a run so tests can exercise the failure paths of the
+/// run lifecycle. Only compiled with the `chaos` feature; iterations are
+/// counted from 0.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
+#[serde(default)]
+pub struct FaultPlan {
+    /// Panic inside the optimizer at this iteration
+    pub panic_at: Option<usize>,
+    /// Sleep this long at the start of every iteration
+    pub slow_iteration_ms: Option<u64>,
+    /// Drop the progress sender and the live-parameter receiver at this
+    /// iteration, as if both channels had failed
+    pub close_channels_at: Option<usize>,
+}
+
+impl<F: Float> PSO<F> {
+    pub(super) async fn inject_faults(&mut self, iteration: usize) {
+        let Some(faults) = self.parameters.faults.clone() else {
+            return;
+        };
+
+        if let Some(ms) = faults.slow_iteration_ms {
+            tokio::time::sleep(Duration::from_millis(ms)).await;
+        }
+        if faults.close_channels_at == Some(iteration) {
+            println!("💥 Closing run channels at iteration {}", iteration);
+            self.status_tx = None;
+            self.params_rx = None;
+        }
+        if faults.panic_at == Some(iteration) {
+            panic!("injected panic at iteration {}", iteration);
+        }
+    }
+}
@@ -0,0 +1,667 @@
+use std::collections::HashMap;
+
+use super::par::*;
+use serde::Serialize;
+
+use super::{soft::SoftMessage, rooms::RoomCatalog, models::{ConflictCounts, ConstraintConfig, CourseRequest, ContractType, ExternalBooking, LecturerRequest, MissingPreferences, OptimizationRequest, OptimizedCourse, RoomRequest, ScheduleChecker, DEFAULT_PRIORITY, DOSEN_TBA, ScheduleMode, SlotConfig, TimePreferenceRequest }};
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictKind {
+    Lecturer,
+    StudentGroup,
+    Kelas,
+    ExamRule,
+    /// Class larger than its room; `jadwal_b` repeats `jadwal_a`
+    RoomCapacity,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ConflictMessage {
+    pub kind: ConflictKind,
+    pub jadwal_a: u32,
+    pub jadwal_b: u32,
+    // Prodi pemilik kedua jadwal (satu bila sama) dan penalti yang disumbangkan
+    pub prodi: Vec<u32>,
+    pub penalty: u32,
+    pub deskripsi: String,
+}
+
+#[derive(Serialize)]
+pub struct ConflictResult {
+    pub penalty: u32,
+    pub conflicts: Vec<ConflictMessage>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct PreferenceMessage {
+    pub id_jadwal: u32,
+    pub id_dosen: u32,
+    pub prodi: u32,
+    pub penalty: u32,
+    pub hari: u32,
+    pub jam_mulai: u32,
+    pub deskripsi: String,
+}
+
+#[derive(Serialize)]
+pub struct PreferenceResult {
+    pub penalty: u32,
+    pub violations: Vec<PreferenceMessage>,
+}
+
+/// Per-row metadata of one schedule, indexed like the schedule itself
+struct PairTable<'a> {
+    /// id_jadwal of the session leader; equal for cross-listed rows
+    session: Vec<u32>,
+    student_groups: Vec<&'a [u32]>,
+    priority: Vec<u8>,
+}
+
+/// Conflicts, preference violations, booking clashes and soft violations
+pub type ScheduleMessages = (Vec<ConflictMessage>, Vec<PreferenceMessage>, Vec<BookingMessage>, Vec<SoftMessage>);
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PreferenceWarningKind {
+    /// Lecturer teaches but has no preference row
+    MissingRow,
+    /// Preference row for a lecturer without courses
+    UnusedRow,
+    /// Contract whose availability has no slot in the Senin - Jumat week
+    ContractWithoutSlot,
+}
+
+#[derive(Serialize)]
+pub struct PreferenceWarning {
+    pub kind: PreferenceWarningKind,
+    pub id_dosen: u32,
+    pub deskripsi: String,
+}
+
+#[derive(Serialize, Clone)]
+pub struct BookingMessage {
+    pub id_jadwal: u32,
+    pub prodi: u32,
+    pub penalty: u32,
+    pub ruangan: u32,
+    pub hari: u32,
+    pub deskripsi: String,
+}
+
+#[derive(Serialize)]
+pub struct BookingResult {
+    pub penalty: u32,
+    pub violations: Vec<BookingMessage>,
+}
+
+impl ScheduleChecker {
+    pub fn new(
+        time_preferences: Vec<TimePreferenceRequest>,
+        config: ConstraintConfig,
+        external_bookings: Vec<ExternalBooking>,
+    ) -> Self {
+        Self {
+            // Baris preferensi untuk dosen TBA diabaikan; tidak ada dosen nyata di baliknya
+            time_preferences: time_preferences
+            .into_iter()
+            .filter(|p| p.id_dosen != DOSEN_TBA)
+            .map(|p| (p.id_dosen, p))
+            .collect(),
+            config,
+            external_bookings,
+            student_groups: HashMap::new(),
+            priorities: HashMap::new(),
+            cross_listed: HashMap::new(),
+            rooms: RoomCatalog::default(),
+        }
+    }
+
+    /// Registers the rooms to allocate from, with their capacities, and class
+    /// sizes so classes placed in a room too small for them are penalized
+    pub fn with_rooms(mut self, rooms: &[RoomRequest], sum_ruangan: Option<u32>, courses: &[CourseRequest]) -> Self {
+        self.rooms = RoomCatalog::new(rooms, sum_ruangan, courses);
+        self
+    }
+
+    /// Registers enrolled student groups so cohorts shared across kelas/prodi cannot overlap
+    pub fn with_student_groups(mut self, courses: &[CourseRequest]) -> Self {
+        self.student_groups = courses
+            .iter()
+            .filter(|c| !c.student_groups.is_empty())
+            .map(|c| (c.id_jadwal, c.student_groups.clone()))
+            .collect();
+        self
+    }
+
+    /// Checker for a whole request under an already resolved `config`:
+    /// preferences, bookings, student groups, cross-listing, priorities,
+    /// rooms, contracts and the missing-preference policy
+    pub fn for_request(request: &OptimizationRequest, config: ConstraintConfig) -> Self {
+        Self::new(request.time_preferences.clone(), config, request.external_bookings.clone())
+            .with_student_groups(&request.courses)
+            .with_cross_listing(&request.courses)
+            .with_priorities(&request.courses)
+            .with_rooms(&request.rooms, request.sum_ruangan, &request.courses)
+            .with_contracts(&request.lecturers)
+            .with_default_preferences(&request.courses)
+    }
+
+    /// Gives lecturers with a `contract_type` but no preference row the
+    /// availability of their contract; call before `with_default_preferences`
+    pub fn with_contracts(mut self, lecturers: &[LecturerRequest]) -> Self {
+        for lecturer in lecturers.iter().filter(|l| l.id_dosen != DOSEN_TBA) {
+            self.time_preferences
+                .entry(lecturer.id_dosen)
+                .or_insert_with(|| TimePreferenceRequest::from_contract(lecturer.id_dosen, lecturer.contract_type));
+        }
+        self
+    }
+
+    /// Gives lecturers who teach but have no preference row the row implied
+    /// by `missing_preferences`
+    pub fn with_default_preferences(mut self, courses: &[CourseRequest]) -> Self {
+        if self.config.missing_preferences == MissingPreferences::Unavailable {
+            for course in courses.iter().filter(|c| c.id_dosen != DOSEN_TBA) {
+                self.time_preferences
+                    .entry(course.id_dosen)
+                    .or_insert_with(|| TimePreferenceRequest::unavailable(course.id_dosen));
+            }
+        }
+        self
+    }
+
+    /// Lecturers without a preference row or contract, rows nobody teaches
+    /// under and contracts that leave a lecturer no slot; computed from the
+    /// request as sent
+    pub fn preference_warnings(
+        courses: &[CourseRequest],
+        preferences: &[TimePreferenceRequest],
+        lecturers: &[LecturerRequest],
+        policy: MissingPreferences,
+    ) -> Vec<PreferenceWarning> {
+        let mut teaching: Vec<u32> = courses.iter().map(|c| c.id_dosen).filter(|&d| d != DOSEN_TBA).collect();
+        teaching.sort_unstable();
+        teaching.dedup();
+        let mut with_row: Vec<u32> = preferences.iter().map(|p| p.id_dosen).collect();
+        with_row.sort_unstable();
+        with_row.dedup();
+
+        let assumed = match policy {
+            MissingPreferences::Available => "selalu bersedia",
+            MissingPreferences::Unavailable => "tidak bersedia di semua slot",
+        };
+        let contract = |id_dosen: u32| lecturers.iter().find(|l| l.id_dosen == id_dosen).map(|l| l.contract_type);
+        let missing = teaching.iter().filter(|d| with_row.binary_search(d).is_err() && contract(**d).is_none()).map(|&id_dosen| PreferenceWarning {
+            kind: PreferenceWarningKind::MissingRow,
+            id_dosen,
+            deskripsi: format!("Dosen {} tidak punya preferensi waktu; dianggap {}.", id_dosen, assumed),
+        });
+        let unused = with_row.iter().filter(|d| teaching.binary_search(d).is_err()).map(|&id_dosen| PreferenceWarning {
+            kind: PreferenceWarningKind::UnusedRow,
+            id_dosen,
+            deskripsi: format!("Preferensi dosen {} diabaikan karena tidak ada mata kuliah.", id_dosen),
+        });
+
+        let saturday = teaching
+            .iter()
+            .filter(|d| with_row.binary_search(d).is_err() && contract(**d) == Some(ContractType::SabtuOnly))
+            .map(|&id_dosen| PreferenceWarning {
+                kind: PreferenceWarningKind::ContractWithoutSlot,
+                id_dosen,
+                deskripsi: format!("Dosen {} berkontrak sabtu saja, padahal jadwal hanya Senin - Jumat; semua mata kuliahnya melanggar preferensi.", id_dosen),
+            });
+
+        missing.chain(unused).chain(saturday).collect()
+    }
+
+    /// Registers course priority tiers so clashes involving low-priority
+    /// courses cost less and the swarm pushes conflicts onto them
+    pub fn with_priorities(mut self, courses: &[CourseRequest]) -> Self {
+        self.priorities = courses
+            .iter()
+            .filter(|c| c.priority != DEFAULT_PRIORITY)
+            .map(|c| (c.id_jadwal, c.priority))
+            .collect();
+        self
+    }
+
+    pub fn priority(&self, id_jadwal: u32) -> u8 {
+        self.priorities.get(&id_jadwal).copied().unwrap_or(DEFAULT_PRIORITY)
+    }
+
+    /// Clash weight scaled by the lower priority tier of the pair;
+    /// default-tier pairs keep the configured weight
+    fn clash_weight(weight: u32, tier_a: u8, tier_b: u8) -> u32 {
+        let tier = tier_a.min(tier_b) as u32;
+        weight * (tier + 1) / (DEFAULT_PRIORITY as u32 + 1)
+    }
+
+    /// Resolves the per-row lookups of the pairwise clash check once, so the
+    /// O(n²) loop reads flat arrays instead of hashing ids for every pair
+    fn pair_table<'a>(&'a self, schedule: &[OptimizedCourse]) -> PairTable<'a> {
+        let mut table = PairTable {
+            session: Vec::with_capacity(schedule.len()),
+            student_groups: Vec::with_capacity(schedule.len()),
+            priority: Vec::with_capacity(schedule.len()),
+        };
+        for course in schedule {
+            let id = course.id_jadwal;
+            table.session.push(self.cross_listed.get(&id).copied().unwrap_or(id));
+            table.student_groups.push(self.student_groups.get(&id).map_or(&[][..], Vec::as_slice));
+            table.priority.push(self.priority(id));
+        }
+        table
+    }
+
+    pub fn evaluate(&self, schedule: &[OptimizedCourse]) -> f32 {
+        let (hard, soft) = self.evaluate_split(schedule);
+        (hard + soft) as f32
+    }
+
+    /// Penalty split into hard constraints (clashes, bookings, room
+    /// capacity, exam rules) and soft ones (lecturer preferences, soft objectives)
+    pub fn evaluate_split(&self, schedule: &[OptimizedCourse]) -> (u32, u32) {
+        let fitness_a = self.detect_conflicts(schedule);
+        let fitness_b = self.check_room_capacity(schedule);
+        let fitness_c = self.check_external_bookings(schedule);
+        let fitness_d = self.check_soft_constraints(schedule);
+
+        // Preferensi pagi/malam mingguan tidak berlaku untuk jadwal ujian
+        match self.config.mode {
+            ScheduleMode::Weekly => (
+                fitness_a.penalty + fitness_b.penalty + fitness_c.penalty,
+                self.check_preferences(schedule).penalty + fitness_d.penalty,
+            ),
+            ScheduleMode::Exam => (
+                fitness_a.penalty + fitness_b.penalty + fitness_c.penalty + self.check_exam_rules(schedule).penalty,
+                fitness_d.penalty,
+            ),
+        }
+    }
+
+    pub fn evaluate_messages(
+        &self,
+        schedule: &[OptimizedCourse],
+    ) -> ScheduleMessages {
+        let mut conflict_result = self.detect_conflicts(schedule);
+        conflict_result.conflicts.extend(self.check_room_capacity(schedule).conflicts);
+        let booking_result = self.check_external_bookings(schedule);
+        let soft_result = self.check_soft_constraints(schedule);
+
+        let preference_violations = match self.config.mode {
+            ScheduleMode::Weekly => self.check_preferences(schedule).violations,
+            ScheduleMode::Exam => {
+                conflict_result.conflicts.extend(self.check_exam_rules(schedule).conflicts);
+                Vec::new()
+            }
+        };
+
+        (conflict_result.conflicts, preference_violations, booking_result.violations, soft_result.violations)
+    }
+
+    pub fn count_conflicts(&self, schedule: &[OptimizedCourse]) -> ConflictCounts {
+        let (conflicts, preferences, bookings, soft) = self.evaluate_messages(schedule);
+
+        let mut counts = ConflictCounts {
+            preference: preferences.len() as u32,
+            room_clash: bookings.len() as u32,
+            soft: soft.len() as u32,
+            ..ConflictCounts::default()
+        };
+        for conflict in conflicts {
+            match conflict.kind {
+                ConflictKind::Lecturer => counts.lecturer_clash += 1,
+                ConflictKind::StudentGroup | ConflictKind::Kelas => counts.kelas_clash += 1,
+                ConflictKind::ExamRule => counts.exam_rule += 1,
+                ConflictKind::RoomCapacity => counts.room_capacity += 1,
+            }
+        }
+
+        counts
+    }
+
+    // Detects scheduling conflicts such as overlapping classes or conflicting lecturers
+    pub fn detect_conflicts(&self, schedule: &[OptimizedCourse]) -> ConflictResult {
+        let mut penalty = 0;
+        let mut conflicts = Vec::new();
+
+        let min_break = self.config.slots.min_break;
+        let weights = &self.config.weights;
+        let table = self.pair_table(schedule);
+
+        for (i, a) in schedule.iter().enumerate() {
+            for (j, b) in schedule.iter().enumerate().skip(i + 1) {
+                if a.hari != b.hari || !Self::is_within_break(a, b, min_break) || table.session[i] == table.session[j] {
+                    continue;
+                }
+                let (tier_a, tier_b) = (table.priority[i], table.priority[j]);
+
+                if a.id_dosen == b.id_dosen && a.id_dosen != DOSEN_TBA && Self::is_overlap(a, b) {
+                    let weight = Self::clash_weight(weights.lecturer_conflict, tier_a, tier_b);
+                    penalty += weight;
+                    conflicts.push(ConflictMessage {
+                        kind: ConflictKind::Lecturer,
+                        jadwal_a: a.id_jadwal,
+                        jadwal_b: b.id_jadwal,
+                        prodi: Self::owning_prodi(a, b),
+                        penalty: weight,
+                        deskripsi: format!(
+                            "Konflik dosen yang sama: dosen {} di dua kelas berbeda.",
+                            a.id_dosen
+                        ),
+                    });
+                }
+
+                if let Some(&group) = table.student_groups[i].iter().find(|g| table.student_groups[j].contains(g)) {
+                    let weight = Self::clash_weight(weights.kelas_conflict, tier_a, tier_b);
+                    penalty += weight;
+                    conflicts.push(ConflictMessage {
+                        kind: ConflictKind::StudentGroup,
+                        jadwal_a: a.id_jadwal,
+                        jadwal_b: b.id_jadwal,
+                        prodi: Self::owning_prodi(a, b),
+                        penalty: weight,
+                        deskripsi: format!(
+                            "Konflik mahasiswa: kelompok {} terdaftar di dua jadwal bersamaan.",
+                            group
+                        ),
+                    });
+                } else if (a.prodi, a.semester, a.id_kelas) == (b.prodi, b.semester, b.id_kelas) {
+                    // Pasangan sudah lolos is_within_break; dengan min_break 0 artinya bentrok biasa
+                    let weight = Self::clash_weight(weights.kelas_conflict, tier_a, tier_b);
+                    penalty += weight;
+                    let deskripsi = if Self::is_overlap(a, b) {
+                        format!("Konflik kelas: kelas {} dijadwalkan di dua sesi bersamaan.", a.id_kelas)
+                    } else {
+                        format!("Jeda antar kelas kurang dari {} menit untuk kelas {}.", min_break, a.id_kelas)
+                    };
+                    conflicts.push(ConflictMessage {
+                        kind: ConflictKind::Kelas,
+                        jadwal_a: a.id_jadwal,
+                        jadwal_b: b.id_jadwal,
+                        prodi: Self::owning_prodi(a, b),
+                        penalty: weight,
+                        deskripsi,
+                    });
+                }
+            }
+        }
+
+        ConflictResult { penalty, conflicts }
+    }
+
+    /// Courses whose assigned room has fewer seats than students enrolled
+    pub fn check_room_capacity(&self, schedule: &[OptimizedCourse]) -> ConflictResult {
+        let weight = self.config.weights.room_capacity;
+        let conflicts: Vec<ConflictMessage> = schedule
+            .iter()
+            .filter(|course| course.ruangan != 0)
+            .filter_map(|course| {
+                let (students, seats) = self.rooms.shortfall(course.id_jadwal, course.ruangan)?;
+                Some(ConflictMessage {
+                    kind: ConflictKind::RoomCapacity,
+                    jadwal_a: course.id_jadwal,
+                    jadwal_b: course.id_jadwal,
+                    prodi: vec![course.prodi],
+                    penalty: weight,
+                    deskripsi: format!(
+                        "Ruangan {} hanya muat {} orang untuk {} mahasiswa.",
+                        course.ruangan, seats, students
+                    ),
+                })
+            })
+            .collect();
+
+        ConflictResult {
+            penalty: conflicts.len() as u32 * weight,
+            conflicts,
+        }
+    }
+
+    /// Prodi a violation between two courses is reported to
+    pub fn owning_prodi(a: &OptimizedCourse, b: &OptimizedCourse) -> Vec<u32> {
+        if a.prodi == b.prodi {
+            vec![a.prodi]
+        } else {
+            vec![a.prodi.min(b.prodi), a.prodi.max(b.prodi)]
+        }
+    }
+
+    /// Student group enrolled in both courses, if any
+    pub fn shared_student_group(&self, jadwal_a: u32, jadwal_b: u32) -> Option<u32> {
+        let groups_a = self.student_groups.get(&jadwal_a)?;
+        let groups_b = self.student_groups.get(&jadwal_b)?;
+        groups_a.iter().find(|g| groups_b.contains(g)).copied()
+    }
+
+    pub fn check_preferences(&self, schedule: &[OptimizedCourse]) -> PreferenceResult {
+        let violations: Vec<PreferenceMessage> = schedule.par_iter()
+            .filter_map(|course| {
+                let pref = self.time_preferences.get(&course.id_dosen)?;
+
+                let waktu_ok = Self::slot_allowed(pref, &self.config.slots, course.hari, course.jam_mulai);
+
+                if waktu_ok {
+                    None
+                } else {
+                    let waktu_str = if self.config.slots.is_pagi(course.jam_mulai) { "pagi" } else { "malam" };
+                    let hari_str = match course.hari {
+                        1 => "Senin",
+                        2 => "Selasa",
+                        3 => "Rabu",
+                        4 => "Kamis",
+                        5 => "Jumat",
+                        _ => "Hari Tidak Dikenal",
+                    };
+
+                    Some(PreferenceMessage {
+                        id_jadwal: course.id_jadwal,
+                        id_dosen: course.id_dosen,
+                        prodi: course.prodi,
+                        penalty: self.config.weights.preference,
+                        hari: course.hari,
+                        jam_mulai: course.jam_mulai,
+                        deskripsi: format!(
+                            "Dosen {} tidak prefer jadwal {} {}.",
+                            course.id_dosen, hari_str, waktu_str
+                        ),
+                    })
+                }
+            })
+            .collect();
+
+        PreferenceResult {
+            penalty: (violations.len() as u32) * self.config.weights.preference,
+            violations,
+        }
+    }
+
+    /// First external booking occupying the course's room at the same time, if any
+    pub fn booking_clash(&self, course: &OptimizedCourse) -> Option<&ExternalBooking> {
+        // Ruangan 0 berarti belum dialokasikan
+        if course.ruangan == 0 {
+            return None;
+        }
+
+        self.external_bookings.iter().find(|booking| {
+            booking.ruangan == course.ruangan
+                && booking.hari == course.hari
+                && course.jam_mulai < booking.jam_akhir
+                && booking.jam_mulai < course.jam_akhir
+        })
+    }
+
+    pub fn check_external_bookings(&self, schedule: &[OptimizedCourse]) -> BookingResult {
+        let violations: Vec<BookingMessage> = schedule
+            .iter()
+            .filter_map(|course| {
+                let booking = self.booking_clash(course)?;
+                Some(BookingMessage {
+                    id_jadwal: course.id_jadwal,
+                    prodi: course.prodi,
+                    penalty: self.config.weights.room_conflict,
+                    ruangan: course.ruangan,
+                    hari: course.hari,
+                    deskripsi: format!(
+                        "Ruangan {} sudah dipesan di luar sistem{}.",
+                        booking.ruangan,
+                        booking.keterangan.as_deref().map(|k| format!(" ({})", k)).unwrap_or_default()
+                    ),
+                })
+            })
+            .collect();
+
+        BookingResult {
+            penalty: (violations.len() as u32) * self.config.weights.room_conflict,
+            violations,
+        }
+    }
+
+    /// Lecturers without a preference row accept every slot
+    pub fn is_preferred(&self, id_dosen: u32, hari: u32, jam_mulai: u32) -> bool {
+        self.time_preferences
+            .get(&id_dosen)
+            .is_none_or(|pref| Self::slot_allowed(pref, &self.config.slots, hari, jam_mulai))
+    }
+
+    fn slot_allowed(pref: &TimePreferenceRequest, slots: &SlotConfig, hari: u32, jam_mulai: u32) -> bool {
+        if slots.is_pagi(jam_mulai) {
+            match hari {
+                1 => pref.senin_pagi,
+                2 => pref.selasa_pagi,
+                3 => pref.rabu_pagi,
+                4 => pref.kamis_pagi,
+                5 => pref.jumat_pagi,
+                _ => false,
+            }
+        } else {
+            match hari {
+                1 => pref.senin_malam,
+                2 => pref.selasa_malam,
+                3 => pref.rabu_malam,
+                4 => pref.kamis_malam,
+                5 => pref.jumat_malam,
+                _ => false,
+            }
+        }
+    }
+
+    #[inline]
+    pub fn is_overlap(a: &OptimizedCourse, b: &OptimizedCourse) -> bool {
+        a.jam_mulai < b.jam_akhir && b.jam_mulai < a.jam_akhir
+    }
+
+    /// Overlap after padding both sessions with `gap` minutes; same as
+    /// `is_overlap` when `gap` is 0
+    #[inline]
+    pub fn is_within_break(a: &OptimizedCourse, b: &OptimizedCourse, gap: u32) -> bool {
+        a.jam_mulai < b.jam_akhir + gap && b.jam_mulai < a.jam_akhir + gap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn request() -> OptimizationRequest {
+        let course = |id_jadwal: u32, id_dosen: u32| {
+            json!({
+                "id_jadwal": id_jadwal, "id_matkul": id_jadwal, "id_dosen": id_dosen, "id_waktu": 1,
+                "id_kelas": id_jadwal, "semester": 1, "sks": 2, "prodi": 1
+            })
+        };
+        let mut courses = vec![course(1, 10), course(2, 20), course(3, 30)];
+        courses[0]["student_groups"] = json!([7]);
+        courses[0]["priority"] = json!(0);
+        courses[1]["cross_listed_with"] = json!([3]);
+        serde_json::from_value(json!({
+            "courses": courses,
+            "parameters": null,
+            "config": null,
+            "mode": null,
+            "per_day_sks_rule": null,
+            "kelas_waktu_policy": null,
+            "time_preferences": [],
+            "lecturers": [{ "id_dosen": 20, "contract_type": "pagi_only" }],
+            "rooms": [{ "ruangan": 101 }, { "ruangan": 102 }],
+            "sum_ruangan": null
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn for_request_registers_the_whole_request() {
+        let checker = ScheduleChecker::for_request(&request(), ConstraintConfig::default());
+
+        assert_eq!(checker.student_groups.get(&1), Some(&vec![7]));
+        assert_eq!(checker.priority(1), 0);
+        assert_eq!(checker.priority(2), DEFAULT_PRIORITY);
+        assert!(checker.same_session(2, 3));
+        assert_eq!(checker.rooms.ids(), [101, 102]);
+        // Kontrak pagi saja; dosen lain tetap tanpa baris (missing_preferences = available)
+        assert!(checker.time_preferences[&20].senin_pagi);
+        assert!(!checker.time_preferences[&20].senin_malam);
+        assert!(!checker.time_preferences.contains_key(&10));
+    }
+
+    #[test]
+    fn for_request_applies_the_missing_preference_policy_after_contracts() {
+        let config = ConstraintConfig { missing_preferences: MissingPreferences::Unavailable, ..ConstraintConfig::default() };
+        let checker = ScheduleChecker::for_request(&request(), config);
+
+        assert!(!checker.time_preferences[&10].senin_pagi);
+        assert!(!checker.time_preferences[&30].senin_pagi);
+        assert!(checker.time_preferences[&20].senin_pagi);
+    }
+
+    // Dua sesi kelas yang sama (prodi 1, semester 1, kelas 1) di hari 1, dosen berbeda
+    fn same_kelas(first: (u32, u32), second: (u32, u32)) -> Vec<OptimizedCourse> {
+        [(1, first), (2, second)]
+            .into_iter()
+            .map(|(id, (jam_mulai, jam_akhir))| OptimizedCourse {
+                id_jadwal: id,
+                id_matkul: id,
+                id_dosen: id,
+                id_kelas: 1,
+                id_waktu: 1,
+                hari: 1,
+                jam_mulai,
+                jam_akhir,
+                ruangan: 0,
+                semester: 1,
+                sks: 2,
+                prodi: 1,
+                dosen_tba: false,
+                locked: false,
+            })
+            .collect()
+    }
+
+    fn kelas_conflicts(checker: &ScheduleChecker, schedule: &[OptimizedCourse]) -> usize {
+        let result = checker.detect_conflicts(schedule);
+        result.conflicts.iter().filter(|c| c.kind == ConflictKind::Kelas).count()
+    }
+
+    #[test]
+    fn detect_conflicts_reports_same_kelas_overlap_without_min_break() {
+        let checker = ScheduleChecker::for_request(&request(), ConstraintConfig::default());
+        assert_eq!(checker.config.slots.min_break, 0);
+
+        assert_eq!(kelas_conflicts(&checker, &same_kelas((480, 560), (520, 600))), 1);
+        assert_eq!(kelas_conflicts(&checker, &same_kelas((480, 560), (560, 640))), 0);
+    }
+
+    #[test]
+    fn detect_conflicts_enforces_min_break_for_same_kelas() {
+        let mut config = ConstraintConfig::default();
+        config.slots.min_break = 10;
+        let checker = ScheduleChecker::for_request(&request(), config);
+
+        assert_eq!(kelas_conflicts(&checker, &same_kelas((480, 560), (520, 600))), 1);
+        assert_eq!(kelas_conflicts(&checker, &same_kelas((480, 560), (565, 645))), 1);
+        assert_eq!(kelas_conflicts(&checker, &same_kelas((480, 560), (570, 650))), 0);
+    }
+}
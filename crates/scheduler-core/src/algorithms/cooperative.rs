@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+
+use rand::Rng;
+use super::par::*;
+
+use super::{
+    float::Float,
+    ga::Algorithm,
+    models::{Particle, SubSwarm, PSO},
+};
+
+impl<F: Float> PSO<F> {
+    /// Whether this run is decomposed into sub-swarms; never for the GA
+    pub fn is_cooperative(&self) -> bool {
+        self.algorithm == Algorithm::Pso
+            && self
+                .parameters
+                .cooperative
+                .as_ref()
+                .is_some_and(|config| self.courses.len() >= config.min_courses)
+    }
+
+    /// Splits the position vector along decoder groups (prodi, semester, kelas,
+    /// waktu) so courses that compete for the same days stay in one sub-swarm,
+    /// then seeds the shared context with a random position.
+    pub(super) fn initialize_sub_swarms(&mut self) {
+        let Some(config) = self.parameters.cooperative.clone() else {
+            return;
+        };
+
+        let mut groups: BTreeMap<(u32, u32, u32, u32), Vec<usize>> = BTreeMap::new();
+        for (i, course) in self.courses.iter().enumerate() {
+            let id_waktu = self.checker.config.effective_waktu(course);
+            groups
+                .entry((course.prodi, course.semester, course.id_kelas, id_waktu))
+                .or_default()
+                .push(i);
+        }
+
+        // Grup kecil digabung agar jumlah sub-swarm tetap wajar
+        let mut parts: Vec<Vec<usize>> = vec![Vec::new()];
+        for courses in groups.into_values() {
+            let current = parts.last_mut().unwrap();
+            if !current.is_empty() && current.len() + courses.len() > config.courses_per_swarm.max(1) {
+                parts.push(Vec::new());
+            }
+            parts.last_mut().unwrap().extend(courses);
+        }
+
+        let rng = &mut self.rng;
+        let velocity_bound = self.parameters.velocity_init.bound(self.parameters.velocity_scale);
+        self.sub_swarms = parts
+            .into_iter()
+            .filter(|courses| !courses.is_empty())
+            .map(|courses| {
+                let dims: Vec<usize> = courses.iter().flat_map(|&i| [i * 2, i * 2 + 1]).collect();
+                let particles = (0..config.sub_swarm_size.max(1))
+                    .map(|_| Particle::new(dims.len(), rng.random(), velocity_bound))
+                    .collect();
+                SubSwarm { dims, particles }
+            })
+            .collect();
+
+        let dimension = self.courses.len() * 2;
+        let context: Vec<F> = match self.initial_positions.first() {
+            Some(position) if position.len() == dimension => position.iter().map(|&v| F::from_f32(v)).collect(),
+            _ => (0..dimension).map(|_| F::random_range(&mut self.rng, F::ZERO, F::from_f32(1.0))).collect(),
+        };
+        let (hard, fitness) = self.evaluate_position(&context);
+        self.global_best_position = context;
+        self.global_best_hard_penalty = hard;
+        self.global_best_fitness = fitness;
+    }
+
+    /// One CCPSO iteration: every sub-swarm in turn evaluates its particles
+    /// inside the current context and writes its best improvement back, so
+    /// later sub-swarms already see it.
+    pub(super) fn cooperative_step(&mut self) {
+        let params = self.parameters.clone();
+        let inertia = self.inertia;
+        let mut sub_swarms = std::mem::take(&mut self.sub_swarms);
+
+        for swarm in &mut sub_swarms {
+            let context = &self.global_best_position;
+            let this = &*self;
+            swarm.particles.par_iter_mut().for_each(|particle| {
+                let mut position = context.clone();
+                for (&dim, &value) in swarm.dims.iter().zip(&particle.position) {
+                    position[dim] = value;
+                }
+                let (hard, fitness) = this.evaluate_position(&position);
+                particle.hard_penalty = hard;
+                particle.fitness = fitness;
+                particle.update_personal_best(params.selection);
+            });
+
+            let best = swarm
+                .particles
+                .iter()
+                .filter(|p| {
+                    params
+                        .selection
+                        .is_better((p.hard_penalty, p.fitness), (self.global_best_hard_penalty, self.global_best_fitness))
+                })
+                .min_by(|a, b| a.fitness.total_cmp(&b.fitness));
+            if let Some(best) = best {
+                for (&dim, &value) in swarm.dims.iter().zip(&best.position) {
+                    self.global_best_position[dim] = value;
+                }
+                self.global_best_hard_penalty = best.hard_penalty;
+                self.global_best_fitness = best.fitness;
+            }
+
+            let gbest: Vec<F> = swarm.dims.iter().map(|&dim| self.global_best_position[dim]).collect();
+            swarm.particles.par_iter_mut().for_each(|particle| {
+                particle.update_velocity(&gbest, inertia, params.cognitive_weight, params.social_weight);
+                particle.update_position();
+            });
+        }
+
+        self.sub_swarms = sub_swarms;
+    }
+
+    /// (hard penalty, fitness at the current hard factor) of a full position
+    pub(super) fn evaluate_position(&self, position: &[F]) -> (f32, f32) {
+        let schedule = PSO::position_to_schedule(position, &self.courses, &self.checker.config);
+        let (hard, soft) = self.checker.evaluate_split(&schedule);
+        (hard as f32, hard as f32 * self.hard_factor + soft as f32)
+    }
+}
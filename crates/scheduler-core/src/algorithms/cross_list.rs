@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use super::models::{CourseRequest, OptimizedCourse, ScheduleChecker};
+
+/// Maps every cross-listed row to the row that is actually scheduled: the
+/// first of its set in request order. `cross_listed_with` is symmetric and
+/// transitive; ids that are not in `courses` are ignored.
+pub fn leaders(courses: &[CourseRequest]) -> HashMap<u32, u32> {
+    if courses.iter().all(|c| c.cross_listed_with.is_empty()) {
+        return HashMap::new();
+    }
+
+    let index: HashMap<u32, usize> = courses.iter().enumerate().map(|(i, c)| (c.id_jadwal, i)).collect();
+    let mut parent: Vec<usize> = (0..courses.len()).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    for (i, course) in courses.iter().enumerate() {
+        for other in &course.cross_listed_with {
+            let Some(&j) = index.get(other) else {
+                continue;
+            };
+            let (a, b) = (root(&mut parent, i), root(&mut parent, j));
+            // Akar selalu baris paling awal agar pemimpinnya stabil
+            parent[a.max(b)] = a.min(b);
+        }
+    }
+
+    (0..courses.len())
+        .filter_map(|i| {
+            let leader = root(&mut parent, i);
+            (leader != i).then(|| (courses[i].id_jadwal, courses[leader].id_jadwal))
+        })
+        .collect()
+}
+
+/// Gives every follower in `schedule` the day, time and room of its leader
+pub fn sync_followers(schedule: &mut [OptimizedCourse], leaders: &HashMap<u32, u32>) {
+    if leaders.is_empty() {
+        return;
+    }
+    let slots: HashMap<u32, (u32, u32, u32, u32)> = schedule
+        .iter()
+        .map(|c| (c.id_jadwal, (c.hari, c.jam_mulai, c.jam_akhir, c.ruangan)))
+        .collect();
+    for course in schedule.iter_mut() {
+        if let Some((hari, jam_mulai, jam_akhir, ruangan)) = leaders.get(&course.id_jadwal).and_then(|l| slots.get(l)) {
+            course.hari = *hari;
+            course.jam_mulai = *jam_mulai;
+            course.jam_akhir = *jam_akhir;
+            course.ruangan = *ruangan;
+        }
+    }
+}
+
+impl ScheduleChecker {
+    /// Registers cross-listed sets so their rows never clash with each other
+    pub fn with_cross_listing(mut self, courses: &[CourseRequest]) -> Self {
+        self.cross_listed = leaders(courses);
+        self
+    }
+
+    /// Both rows are the same physical session
+    pub fn same_session(&self, jadwal_a: u32, jadwal_b: u32) -> bool {
+        if self.cross_listed.is_empty() {
+            return false;
+        }
+        let leader = |id| self.cross_listed.get(&id).copied().unwrap_or(id);
+        leader(jadwal_a) == leader(jadwal_b)
+    }
+
+    /// Removes follower rows so repair, compaction and room assignment only
+    /// see one row per session; put them back with `merge_cross_listed`
+    pub fn split_cross_listed(&self, schedule: &mut Vec<OptimizedCourse>) -> Vec<OptimizedCourse> {
+        if self.cross_listed.is_empty() {
+            return Vec::new();
+        }
+        let (followers, leaders) = schedule.drain(..).partition(|c| self.cross_listed.contains_key(&c.id_jadwal));
+        *schedule = leaders;
+        followers
+    }
+
+    /// Re-adds followers at their leader's slot. Returns the followers whose
+    /// leader is no longer in the schedule.
+    pub fn merge_cross_listed(&self, schedule: &mut Vec<OptimizedCourse>, followers: Vec<OptimizedCourse>) -> Vec<OptimizedCourse> {
+        let mut orphans = Vec::new();
+        for follower in followers {
+            if schedule.iter().any(|c| Some(&c.id_jadwal) == self.cross_listed.get(&follower.id_jadwal)) {
+                schedule.push(follower);
+            } else {
+                orphans.push(follower);
+            }
+        }
+        sync_followers(schedule, &self.cross_listed);
+        orphans
+    }
+}
@@ -0,0 +1,432 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+
+use super::{
+    cross_list,
+    float::Float,
+    models::{ConstraintConfig, CourseRequest, OptimizedCourse, ScheduleMode, PSO},
+};
+
+// (prodi, semester, id_kelas, id_waktu)
+type GroupKey = (u32, u32, u32, u32);
+// (prodi, semester, id_kelas, id_waktu, hari)
+type DayKey = (u32, u32, u32, u32, u32);
+
+/// A course placed on a day, still carrying the position value that orders
+/// it within that day
+pub struct DayPlacement<P> {
+    pub key: DayKey,
+    pub time_order: P,
+    pub course: OptimizedCourse,
+}
+
+/// Courses of one kelas on one day in the order they are held
+pub struct DaySequence {
+    pub id_waktu: u32,
+    pub courses: Vec<OptimizedCourse>,
+}
+
+/// Turns a particle position into a schedule in separate stages:
+/// day assignment, intra-day sequencing and time materialization. Rooms are
+/// assigned after optimization (`rooms::assign_rooms`). Locked courses keep
+/// their slot whatever the position says; their kelas' other courses fill
+/// the rest.
+pub struct ScheduleBuilder<'a> {
+    courses: &'a [CourseRequest],
+    config: &'a ConstraintConfig,
+    // id_jadwal pengikut lintas kelas -> id_jadwal pemimpinnya
+    leaders: HashMap<u32, u32>,
+    // id_jadwal -> durasi (menit), sudah termasuk override praktikum
+    durations: HashMap<u32, u32>,
+    // Mata kuliah dengan slot terkunci, sudah di slotnya
+    locked: Vec<OptimizedCourse>,
+}
+
+impl<'a> ScheduleBuilder<'a> {
+    pub fn new(courses: &'a [CourseRequest], config: &'a ConstraintConfig) -> Self {
+        let leaders = cross_list::leaders(courses);
+        let durations = courses
+            .iter()
+            .filter(|course| !leaders.contains_key(&course.id_jadwal))
+            .map(|course| (course.id_jadwal, config.duration(course)))
+            .collect();
+        let locked = courses
+            .iter()
+            .filter(|course| !leaders.contains_key(&course.id_jadwal))
+            .filter_map(|course| OptimizedCourse::from_lock(course, config))
+            .collect();
+
+        ScheduleBuilder { courses, config, leaders, durations, locked }
+    }
+
+    /// All decoding stages in order
+    pub fn build<P: Float>(&self, position: &[P]) -> Vec<OptimizedCourse> {
+        if self.config.mode == ScheduleMode::Exam {
+            let mut schedule = PSO::position_to_exam_schedule(position, self.courses, &self.config.exam);
+            for course in &mut schedule {
+                if let Some(locked) = self.locked.iter().find(|l| l.id_jadwal == course.id_jadwal) {
+                    *course = locked.clone();
+                }
+            }
+            cross_list::sync_followers(&mut schedule, &self.leaders);
+            return schedule;
+        }
+
+        let (placements, followers) = self.assign_days(position);
+        let mut schedule = self.materialize_times(self.sequence(placements));
+        schedule.extend(self.locked.iter().cloned());
+        schedule.extend(followers);
+        cross_list::sync_followers(&mut schedule, &self.leaders);
+        schedule
+    }
+
+    /// Spreads each kelas' courses over the week in order of their day value,
+    /// moving to the next day once `max_sks` (counting locked courses) is
+    /// reached. Cross-listed followers are returned separately; they copy
+    /// their leader's slot. Locked courses are left out.
+    pub fn assign_days<P: Float>(&self, position: &[P]) -> (Vec<DayPlacement<P>>, Vec<OptimizedCourse>) {
+        let mut grouped: HashMap<GroupKey, Vec<(P, P, OptimizedCourse)>> = HashMap::new();
+        let mut followers = Vec::new();
+
+        for (i, course) in self.courses.iter().enumerate() {
+            let idx = i * 2;
+
+            if idx + 1 >= position.len() {
+                break;
+            }
+
+            let id_waktu = self.config.effective_waktu(course);
+            let opt_course = OptimizedCourse {
+                id_waktu,
+                ..OptimizedCourse::from_request(course)
+            };
+            if self.leaders.contains_key(&course.id_jadwal) {
+                followers.push(opt_course);
+                continue;
+            }
+            if course.locked.is_some() {
+                continue;
+            }
+
+            let key = (course.prodi, course.semester, course.id_kelas, id_waktu);
+            grouped.entry(key).or_default().push((position[idx], position[idx + 1], opt_course));
+        }
+
+        let mut placements = Vec::with_capacity(self.courses.len());
+
+        for (key, mut sorted) in grouped {
+            sorted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+            let max_sks = self.config.per_day_sks_rule.max_sks(sorted.len(), self.config.max_sks_per_day);
+            let mut sks_per_day = [0u32; 5];
+            for locked in self.locked.iter().filter(|c| (c.prodi, c.semester, c.id_kelas, c.id_waktu) == key) {
+                if let Some(day) = sks_per_day.get_mut(locked.hari.wrapping_sub(1) as usize) {
+                    *day += locked.sks;
+                }
+            }
+            let mut current_day = 0;
+
+            for (_, time_order, mut course) in sorted {
+                while current_day < 5 {
+                    if sks_per_day[current_day] + course.sks <= max_sks {
+                        course.hari = current_day as u32 + 1;
+                        sks_per_day[current_day] += course.sks;
+                        break;
+                    }
+                    current_day += 1;
+                }
+
+                if course.hari == 0 {
+                    course.hari = 5;
+                }
+
+                placements.push(DayPlacement {
+                    key: (course.prodi, course.semester, course.id_kelas, course.id_waktu, course.hari),
+                    time_order,
+                    course,
+                });
+            }
+        }
+
+        (placements, followers)
+    }
+
+    /// Orders each kelas' courses within a day by their time value
+    pub fn sequence<P: Float>(&self, placements: Vec<DayPlacement<P>>) -> Vec<DaySequence> {
+        let mut by_day: HashMap<DayKey, Vec<(P, OptimizedCourse)>> = HashMap::new();
+        for placement in placements {
+            by_day.entry(placement.key).or_default().push((placement.time_order, placement.course));
+        }
+
+        by_day
+            .into_iter()
+            .map(|((_, _, _, id_waktu, _), mut entries)| {
+                entries.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+                DaySequence {
+                    id_waktu,
+                    courses: entries.into_iter().map(|(_, course)| course).collect(),
+                }
+            })
+            .collect()
+    }
+
+    /// Gives every sequenced course its start and end time back to back
+    /// inside its session window, leaving `min_break` between courses,
+    /// skipping past locked courses of the same kelas and wrapping to the
+    /// window start when a course would overrun it
+    pub fn materialize_times(&self, sequences: Vec<DaySequence>) -> Vec<OptimizedCourse> {
+        let mut schedule = Vec::with_capacity(self.courses.len());
+
+        for DaySequence { id_waktu, courses } in sequences {
+            let (start, end) = self.config.slots.window(id_waktu);
+            let mut current_time = start;
+
+            for mut course in courses {
+                let duration = self.durations[&course.id_jadwal];
+                let kelas = (course.prodi, course.semester, course.id_kelas, course.hari);
+                course.jam_mulai = self.next_start(current_time, duration, kelas, (start, end));
+                course.jam_akhir = course.jam_mulai + duration;
+                current_time = course.jam_akhir + self.config.slots.min_break;
+
+                schedule.push(course);
+            }
+        }
+
+        schedule
+    }
+
+    /// Start of a course of `duration` minutes following a course of its
+    /// kelas (prodi, semester, id_kelas, hari) that left off at `current_time`
+    fn next_start(&self, mut current_time: u32, duration: u32, kelas: (u32, u32, u32, u32), window: (u32, u32)) -> u32 {
+        let min_break = self.config.slots.min_break;
+        let locked: Vec<&OptimizedCourse> = self
+            .locked
+            .iter()
+            .filter(|c| (c.prodi, c.semester, c.id_kelas, c.hari) == kelas)
+            .collect();
+        // Lompati sesi terkunci yang akan tertimpa
+        let skip_locked = |mut time: u32| {
+            while let Some(blocking) = locked
+                .iter()
+                .find(|c| time < c.jam_akhir + min_break && c.jam_mulai < time + duration + min_break)
+            {
+                time = blocking.jam_akhir + min_break;
+            }
+            time
+        };
+
+        current_time = skip_locked(current_time);
+        if current_time + duration > window.1 {
+            // Setelah kembali ke awal jendela, sesi terkunci di sana juga harus dilewati
+            current_time = skip_locked(window.0);
+        }
+        current_time
+    }
+
+    /// Inverse of `build` for weekly schedules: a position whose day values
+    /// order each kelas' courses by day and whose time values give the order
+    /// `materialize_times` must walk to land on their start times. Decoded
+    /// schedules come back unchanged; hand-made ones may be repacked where a
+    /// day was left below `max_sks` or times have gaps. Courses missing from
+    /// the schedule get random values.
+    pub fn encode(&self, schedule: &[OptimizedCourse], rng: &mut impl Rng) -> Vec<f32> {
+        let slots: HashMap<u32, (u32, u32)> = schedule.iter().map(|c| (c.id_jadwal, (c.hari, c.jam_mulai))).collect();
+        let mut position: Vec<f32> = (0..self.courses.len() * 2).map(|_| rng.random_range(0.0..1.0)).collect();
+
+        // (indeks mata kuliah, hari, jam mulai) per kelas
+        let mut grouped: HashMap<GroupKey, Vec<(usize, u32, u32)>> = HashMap::new();
+        for (i, course) in self.courses.iter().enumerate() {
+            if self.leaders.contains_key(&course.id_jadwal) || course.locked.is_some() {
+                continue;
+            }
+            if let Some(&(hari, jam_mulai)) = slots.get(&course.id_jadwal) {
+                let key = (course.prodi, course.semester, course.id_kelas, self.config.effective_waktu(course));
+                grouped.entry(key).or_default().push((i, hari, jam_mulai));
+            }
+        }
+
+        for ((prodi, semester, id_kelas, id_waktu), mut entries) in grouped {
+            entries.sort_by_key(|&(_, hari, jam_mulai)| (hari, jam_mulai));
+            let window = self.config.slots.window(id_waktu);
+            let total = entries.len() as f32;
+            let mut rank = 0;
+
+            for day in entries.chunk_by(|a, b| a.1 == b.1) {
+                // Urutan disimulasikan seperti materialize_times, supaya sesi yang
+                // dibungkus ke awal jendela tetap berada di belakang
+                let mut remaining = day.to_vec();
+                let mut current_time = window.0;
+                let mut within = 0;
+                while !remaining.is_empty() {
+                    let kelas = (prodi, semester, id_kelas, remaining[0].1);
+                    let duration = |i: usize| self.durations[&self.courses[i].id_jadwal];
+                    let next = remaining
+                        .iter()
+                        .position(|&(i, _, jam_mulai)| self.next_start(current_time, duration(i), kelas, window) == jam_mulai)
+                        .unwrap_or(0);
+                    let (i, _, jam_mulai) = remaining.remove(next);
+                    current_time = jam_mulai + duration(i) + self.config.slots.min_break;
+
+                    position[i * 2] = (rank as f32 + 0.5) / total;
+                    position[i * 2 + 1] = (within as f32 + 0.5) / day.len() as f32;
+                    rank += 1;
+                    within += 1;
+                }
+            }
+        }
+
+        position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::models::{CourseType, LockedSlot, PerDaySksRule, DEFAULT_PRIORITY};
+
+    fn course(id_jadwal: u32, sks: u32) -> CourseRequest {
+        CourseRequest {
+            id_jadwal,
+            id_matkul: id_jadwal,
+            id_dosen: id_jadwal,
+            id_waktu: 1,
+            id_kelas: 1,
+            semester: 1,
+            sks,
+            prodi: 1,
+            student_groups: vec![],
+            priority: DEFAULT_PRIORITY,
+            jenis: CourseType::Teori,
+            cross_listed_with: vec![],
+            jumlah_mahasiswa: 0,
+            locked: None,
+        }
+    }
+
+    fn fixed_cap(max_sks_per_day: u32) -> ConstraintConfig {
+        ConstraintConfig { max_sks_per_day, per_day_sks_rule: PerDaySksRule::FixedCap, ..ConstraintConfig::default() }
+    }
+
+    // Nilai hari naik sesuai urutan mata kuliah, nilai waktu 0.5
+    fn ascending(n: usize) -> Vec<f32> {
+        (0..n).flat_map(|i| [i as f32 / n as f32, 0.5]).collect()
+    }
+
+    fn days(placements: &[DayPlacement<f32>]) -> Vec<(u32, u32)> {
+        let mut days: Vec<_> = placements.iter().map(|p| (p.course.id_jadwal, p.course.hari)).collect();
+        days.sort_unstable();
+        days
+    }
+
+    #[test]
+    fn assign_days_moves_to_the_next_day_at_max_sks() {
+        let courses = [course(1, 3), course(2, 3), course(3, 3)];
+        let config = fixed_cap(6);
+        let builder = ScheduleBuilder::new(&courses, &config);
+
+        let (placements, followers) = builder.assign_days(&ascending(3));
+        assert!(followers.is_empty());
+        assert_eq!(days(&placements), [(1, 1), (2, 1), (3, 2)]);
+    }
+
+    #[test]
+    fn assign_days_puts_overflow_on_day_5() {
+        let courses: Vec<_> = (1..=7).map(|id| course(id, 3)).collect();
+        let config = fixed_cap(3);
+        let builder = ScheduleBuilder::new(&courses, &config);
+
+        let (placements, _) = builder.assign_days(&ascending(7));
+        assert_eq!(days(&placements), [(1, 1), (2, 2), (3, 3), (4, 4), (5, 5), (6, 5), (7, 5)]);
+    }
+
+    #[test]
+    fn assign_days_uses_the_configured_per_day_rule() {
+        let courses: Vec<_> = (1..=4).map(|id| course(id, 2)).collect();
+        let strict = ConstraintConfig::default();
+        let (placements, _) = ScheduleBuilder::new(&courses, &strict).assign_days(&ascending(4));
+        assert_eq!(days(&placements), [(1, 1), (2, 2), (3, 3), (4, 4)]);
+
+        let fixed = fixed_cap(4);
+        let (placements, _) = ScheduleBuilder::new(&courses, &fixed).assign_days(&ascending(4));
+        assert_eq!(days(&placements), [(1, 1), (2, 1), (3, 2), (4, 2)]);
+    }
+
+    #[test]
+    fn position_to_schedule_follows_the_config_cap() {
+        // Satu-satunya dekoder: PSO, repair, replay dan finalize memakai jalur ini
+        let courses: Vec<_> = (1..=4).map(|id| course(id, 2)).collect();
+        let position = ascending(4);
+        let days = |config: &ConstraintConfig| {
+            let mut days: Vec<_> = PSO::position_to_schedule(&position, &courses, config)
+                .iter()
+                .map(|c| (c.id_jadwal, c.hari))
+                .collect();
+            days.sort_unstable();
+            days
+        };
+
+        assert_eq!(days(&ConstraintConfig::default()), [(1, 1), (2, 2), (3, 3), (4, 4)]);
+        assert_eq!(days(&fixed_cap(6)), [(1, 1), (2, 1), (3, 1), (4, 2)]);
+        assert_eq!(days(&fixed_cap(2)), [(1, 1), (2, 2), (3, 3), (4, 4)]);
+    }
+
+    #[test]
+    fn sequence_orders_a_day_by_time_value() {
+        let courses = [course(1, 2), course(2, 2), course(3, 2)];
+        let config = fixed_cap(6);
+        let builder = ScheduleBuilder::new(&courses, &config);
+        // Semua di hari 1; nilai waktu terbalik dari urutan id
+        let position = [0.1, 0.9, 0.2, 0.5, 0.3, 0.1];
+
+        let (placements, _) = builder.assign_days(&position);
+        let sequences = builder.sequence(placements);
+        assert_eq!(sequences.len(), 1);
+        let order: Vec<u32> = sequences[0].courses.iter().map(|c| c.id_jadwal).collect();
+        assert_eq!(order, [3, 2, 1]);
+    }
+
+    fn materialized(config: &ConstraintConfig, courses: &[CourseRequest]) -> Vec<(u32, u32, u32)> {
+        let builder = ScheduleBuilder::new(courses, config);
+        let (placements, _) = builder.assign_days(&ascending(courses.len()));
+        let mut times: Vec<_> = builder
+            .materialize_times(builder.sequence(placements))
+            .iter()
+            .map(|c| (c.id_jadwal, c.jam_mulai, c.jam_akhir))
+            .collect();
+        times.sort_unstable();
+        times
+    }
+
+    #[test]
+    fn materialize_times_leaves_min_break_between_courses() {
+        let courses = [course(1, 2), course(2, 2)];
+        let mut config = fixed_cap(6);
+        config.slots.min_break = 10;
+
+        assert_eq!(materialized(&config, &courses), [(1, 480, 560), (2, 570, 650)]);
+    }
+
+    #[test]
+    fn materialize_times_wraps_to_the_window_start() {
+        // Jendela pagi 480-720 hanya muat dua sesi 80 menit dengan jeda 10
+        let courses = [course(1, 2), course(2, 2), course(3, 2)];
+        let mut config = fixed_cap(6);
+        config.slots.min_break = 10;
+
+        assert_eq!(materialized(&config, &courses), [(1, 480, 560), (2, 570, 650), (3, 480, 560)]);
+    }
+
+    #[test]
+    fn materialize_times_skips_locked_courses_after_wrapping() {
+        // Sesi terkunci 480-560 di hari 1; mata kuliah 4 kembali ke awal jendela
+        let mut courses = vec![course(1, 2), course(2, 1), course(3, 1), course(4, 2)];
+        courses[0].locked = Some(LockedSlot { hari: 1, jam_mulai: 480, ruangan: None });
+        let mut config = fixed_cap(6);
+        config.slots.min_break = 10;
+
+        let times = materialized(&config, &courses);
+        assert_eq!(times[2], (4, 570, 650));
+        assert!(times.iter().all(|&(_, mulai, _)| mulai >= 570));
+    }
+}
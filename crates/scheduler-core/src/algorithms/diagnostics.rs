@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    float::Float,
+    models::{Particle, PSO},
+};
+
+/// Swarm state after one iteration, recorded with
+/// `parameters.record_diagnostics` for research on the algorithm's behaviour
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct IterationDiagnostics {
+    pub iteration: usize,
+    /// Mean Euclidean distance of particles to the centroid of their swarm
+    pub diversity: f32,
+    /// Mean absolute velocity over all coordinates
+    pub mean_abs_velocity: f32,
+    /// Share of particles whose personal best improved in this iteration
+    pub pbest_improvement_rate: f32,
+    /// Inertia weight used for the particle moves of this iteration
+    #[serde(default)]
+    pub inertia_weight: f32,
+}
+
+impl<F: Float> PSO<F> {
+    /// Personal best fitness of every particle, main swarm first, to tell
+    /// afterwards which ones improved
+    pub(super) fn pbest_snapshot(&self) -> Vec<f32> {
+        self.swarms().flatten().map(|p| p.pbest_fitness).collect()
+    }
+
+    pub(super) fn record_diagnostics(&mut self, iteration: usize, pbest_before: &[f32]) {
+        let mut particles = 0usize;
+        let mut distance_sum = 0.0f64;
+        let mut velocity_sum = 0.0f64;
+        let mut coordinates = 0usize;
+        for swarm in self.swarms() {
+            let Some(first) = swarm.first() else {
+                continue;
+            };
+
+            let mut centroid = vec![0.0f64; first.position.len()];
+            for particle in swarm {
+                for (sum, v) in centroid.iter_mut().zip(&particle.position) {
+                    *sum += v.to_f32() as f64;
+                }
+            }
+            centroid.iter_mut().for_each(|sum| *sum /= swarm.len() as f64);
+
+            for particle in swarm {
+                let squared: f64 = centroid
+                    .iter()
+                    .zip(&particle.position)
+                    .map(|(c, v)| (v.to_f32() as f64 - c).powi(2))
+                    .sum();
+                distance_sum += squared.sqrt();
+                velocity_sum += particle.velocity.iter().map(|v| v.to_f32().abs() as f64).sum::<f64>();
+                coordinates += particle.velocity.len();
+            }
+            particles += swarm.len();
+        }
+
+        let improved = self
+            .swarms()
+            .flatten()
+            .zip(pbest_before)
+            .filter(|(p, &before)| p.pbest_fitness != before)
+            .count();
+
+        self.diagnostics.push(IterationDiagnostics {
+            iteration,
+            diversity: (distance_sum / particles.max(1) as f64) as f32,
+            mean_abs_velocity: (velocity_sum / coordinates.max(1) as f64) as f32,
+            pbest_improvement_rate: improved as f32 / particles.max(1) as f32,
+            inertia_weight: self.inertia,
+        });
+    }
+
+    /// Main swarm followed by the cooperative sub-swarms
+    fn swarms(&self) -> impl Iterator<Item = &Vec<Particle<F>>> {
+        std::iter::once(&self.particles).chain(self.sub_swarms.iter().map(|s| &s.particles))
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use super::{
+    checker::{ConflictKind, ConflictMessage, ConflictResult},
+    float::Float,
+    models::{CourseRequest, ExamConfig, OptimizedCourse, ScheduleChecker, PSO},
+};
+
+impl PSO {
+    /// Exam decoding: each course takes one (day, slot) pair directly from its
+    /// two position values, wrapped into [0, 1) so unbounded particles stay valid.
+    pub fn position_to_exam_schedule<P: Float>(
+        position: &[P],
+        courses: &[CourseRequest],
+        exam: &ExamConfig,
+    ) -> Vec<OptimizedCourse> {
+        let days = exam.days.max(1);
+        let slots = exam.slot_starts.len().max(1);
+
+        courses
+            .iter()
+            .enumerate()
+            .take_while(|(i, _)| i * 2 + 1 < position.len())
+            .map(|(i, course)| {
+                let day_idx = position[i * 2].bucket(days);
+                let slot_idx = position[i * 2 + 1].bucket(slots as u32) as usize;
+                let jam_mulai = exam.slot_starts.get(slot_idx.min(slots - 1)).copied().unwrap_or(480);
+
+                OptimizedCourse {
+                    hari: day_idx.min(days - 1) + 1,
+                    jam_mulai,
+                    jam_akhir: jam_mulai + exam.duration,
+                    ..OptimizedCourse::from_request(course)
+                }
+            })
+            .collect()
+    }
+}
+
+impl ScheduleChecker {
+    /// Per-kelas exam rules: no overlapping exams, at most `max_exams_per_day`
+    /// per day and at least `min_gap` minutes between consecutive exams.
+    pub fn check_exam_rules(&self, schedule: &[OptimizedCourse]) -> ConflictResult {
+        let exam = &self.config.exam;
+        let weights = &self.config.weights;
+
+        let mut by_kelas_day: HashMap<(u32, u32, u32, u32), Vec<&OptimizedCourse>> = HashMap::new();
+        for course in schedule {
+            by_kelas_day
+                .entry((course.prodi, course.semester, course.id_kelas, course.hari))
+                .or_default()
+                .push(course);
+        }
+
+        let mut penalty = 0;
+        let mut conflicts = Vec::new();
+
+        for ((prodi, _, id_kelas, hari), mut exams) in by_kelas_day {
+            exams.sort_by_key(|c| c.jam_mulai);
+
+            if exams.len() as u32 > exam.max_exams_per_day {
+                let excess = exams.len() as u32 - exam.max_exams_per_day;
+                penalty += excess * weights.exam_rule;
+                conflicts.push(ConflictMessage {
+                    kind: ConflictKind::ExamRule,
+                    jadwal_a: exams[0].id_jadwal,
+                    jadwal_b: exams[exams.len() - 1].id_jadwal,
+                    prodi: vec![prodi],
+                    penalty: excess * weights.exam_rule,
+                    deskripsi: format!(
+                        "Kelas {} memiliki {} ujian pada hari ke-{} (maks {}).",
+                        id_kelas,
+                        exams.len(),
+                        hari,
+                        exam.max_exams_per_day
+                    ),
+                });
+            }
+
+            for pair in exams.windows(2) {
+                let (a, b) = (pair[0], pair[1]);
+                if b.jam_mulai < a.jam_akhir {
+                    penalty += weights.kelas_conflict;
+                    conflicts.push(ConflictMessage {
+                        kind: ConflictKind::Kelas,
+                        jadwal_a: a.id_jadwal,
+                        jadwal_b: b.id_jadwal,
+                        prodi: vec![prodi],
+                        penalty: weights.kelas_conflict,
+                        deskripsi: format!("Kelas {} memiliki dua ujian bersamaan.", id_kelas),
+                    });
+                } else if b.jam_mulai - a.jam_akhir < exam.min_gap {
+                    penalty += weights.exam_rule;
+                    conflicts.push(ConflictMessage {
+                        kind: ConflictKind::ExamRule,
+                        jadwal_a: a.id_jadwal,
+                        jadwal_b: b.id_jadwal,
+                        prodi: vec![prodi],
+                        penalty: weights.exam_rule,
+                        deskripsi: format!(
+                            "Jeda ujian kelas {} hanya {} menit (min {}).",
+                            id_kelas,
+                            b.jam_mulai - a.jam_akhir,
+                            exam.min_gap
+                        ),
+                    });
+                }
+            }
+        }
+
+        ConflictResult { penalty, conflicts }
+    }
+}
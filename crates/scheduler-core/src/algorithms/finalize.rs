@@ -0,0 +1,60 @@
+use super::{
+    decode::ScheduleBuilder,
+    models::{OptimizationRequest, OptimizedCourse, ScheduleChecker, ScheduleMode},
+    repair::{RepairResult, ScheduleRepairer, UnplacedCourse},
+    rooms,
+};
+
+/// Decoded schedule after post-processing, with what could not be placed
+#[derive(Default)]
+pub struct FinalSchedule {
+    pub schedule: Vec<OptimizedCourse>,
+    pub unplaced: Vec<UnplacedCourse>,
+    /// Local repair run by require_feasible when PSO left hard conflicts
+    pub repair: Option<RepairResult>,
+    pub unassigned_rooms: Vec<u32>,
+    pub rooms_kept: Option<usize>,
+}
+
+/// Decoding plus the post-processing passes (priority unplacing, compaction,
+/// room assignment, room relabelling); shared by live runs and replay
+pub fn finalize_schedule(request: &OptimizationRequest, checker: &ScheduleChecker, position: &[f32]) -> FinalSchedule {
+    let config = &checker.config;
+    let repairer = ScheduleRepairer::new(checker.clone());
+    let builder = ScheduleBuilder::new(&request.courses, config);
+    let mut schedule = builder.build(position);
+    let followers = checker.split_cross_listed(&mut schedule);
+    let mut unplaced = repairer.unplace_low_priority(&mut schedule);
+    let require_feasible = request.parameters.as_ref().is_some_and(|p| p.require_feasible);
+    let repair = (require_feasible && config.mode == ScheduleMode::Weekly && checker.evaluate_split(&schedule).0 > 0)
+        .then(|| repairer.resolve_conflicts(&mut schedule));
+    if request.compact && config.mode == ScheduleMode::Weekly {
+        repairer.compact(&mut schedule);
+    }
+
+    let (unassigned_rooms, rooms_kept) = if checker.rooms.is_empty() {
+        (Vec::new(), None)
+    } else {
+        let unassigned = rooms::assign_rooms(&mut schedule, &checker.rooms, &request.external_bookings);
+        let kept = request
+            .previous_schedule
+            .as_ref()
+            .map(|previous| rooms::stabilize_rooms(&mut schedule, previous, &checker.rooms, &request.external_bookings));
+        (unassigned, kept)
+    };
+    for orphan in checker.merge_cross_listed(&mut schedule, followers) {
+        unplaced.push(UnplacedCourse {
+            id_jadwal: orphan.id_jadwal,
+            priority: checker.priority(orphan.id_jadwal),
+            deskripsi: format!("Jadwal {} ikut tidak ditempatkan bersama sesi lintas kelasnya.", orphan.id_jadwal),
+        });
+    }
+
+    FinalSchedule {
+        schedule,
+        unplaced,
+        repair,
+        unassigned_rooms,
+        rooms_kept,
+    }
+}
@@ -0,0 +1,98 @@
+use std::{
+    fmt::Debug,
+    ops::{Add, AddAssign, Mul, Sub},
+};
+
+use rand::{rngs::StdRng, Rng};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Coordinate type of particle positions and velocities, chosen per run
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Precision {
+    /// Half the swarm memory; the default
+    #[default]
+    F32,
+    /// Avoids rounding drift in long runs and fine weight tuning
+    F64,
+}
+
+impl Precision {
+    pub fn bytes(self) -> u64 {
+        match self {
+            Precision::F32 => 4,
+            Precision::F64 => 8,
+        }
+    }
+}
+
+/// What the optimizer needs from a coordinate type. Positions cross the API
+/// as f32, so both precisions decode stored positions identically.
+pub trait Float:
+    Copy + Send + Sync + Debug + PartialOrd + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + AddAssign + 'static
+{
+    const ZERO: Self;
+
+    fn from_f32(value: f32) -> Self;
+
+    fn to_f32(self) -> f32;
+
+    /// Uniform draw from [0, 1)
+    fn random(rng: &mut StdRng) -> Self;
+
+    /// Uniform draw from [low, high)
+    fn random_range(rng: &mut StdRng, low: Self, high: Self) -> Self;
+
+    /// Which of `count` equal parts of [0, 1) the value falls in after
+    /// wrapping it into that range; may equal `count` through rounding
+    fn bucket(self, count: u32) -> u32;
+}
+
+impl Float for f32 {
+    const ZERO: Self = 0.0;
+
+    fn from_f32(value: f32) -> Self {
+        value
+    }
+
+    fn to_f32(self) -> f32 {
+        self
+    }
+
+    fn random(rng: &mut StdRng) -> Self {
+        rng.random()
+    }
+
+    fn random_range(rng: &mut StdRng, low: Self, high: Self) -> Self {
+        rng.random_range(low..high)
+    }
+
+    fn bucket(self, count: u32) -> u32 {
+        (self.rem_euclid(1.0) * count as f32) as u32
+    }
+}
+
+impl Float for f64 {
+    const ZERO: Self = 0.0;
+
+    fn from_f32(value: f32) -> Self {
+        value as f64
+    }
+
+    fn to_f32(self) -> f32 {
+        self as f32
+    }
+
+    fn random(rng: &mut StdRng) -> Self {
+        rng.random()
+    }
+
+    fn random_range(rng: &mut StdRng, low: Self, high: Self) -> Self {
+        rng.random_range(low..high)
+    }
+
+    fn bucket(self, count: u32) -> u32 {
+        (self.rem_euclid(1.0) * count as f64) as u32
+    }
+}
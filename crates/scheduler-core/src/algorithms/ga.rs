@@ -0,0 +1,114 @@
+use std::cmp::Ordering;
+
+use rand::Rng;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{float::Float, models::PSO};
+
+/// Search strategy of a run. Both evolve the same positions, decoded by
+/// `position_to_schedule`, so results stay comparable and replayable.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Algorithm {
+    #[default]
+    Pso,
+    Ga,
+}
+
+/// Genetic algorithm settings; `swarm_size` is the population size
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(default)]
+pub struct GaParameters {
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub crossover_rate: f32,
+    /// Chance per course of redrawing its (day, time) genes; 1 / number of
+    /// courses when absent
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub mutation_rate: Option<f32>,
+    #[schemars(range(min = 1))]
+    pub tournament_size: usize,
+    /// Best individuals carried over unchanged to the next generation
+    pub elite: usize,
+}
+
+impl Default for GaParameters {
+    fn default() -> Self {
+        Self {
+            crossover_rate: 0.9,
+            mutation_rate: None,
+            tournament_size: 3,
+            elite: 2,
+        }
+    }
+}
+
+impl<F: Float> PSO<F> {
+    /// One generation over the evaluated population: the elite survive and
+    /// the rest is bred by tournament selection, uniform crossover of whole
+    /// (day, time) gene pairs and mutation that redraws a course's pair
+    pub(super) fn evolve_population(&mut self) {
+        let size = self.particles.len();
+        if size == 0 {
+            return;
+        }
+        let ga = self.parameters.ga.clone();
+        let selection = self.parameters.selection;
+        let genes = self.courses.len();
+        let mutation_rate = ga.mutation_rate.unwrap_or(1.0 / genes.max(1) as f32);
+
+        let mut ranked: Vec<usize> = (0..size).collect();
+        ranked.sort_by(|&a, &b| {
+            let (a, b) = (&self.particles[a], &self.particles[b]);
+            let (a, b) = ((a.hard_penalty, a.fitness), (b.hard_penalty, b.fitness));
+            if selection.is_better(a, b) {
+                Ordering::Less
+            } else if selection.is_better(b, a) {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        });
+        let mut rank = vec![0; size];
+        for (place, &index) in ranked.iter().enumerate() {
+            rank[index] = place;
+        }
+
+        let mut next: Vec<Vec<F>> = ranked
+            .iter()
+            .take(ga.elite.min(size))
+            .map(|&index| self.particles[index].position.clone())
+            .collect();
+        while next.len() < size {
+            let mut tournament = || {
+                (0..ga.tournament_size.max(1))
+                    .map(|_| self.rng.random_range(0..size))
+                    .min_by_key(|&index| rank[index])
+                    .unwrap_or(0)
+            };
+            let (a, b) = (tournament(), tournament());
+
+            let mut child = self.particles[a].position.clone();
+            let other = &self.particles[b].position;
+            if self.rng.random::<f32>() < ga.crossover_rate {
+                for gene in 0..genes {
+                    if self.rng.random_bool(0.5) {
+                        child[gene * 2] = other[gene * 2];
+                        child[gene * 2 + 1] = other[gene * 2 + 1];
+                    }
+                }
+            }
+            for gene in 0..genes {
+                if self.rng.random::<f32>() < mutation_rate {
+                    child[gene * 2] = F::random_range(&mut self.rng, F::ZERO, F::from_f32(1.0));
+                    child[gene * 2 + 1] = F::random_range(&mut self.rng, F::ZERO, F::from_f32(1.0));
+                }
+            }
+            next.push(child);
+        }
+
+        for (particle, position) in self.particles.iter_mut().zip(next) {
+            particle.position = position;
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+
+use super::models::{ConstraintConfig, CourseRequest, PreferenceImpactRequest, ScheduleChecker};
+
+// Di atas rasio ini dosen sebaiknya diminta melonggarkan preferensi
+const TIGHT_LOAD: f32 = 0.8;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum ImpactLevel {
+    Infeasible,
+    Tight,
+    Ok,
+}
+
+/// How much room a lecturer's preferences leave for their teaching load
+#[derive(Serialize, Debug)]
+pub struct LecturerImpact {
+    pub id_dosen: u32,
+    pub courses: usize,
+    pub sks: u32,
+    pub demanded_minutes: u32,
+    /// Preferred day/window pairs among the windows the courses are taught in
+    pub open_slots: usize,
+    pub total_slots: usize,
+    pub available_minutes: u32,
+    /// demanded / available; absent when nothing is available
+    pub load: Option<f32>,
+    pub level: ImpactLevel,
+}
+
+impl ScheduleChecker {
+    /// Checker for POST /preferences/impact: the request carries only courses
+    /// and preferences, so only cross-listing and the missing-preference
+    /// policy apply
+    pub fn for_impact(request: &PreferenceImpactRequest, config: ConstraintConfig) -> Self {
+        Self::new(request.time_preferences.clone(), config, Vec::new())
+            .with_cross_listing(&request.courses)
+            .with_default_preferences(&request.courses)
+    }
+
+    /// One entry per teaching lecturer, most constrained first. Only the
+    /// lecturer's own preferences are considered, not clashes with others.
+    pub fn preference_impact(&self, courses: &[CourseRequest]) -> Vec<LecturerImpact> {
+        let mut by_dosen: BTreeMap<u32, Vec<&CourseRequest>> = BTreeMap::new();
+        // Sesi lintas kelas hanya dihitung sekali
+        for course in courses.iter().filter(|c| !self.cross_listed.contains_key(&c.id_jadwal)) {
+            by_dosen.entry(course.id_dosen).or_default().push(course);
+        }
+
+        let slots = &self.config.slots;
+        let mut impacts: Vec<LecturerImpact> = by_dosen
+            .into_iter()
+            .map(|(id_dosen, courses)| {
+                let windows: BTreeSet<u32> = courses.iter().map(|c| self.config.effective_waktu(c)).collect();
+                let demanded_minutes = courses.iter().map(|c| self.config.duration(c)).sum();
+
+                let mut open_slots = 0;
+                let mut available_minutes = 0;
+                for &id_waktu in &windows {
+                    let (start, end) = slots.window(id_waktu);
+                    for hari in 1..=5 {
+                        if self.is_preferred(id_dosen, hari, start) {
+                            open_slots += 1;
+                            available_minutes += end.saturating_sub(start);
+                        }
+                    }
+                }
+
+                let load = (available_minutes > 0).then(|| demanded_minutes as f32 / available_minutes as f32);
+                let level = match load {
+                    Some(load) if load <= TIGHT_LOAD => ImpactLevel::Ok,
+                    Some(load) if load <= 1.0 => ImpactLevel::Tight,
+                    _ => ImpactLevel::Infeasible,
+                };
+
+                LecturerImpact {
+                    id_dosen,
+                    courses: courses.len(),
+                    sks: courses.iter().map(|c| c.sks).sum(),
+                    demanded_minutes,
+                    open_slots,
+                    total_slots: windows.len() * 5,
+                    available_minutes,
+                    load,
+                    level,
+                }
+            })
+            .collect();
+
+        impacts.sort_by(|a, b| {
+            a.level
+                .cmp(&b.level)
+                .then_with(|| b.load.unwrap_or(f32::INFINITY).total_cmp(&a.load.unwrap_or(f32::INFINITY)))
+                .then_with(|| a.id_dosen.cmp(&b.id_dosen))
+        });
+        impacts
+    }
+}
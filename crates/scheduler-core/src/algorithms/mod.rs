@@ -0,0 +1,24 @@
+pub mod optimizer;
+pub mod models;
+pub mod checker;
+pub mod repair;
+pub mod exam;
+pub mod rooms;
+pub mod soft;
+pub mod relax;
+pub mod cooperative;
+pub mod impact;
+pub mod cross_list;
+pub mod stats;
+pub mod float;
+pub mod decode;
+pub mod stepper;
+pub mod diagnostics;
+pub mod ga;
+pub mod anneal;
+pub mod finalize;
+pub mod presets;
+pub mod par;
+#[cfg(feature = "chaos")]
+pub mod chaos;
+
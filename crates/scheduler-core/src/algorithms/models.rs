@@ -0,0 +1,1272 @@
+use chrono::{DateTime, Utc};
+use rand::rngs::StdRng;
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize};
+#[cfg(feature = "runtime")]
+use tokio::sync::{broadcast, mpsc};
+#[cfg(feature = "runtime")]
+use tokio_util::sync::CancellationToken;
+#[cfg(feature = "parallel")]
+use std::sync::Arc;
+use std::{collections::HashMap, time::Duration};
+
+use super::{
+    diagnostics::IterationDiagnostics,
+    float::Precision,
+    anneal::RefinementParameters,
+    ga::{Algorithm, GaParameters},
+    presets::Preset,
+    rooms::RoomCatalog,
+    stats::ScheduleStats,
+};
+
+/// Particle with coordinates of type `F` (see `PsoParameters::precision`)
+#[derive(Debug, Clone)]
+pub struct Particle<F = f32> {
+    pub position: Vec<F>,
+    pub velocity: Vec<F>,
+    pub pbest_position: Vec<F>,
+    pub pbest_fitness: f32,
+    pub fitness: f32,
+    // Bagian fitness dari pelanggaran keras, dipakai seleksi feasibility_first
+    pub hard_penalty: f32,
+    pub pbest_hard_penalty: f32,
+    // Diturunkan dari seed run agar update paralel tetap deterministik
+    pub rng: StdRng,
+}
+
+/// Sub-swarm of the cooperative mode; its particles only hold the dimensions
+/// listed in `dims`
+#[derive(Debug, Clone)]
+pub struct SubSwarm<F = f32> {
+    pub dims: Vec<usize>,
+    pub particles: Vec<Particle<F>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct CourseRequest {
+
+    pub id_jadwal: u32,
+    pub id_matkul: u32,
+    // 0 atau null: dosen belum ditentukan (TBA)
+    #[serde(default, deserialize_with = "dosen_or_tba")]
+    #[schemars(with = "Option<u32>")]
+    pub id_dosen: u32,
+    pub id_waktu: u32,
+    pub id_kelas: u32,
+    pub semester: u32,
+    pub sks: u32,
+    pub prodi: u32,
+    // Kelompok mahasiswa lintas kelas/prodi (mis. peserta minor)
+    #[serde(default)]
+    pub student_groups: Vec<u32>,
+    // Tingkat prioritas; makin kecil makin boleh dikorbankan (0 = pilihan)
+    #[serde(default = "default_priority")]
+    pub priority: u8,
+    #[serde(default)]
+    pub jenis: CourseType,
+    // Baris lain (id_jadwal) yang sebenarnya satu sesi fisik dengan baris ini
+    #[serde(default)]
+    pub cross_listed_with: Vec<u32>,
+    // Jumlah mahasiswa terdaftar; 0 = tidak diketahui, kapasitas ruangan tidak diperiksa
+    #[serde(default)]
+    pub jumlah_mahasiswa: u32,
+    // Slot tetap (mis. dosen tamu); optimizer menjadwalkan yang lain di sekitarnya
+    #[serde(default)]
+    pub locked: Option<LockedSlot>,
+}
+
+/// Day, start time and optionally room a course must keep
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
+pub struct LockedSlot {
+    #[schemars(range(min = 1, max = 5))]
+    pub hari: u32,
+    pub jam_mulai: u32,
+    /// Assigned by the room allocator when absent
+    #[serde(default)]
+    pub ruangan: Option<u32>,
+}
+
+/// Kind of session; praktikum usually runs longer than its SKS suggests
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CourseType {
+    #[default]
+    Teori,
+    Praktikum,
+}
+
+pub const DEFAULT_PRIORITY: u8 = 1;
+
+/// id_dosen of a course whose lecturer is not assigned yet; such courses are
+/// scheduled but skip lecturer clash and preference checks
+pub const DOSEN_TBA: u32 = 0;
+
+fn dosen_or_tba<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    Ok(Option::<u32>::deserialize(deserializer)?.unwrap_or(DOSEN_TBA))
+}
+
+fn default_priority() -> u8 {
+    DEFAULT_PRIORITY
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct OptimizationRequest {
+    pub courses: Vec<CourseRequest>,
+    // Jika kosong, dipakai preset; tanpa preset dipakai default milik tenant
+    pub parameters: Option<PsoParameters>,
+    // Set parameter bernama (GET /presets) untuk pengguna yang tidak ingin mengatur bobot PSO
+    #[serde(default)]
+    pub preset: Option<Preset>,
+    pub config: Option<ConstraintConfig>,
+    // Menimpa mode dari config bila diisi
+    pub mode: Option<ScheduleMode>,
+    pub per_day_sks_rule: Option<PerDaySksRule>,
+    pub kelas_waktu_policy: Option<HashMap<u32, Vec<u32>>>,
+    pub time_preferences: Vec<TimePreferenceRequest>,
+    // Jenis kontrak dosen; dipakai bila dosen tidak punya baris time_preferences
+    #[serde(default)]
+    pub lecturers: Vec<LecturerRequest>,
+    #[serde(default)]
+    pub external_bookings: Vec<ExternalBooking>,
+    // Ruangan untuk fase alokasi ruangan setelah PSO; hasil memakai id ruangan ini
+    #[serde(default)]
+    pub rooms: Vec<RoomRequest>,
+    // Usang: ruangan tanpa nama 1..=N (N <= rooms::MAX_SUM_RUANGAN), hanya dipakai bila rooms kosong
+    pub sum_ruangan: Option<u32>,
+    // Mulai dari posisi terbaik run lama yang mirip (opt-in)
+    #[serde(default)]
+    pub warm_start_from_history: bool,
+    // Geser kuliah sepagi mungkin setelah PSO (hanya jadwal mingguan)
+    #[serde(default)]
+    pub compact: bool,
+    // Jadwal yang sedang berlaku; dijadikan partikel awal agar optimasi ulang tengah semester mulai dari sana
+    #[serde(default)]
+    pub initial_schedule: Vec<OptimizedCourse>,
+    // Draf sebelumnya; ruangan dilabeli ulang agar kelas tetap di ruang yang sama
+    #[serde(default)]
+    pub previous_schedule: Option<Vec<OptimizedCourse>>,
+    // Tambahkan day_name dan jam "HH:MM" di setiap baris jadwal hasil
+    #[serde(default)]
+    pub verbose_output: bool,
+    // Strategi pencarian: pso (default) atau ga
+    #[serde(default)]
+    pub algorithm: Algorithm,
+    // Tambahkan per_prodi: jadwal, pelanggaran dan sumbangan fitness tiap prodi
+    #[serde(default)]
+    pub per_prodi_report: bool,
+    // Jumlah run PSO dengan seed berbeda; hasil terbaik yang dipakai (default 1)
+    #[serde(default)]
+    pub num_runs: Option<usize>,
+}
+
+#[derive(Clone, Serialize)]
+pub struct Status {
+    pub message: String
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct OptimizationProgress {
+     // Job yang sedang berjalan; None untuk event di luar run
+     #[serde(skip_serializing_if = "Option::is_none")]
+     pub job_id: Option<u64>,
+     pub iteration: usize,
+        // Diserialisasi sebagai elapsed_ms + elapsed_iso (lihat serialize_elapsed)
+        #[serde(flatten, serialize_with = "serialize_elapsed")]
+        pub elapsed_time: Duration,
+        // Waktu UTC saat event dikirim (RFC 3339)
+        pub timestamp: DateTime<Utc>,
+        // Waktu UTC run dimulai; None untuk event di luar run
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub started_at: Option<DateTime<Utc>>,
+        pub best_fitness: f32,
+        // best_fitness pada skala 0–100 (lihat QualityScale)
+        pub quality_score: f32,
+        pub all_best_fitness: Option<Vec<f32>>,  // Menjadi opsional
+        pub current_run: Option<usize>,          // Menjadi opsional
+        pub total_runs: Option<usize>,           // Menjadi opsional
+        pub is_finished: bool,
+        // Run terakhir job selesai (is_finished dikirim di akhir tiap run)
+        pub job_finished: bool,
+        // Global best berubah sejak event sebelumnya
+        pub improved: bool,
+        pub conflict_counts: ConflictCounts,
+        // Hanya diisi saat jadwal diperbarui (mis. perbaikan setelah PATCH)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub schedule: Option<Vec<OptimizedCourse>>,
+        // Hanya diisi bila parameters.fitness_sample_size diset
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub fitness_distribution: Option<FitnessDistribution>,
+        // Seed run yang sedang berjalan
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub seed: Option<u64>,
+        // Tiap parameters.stats_interval iterasi dan di akhir run; SSE mengirimnya sebagai event `stats`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub stats: Option<ScheduleStats>,
+        // Diset bila field opsional dibuang karena event melebihi limits.max_event_bytes
+        #[serde(skip_serializing_if = "std::ops::Not::not")]
+        pub truncated: bool,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        pub truncated_fields: Vec<&'static str>,
+        // pub conflicts: ConflictInfo,
+}
+
+/// Writes `elapsed_ms` and an ISO-8601 `elapsed_iso` ("PT1M2.345S"). The old
+/// `{secs, nanos}` object under `elapsed_time` is kept while the
+/// `legacy-elapsed-time` feature is enabled, until clients have migrated.
+fn serialize_elapsed<S: serde::Serializer>(elapsed: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    use serde::ser::SerializeMap;
+
+    let mut map = serializer.serialize_map(None)?;
+    map.serialize_entry("elapsed_ms", &(elapsed.as_millis() as u64))?;
+    map.serialize_entry("elapsed_iso", &iso8601_duration(elapsed))?;
+    #[cfg(feature = "legacy-elapsed-time")]
+    map.serialize_entry("elapsed_time", elapsed)?;
+    map.end()
+}
+
+fn iso8601_duration(elapsed: &Duration) -> String {
+    let total = elapsed.as_secs();
+    let (hours, minutes, seconds) = (total / 3600, total / 60 % 60, total % 60);
+
+    let mut out = String::from("PT");
+    if hours > 0 {
+        out.push_str(&format!("{}H", hours));
+    }
+    if minutes > 0 {
+        out.push_str(&format!("{}M", minutes));
+    }
+    out.push_str(&format!("{}.{:03}S", seconds, elapsed.subsec_millis()));
+    out
+}
+
+/// Percentiles of current particle fitness over a random sample of the swarm
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct FitnessDistribution {
+    pub sampled: usize,
+    pub min: f32,
+    pub p25: f32,
+    pub median: f32,
+    pub p75: f32,
+    pub max: f32,
+}
+
+/// Violations of the current global best, per constraint category
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct ConflictCounts {
+    pub lecturer_clash: u32,
+    pub kelas_clash: u32,
+    pub room_clash: u32,
+    pub room_capacity: u32,
+    pub preference: u32,
+    pub exam_rule: u32,
+    pub soft: u32,
+}
+
+/// Decoded global best, reused by progress events until the best changes
+#[derive(Debug, Clone)]
+pub struct BestSnapshot<F = f32> {
+    pub position: Vec<F>,
+    pub schedule: Vec<OptimizedCourse>,
+    pub conflicts: ConflictCounts,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct TimePreferenceRequest {
+    pub id_dosen: u32,
+    pub senin_pagi: bool,
+    pub senin_malam: bool,
+    pub selasa_pagi: bool,
+    pub selasa_malam: bool,
+    pub rabu_pagi: bool,
+    pub rabu_malam: bool,
+    pub kamis_pagi: bool,
+    pub kamis_malam: bool,
+    pub jumat_pagi: bool,
+    pub jumat_malam: bool,
+    // Dosen tidak mau datang dua kali sehari (pagi dan malam)
+    #[serde(default)]
+    pub avoid_split_day: bool,
+    // Beban mengajar maksimum dosen ini; kosong = tanpa batas
+    #[serde(default)]
+    pub max_sks_per_hari: Option<u32>,
+    #[serde(default)]
+    pub max_sks_per_minggu: Option<u32>,
+}
+
+/// Teaching contract of a lecturer; decides their base availability when no
+/// explicit preference row is sent
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ContractType {
+    #[default]
+    Penuh,
+    PagiOnly,
+    MalamOnly,
+    /// Saturdays only; the Senin - Jumat week has no slot for it
+    SabtuOnly,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct LecturerRequest {
+    pub id_dosen: u32,
+    #[serde(default)]
+    pub contract_type: ContractType,
+}
+
+impl TimePreferenceRequest {
+    /// Base availability implied by a contract: every weekday pagi and/or malam
+    pub fn from_contract(id_dosen: u32, contract: ContractType) -> Self {
+        let pagi = matches!(contract, ContractType::Penuh | ContractType::PagiOnly);
+        let malam = matches!(contract, ContractType::Penuh | ContractType::MalamOnly);
+        Self {
+            senin_pagi: pagi,
+            senin_malam: malam,
+            selasa_pagi: pagi,
+            selasa_malam: malam,
+            rabu_pagi: pagi,
+            rabu_malam: malam,
+            kamis_pagi: pagi,
+            kamis_malam: malam,
+            jumat_pagi: pagi,
+            jumat_malam: malam,
+            ..Self::unavailable(id_dosen)
+        }
+    }
+
+    pub fn unavailable(id_dosen: u32) -> Self {
+        Self {
+            id_dosen,
+            senin_pagi: false,
+            senin_malam: false,
+            selasa_pagi: false,
+            selasa_malam: false,
+            rabu_pagi: false,
+            rabu_malam: false,
+            kamis_pagi: false,
+            kamis_malam: false,
+            jumat_pagi: false,
+            jumat_malam: false,
+            avoid_split_day: false,
+            max_sks_per_hari: None,
+            max_sks_per_minggu: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScheduleChecker {
+   pub time_preferences: HashMap<u32, TimePreferenceRequest>,
+   pub config: ConstraintConfig,
+   pub external_bookings: Vec<ExternalBooking>,
+   pub student_groups: HashMap<u32, Vec<u32>>,
+   // id_jadwal -> prioritas, hanya untuk yang bukan default
+   pub priorities: HashMap<u32, u8>,
+   // id_jadwal pengikut -> id_jadwal pemimpin sesi lintas kelas
+   pub cross_listed: HashMap<u32, u32>,
+   pub rooms: RoomCatalog,
+}
+
+/// Physical room the allocator may use
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct RoomRequest {
+    /// Room id, reported back as `ruangan`
+    pub ruangan: u32,
+    #[serde(default)]
+    pub nama: Option<String>,
+    /// Untyped rooms take any course
+    #[serde(default)]
+    pub jenis: Option<RoomType>,
+    /// Seats; unknown capacity fits every class
+    #[serde(default)]
+    pub kapasitas: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RoomType {
+    Teori,
+    /// Required by praktikum courses
+    Lab,
+}
+
+/// Room occupied outside the system (seminar, exam, event)
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct ExternalBooking {
+    pub ruangan: u32,
+    pub hari: u32,
+    pub jam_mulai: u32,
+    pub jam_akhir: u32,
+    #[serde(default)]
+    pub keterangan: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct OptimizedCourse {
+    pub id_jadwal: u32,
+    pub id_matkul: u32,
+    pub id_dosen: u32,
+    pub id_kelas: u32,
+    pub id_waktu: u32,
+    pub hari: u32,
+    pub jam_mulai: u32,
+    pub jam_akhir: u32,
+    pub ruangan: u32,
+    pub semester: u32,
+    pub sks: u32,
+    pub prodi: u32,
+    // Dosen belum ditentukan (id_dosen = DOSEN_TBA)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dosen_tba: bool,
+    // Slot dikunci pemanggil; tidak dipindah optimizer maupun perbaikan
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub locked: bool,
+}
+
+pub struct PSO<F = f32> {
+    pub particles: Vec<Particle<F>>,
+    // Hanya terisi pada mode kooperatif; particles kosong
+    pub sub_swarms: Vec<SubSwarm<F>>,
+    // Posisi awal yang disuntikkan ke sebagian swarm (warm start)
+    pub initial_positions: Vec<Vec<f32>>,
+    pub global_best_position: Vec<F>,
+    pub global_best_fitness: f32,
+    pub global_best_hard_penalty: f32,
+    // Global best yang sudah didekode untuk event progress terakhir
+    pub best_snapshot: Option<BestSnapshot<F>>,
+    // Iterasi saat global best membaik, untuk log run
+    pub improvements: Vec<(usize, f32)>,
+    pub stopped_at: Option<usize>,
+    pub termination: TerminationReason,
+    pub extension: Option<RunExtension>,
+    // Ditempelkan ke setiap event progress
+    pub job_id: Option<u64>,
+    // Seed untuk semua RNG di run ini; dibangkitkan bila parameters.seed kosong
+    pub seed: u64,
+    pub rng: StdRng,
+    // Faktor pengali penalti keras pada iterasi saat ini (penalty_schedule)
+    pub hard_factor: f32,
+    // Bobot inersia pada iterasi saat ini (inertia_schedule) dan state peta logistiknya
+    pub inertia: f32,
+    pub inertia_chaos: f32,
+    pub parameters: PsoParameters,
+    pub courses: Vec<CourseRequest>,
+    pub checker: ScheduleChecker,
+    #[cfg(feature = "runtime")]
+    pub status_tx: Option<broadcast::Sender<OptimizationProgress>>,
+    #[cfg(feature = "runtime")]
+    pub cancel: Option<CancellationToken>,
+    // Perubahan parameter dari /jobs/:id/params selama run berjalan
+    #[cfg(feature = "runtime")]
+    pub params_rx: Option<mpsc::UnboundedReceiver<LiveParams>>,
+    // Iterasi saat perubahan itu diterapkan, untuk log run
+    pub params_changes: Vec<(usize, LiveParams)>,
+    // Dari request.algorithm; ga memakai partikel sebagai populasi
+    pub algorithm: Algorithm,
+    // Hanya terisi bila parameters.record_diagnostics
+    pub diagnostics: Vec<IterationDiagnostics>,
+    // Thread pool khusus job ini (settings.job_threads); None memakai pool global
+    #[cfg(feature = "parallel")]
+    pub pool: Option<Arc<rayon::ThreadPool>>,
+}
+
+/// Outcome of `PSO::run`, independent of the precision the swarm ran at
+#[cfg(feature = "runtime")]
+pub struct FinishedRun {
+    pub best_position: Vec<f32>,
+    pub fitness: f32,
+    pub improvements: Vec<(usize, f32)>,
+    pub params_changes: Vec<(usize, LiveParams)>,
+    pub stopped_at: Option<usize>,
+    pub termination: TerminationReason,
+    pub extension: Option<RunExtension>,
+    pub diagnostics: Vec<IterationDiagnostics>,
+    pub params_rx: Option<mpsc::UnboundedReceiver<LiveParams>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct PsoParameters {
+    #[schemars(range(min = 1))]
+    pub swarm_size: usize,
+    pub max_iterations: usize,
+    #[schemars(range(min = 0.0, max = 4.0))]
+    pub cognitive_weight: f32,
+    #[schemars(range(min = 0.0, max = 4.0))]
+    pub social_weight: f32,
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub inertia_weight: f32,
+    // Bobot inersia yang berubah selama run; None = inertia_weight tetap
+    #[serde(default)]
+    pub inertia_schedule: Option<InertiaSchedule>,
+    // Batasi pemakaian CPU agar server kecil tetap responsif
+    #[serde(default)]
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub max_cpu_fraction: Option<f32>,
+    #[serde(default)]
+    pub max_iterations_per_second: Option<f32>,
+    #[serde(default)]
+    pub selection: SelectionRule,
+    // Jumlah partikel yang diambil acak untuk distribusi fitness di progress
+    #[serde(default)]
+    pub fitness_sample_size: Option<usize>,
+    // Kirim statistik jadwal (ruangan, jeda, preferensi) tiap N iterasi
+    #[serde(default)]
+    pub stats_interval: Option<usize>,
+    // Kirim progress hanya tiap N iterasi (default tiap iterasi); progress akhir selalu dikirim
+    #[serde(default)]
+    #[schemars(range(min = 1))]
+    pub report_every_n_iterations: Option<usize>,
+    // Jarak minimum antar progress dalam milidetik, untuk klien di jaringan lambat
+    #[serde(default)]
+    pub report_min_interval_ms: Option<u64>,
+    // Catat keragaman swarm, |kecepatan| rata-rata dan laju perbaikan pbest tiap iterasi
+    #[serde(default)]
+    pub record_diagnostics: bool,
+    // Berhenti bila global best tidak membaik selama N iterasi
+    #[serde(default)]
+    pub max_stagnation_iterations: Option<usize>,
+    #[serde(default)]
+    pub time_limit_secs: Option<f32>,
+    // Berhenti begitu ada partikel tanpa bentrok keras dengan penalti lunak <= nilai ini
+    #[serde(default)]
+    pub feasible_soft_threshold: Option<f32>,
+    // Perpanjang run selama masih ada bentrok keras
+    #[serde(default)]
+    pub require_feasible: bool,
+    #[serde(default)]
+    pub extension_iterations: Option<usize>,
+    #[serde(default)]
+    pub max_extension_secs: Option<f32>,
+    // Run ulang (maks N kali) dari posisi terbaik yang digeser acak selama bentrok keras tersisa
+    #[serde(default)]
+    pub conflict_retries: Option<usize>,
+    // Besar geseran acak per koordinat untuk run ulang; default 0.05
+    #[serde(default)]
+    pub retry_perturbation: Option<f32>,
+    // Hanya dipakai bila algorithm = ga
+    #[serde(default)]
+    pub ga: GaParameters,
+    // Penghalusan simulated annealing setelah swarm selesai; None = tanpa penghalusan
+    #[serde(default)]
+    pub refinement: Option<RefinementParameters>,
+    /// Seed for every random draw in the run; generated and reported when absent
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Co-evolving sub-swarms for large instances; None keeps a single swarm
+    #[serde(default)]
+    pub cooperative: Option<CooperativeConfig>,
+    #[serde(default)]
+    pub velocity_init: VelocityInit,
+    // Setengah rentang kecepatan awal; default mengikuti velocity_init
+    #[serde(default)]
+    pub velocity_scale: Option<f32>,
+    // Tipe koordinat partikel; f64 menggandakan memori swarm
+    #[serde(default)]
+    pub precision: Precision,
+    // Kegagalan buatan untuk menguji siklus hidup run
+    #[cfg(feature = "chaos")]
+    #[serde(default)]
+    pub faults: Option<super::chaos::FaultPlan>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum InertiaCurve {
+    /// Straight line from `start` to `end` over `max_iterations`
+    #[default]
+    LinearDecay,
+    /// Linear decay scaled by a logistic map, so the weight keeps jumping
+    /// around the trend instead of settling
+    Chaotic,
+    /// Rises towards `start` while the global best stagnates and falls
+    /// towards `end` while it improves
+    FuzzyAdaptive,
+}
+
+/// Inertia weight that changes over the run, from exploring (`start`) to
+/// exploiting (`end`)
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(default)]
+pub struct InertiaSchedule {
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub start: f32,
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub end: f32,
+    pub curve: InertiaCurve,
+}
+
+impl Default for InertiaSchedule {
+    fn default() -> Self {
+        Self {
+            start: 0.9,
+            end: 0.4,
+            curve: InertiaCurve::LinearDecay,
+        }
+    }
+}
+
+impl InertiaSchedule {
+    /// Weight for `iteration`. `current` is the weight of the previous
+    /// iteration, `chaos` the logistic-map state in (0, 1) and `stagnation`
+    /// the iterations since the global best last improved.
+    pub fn weight(&self, iteration: usize, max_iterations: usize, current: f32, chaos: &mut f32, stagnation: usize) -> f32 {
+        let t = iteration as f32 / max_iterations.saturating_sub(1).max(1) as f32;
+        let t = t.clamp(0.0, 1.0);
+        let (low, high) = (self.start.min(self.end), self.start.max(self.end));
+
+        match self.curve {
+            InertiaCurve::LinearDecay => self.start + (self.end - self.start) * t,
+            InertiaCurve::Chaotic => {
+                *chaos = 4.0 * *chaos * (1.0 - *chaos);
+                // Peta logistik bisa jatuh ke titik tetap 0; mulai ulang dari tengah
+                if !(0.0..1.0).contains(chaos) || *chaos < f32::EPSILON {
+                    *chaos = 0.7;
+                }
+                (self.start - self.end) * (1.0 - t) + self.end * *chaos
+            }
+            InertiaCurve::FuzzyAdaptive => {
+                // Tiga aturan: stagnasi rendah -> turunkan, sedang -> tetap, tinggi -> naikkan.
+                // Keanggotaan segitiga di 0, 0.5 dan 1; defuzzifikasi rata-rata berbobot.
+                let window = (max_iterations / 20).max(5);
+                let r = (stagnation as f32 / window as f32).min(1.0);
+                let (decrease, increase) = ((1.0 - 2.0 * r).max(0.0), (2.0 * r - 1.0).max(0.0));
+                let keep = 1.0 - decrease - increase;
+                let step = (high - low) / 10.0;
+                let change = (increase * step - decrease * step) / (decrease + keep + increase);
+                (current + change).clamp(low, high)
+            }
+        }
+    }
+}
+
+/// How initial particle velocities are drawn. Positions live in [0, 1), so
+/// a full (-1, 1) spread throws particles out of their initial layout on the
+/// first update.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VelocityInit {
+    /// Uniform in (-scale, scale), scale 1 by default
+    Random,
+    /// Start at rest
+    Zero,
+    /// Uniform in (-scale, scale), scale 0.1 by default
+    #[default]
+    SmallRandom,
+}
+
+impl VelocityInit {
+    /// Half-width of the initial velocity range
+    pub fn bound(self, scale: Option<f32>) -> f32 {
+        match self {
+            VelocityInit::Random => scale.unwrap_or(1.0),
+            VelocityInit::Zero => 0.0,
+            VelocityInit::SmallRandom => scale.unwrap_or(0.1),
+        }
+        .abs()
+    }
+}
+
+/// Cooperative co-evolution (CCPSO): the position vector is split by course
+/// group and each part gets its own sub-swarm, evaluated against the shared
+/// global best as context
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(default)]
+pub struct CooperativeConfig {
+    /// Only instances with at least this many courses are decomposed
+    pub min_courses: usize,
+    /// Decoder groups are merged until a sub-swarm covers this many courses
+    pub courses_per_swarm: usize,
+    pub sub_swarm_size: usize,
+}
+
+impl Default for CooperativeConfig {
+    fn default() -> Self {
+        Self {
+            min_courses: 2000,
+            courses_per_swarm: 100,
+            sub_swarm_size: 10,
+        }
+    }
+}
+
+impl Default for PsoParameters {
+    fn default() -> Self {
+        Self {
+            swarm_size: 30,
+            max_iterations: 500,
+            cognitive_weight: 1.5,
+            social_weight: 1.5,
+            inertia_weight: 0.7,
+            inertia_schedule: None,
+            max_cpu_fraction: None,
+            max_iterations_per_second: None,
+            selection: SelectionRule::default(),
+            fitness_sample_size: None,
+            stats_interval: None,
+            report_every_n_iterations: None,
+            report_min_interval_ms: None,
+            record_diagnostics: false,
+            max_stagnation_iterations: None,
+            time_limit_secs: None,
+            feasible_soft_threshold: None,
+            require_feasible: false,
+            extension_iterations: None,
+            max_extension_secs: None,
+            conflict_retries: None,
+            retry_perturbation: None,
+            ga: GaParameters::default(),
+            refinement: None,
+            seed: None,
+            cooperative: None,
+            velocity_init: VelocityInit::default(),
+            velocity_scale: None,
+            precision: Precision::default(),
+            #[cfg(feature = "chaos")]
+            faults: None,
+        }
+    }
+}
+
+/// Tweaks accepted by a running job via /jobs/:id/params, applied at the
+/// next iteration boundary
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LiveParams {
+    /// New total; values at or below the current iteration end the run there
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_iterations: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inertia_weight: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cognitive_weight: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub social_weight: Option<f32>,
+    /// Only the listed `PenaltyWeights` fields change
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub weights: HashMap<String, u32>,
+}
+
+impl LiveParams {
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, value) in [
+            ("inertia_weight", self.inertia_weight),
+            ("cognitive_weight", self.cognitive_weight),
+            ("social_weight", self.social_weight),
+        ] {
+            if value.is_some_and(|v| !v.is_finite() || v < 0.0) {
+                return Err(format!("{} harus bilangan tidak negatif", name));
+            }
+        }
+        let known = serde_json::to_value(PenaltyWeights::default()).unwrap_or_default();
+        match self.weights.keys().find(|name| known.get(name.as_str()).is_none()) {
+            Some(name) => Err(format!("bobot penalti tidak dikenal: {}", name)),
+            None => Ok(()),
+        }
+    }
+
+    /// Overwrites the listed fields of `weights`
+    pub fn apply_weights(&self, weights: &mut PenaltyWeights) {
+        let Ok(serde_json::Value::Object(mut fields)) = serde_json::to_value(&*weights) else {
+            return;
+        };
+        for (name, value) in &self.weights {
+            fields.insert(name.clone(), (*value).into());
+        }
+        if let Ok(updated) = serde_json::from_value(serde_json::Value::Object(fields)) {
+            *weights = updated;
+        }
+    }
+}
+
+/// Extra iterations spent by `require_feasible` after max_iterations
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct RunExtension {
+    pub extensions: usize,
+    pub extra_iterations: usize,
+    pub feasible: bool,
+}
+
+/// Why a run ended
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TerminationReason {
+    #[default]
+    MaxIterations,
+    TargetFitness,
+    /// No hard conflicts and soft penalty within `feasible_soft_threshold`
+    FeasibleTarget,
+    /// At most one session to place, so it was decoded directly without a swarm
+    Constructed,
+    Stagnation,
+    Timeout,
+    UserStop,
+}
+
+impl TerminationReason {
+    /// The swarm settled on its own rather than being cut short
+    pub fn converged(self) -> bool {
+        matches!(
+            self,
+            TerminationReason::TargetFitness
+                | TerminationReason::FeasibleTarget
+                | TerminationReason::Constructed
+                | TerminationReason::Stagnation
+        )
+    }
+
+    /// The run was interrupted, so the schedule is a best-so-far result
+    pub fn partial(self) -> bool {
+        matches!(self, TerminationReason::Timeout | TerminationReason::UserStop)
+    }
+}
+
+/// How two candidate solutions are ranked for personal and global bests
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SelectionRule {
+    /// Lower total weighted penalty wins
+    #[default]
+    WeightedSum,
+    /// Lower hard-constraint penalty wins; total fitness only breaks ties
+    FeasibilityFirst,
+}
+
+impl SelectionRule {
+    /// Whether `(hard, fitness)` of a candidate beats the current best
+    pub fn is_better(self, candidate: (f32, f32), best: (f32, f32)) -> bool {
+        if candidate.1.is_nan() {
+            return false;
+        }
+
+        match self {
+            SelectionRule::WeightedSum => candidate.1 < best.1,
+            SelectionRule::FeasibilityFirst => {
+                candidate.0 < best.0 || (candidate.0 == best.0 && candidate.1 < best.1)
+            }
+        }
+    }
+}
+
+/// Teaching windows in minutes since midnight
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(default)]
+pub struct SlotConfig {
+    pub pagi: (u32, u32),
+    pub malam: (u32, u32),
+    pub minutes_per_sks: u32,
+    /// Minimum gap in minutes between consecutive sessions of the same kelas
+    /// or student group, e.g. to change rooms
+    pub min_break: u32,
+}
+
+// Jendela mengajar tidak boleh melewati tengah malam
+const MINUTES_PER_DAY: u32 = 24 * 60;
+
+impl Default for SlotConfig {
+    fn default() -> Self {
+        Self {
+            pagi: (480, 720),
+            malam: (1080, 1320),
+            minutes_per_sks: 40,
+            min_break: 0,
+        }
+    }
+}
+
+impl ConstraintConfig {
+    /// id_waktu the course is decoded with: its own label when the kelas
+    /// policy allows it (or has none), otherwise the first allowed value
+    pub fn effective_waktu(&self, course: &CourseRequest) -> u32 {
+        match self.kelas_waktu_policy.get(&course.id_kelas) {
+            Some(allowed) if !allowed.is_empty() && !allowed.contains(&course.id_waktu) => allowed[0],
+            _ => course.id_waktu,
+        }
+    }
+}
+
+impl ConstraintConfig {
+    /// Rejects configs the decoder and checks can't work with: empty or
+    /// reversed teaching windows and zero-length steps
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, (start, end)) in [("slots.pagi", self.slots.pagi), ("slots.malam", self.slots.malam)] {
+            if start >= end || end > MINUTES_PER_DAY {
+                return Err(format!(
+                    "{} [{}, {}] harus awal < akhir dan akhir <= {}",
+                    name, start, end, MINUTES_PER_DAY
+                ));
+            }
+        }
+        if self.slots.minutes_per_sks == 0 {
+            return Err("slots.minutes_per_sks harus lebih dari 0".to_string());
+        }
+        if self.max_sks_per_day == 0 {
+            return Err("max_sks_per_day harus lebih dari 0".to_string());
+        }
+        Ok(())
+    }
+
+    /// Length of a session in minutes, rounded to whole minutes
+    pub fn duration(&self, course: &CourseRequest) -> u32 {
+        let base = course.sks * self.slots.minutes_per_sks;
+        let factor = self
+            .durations
+            .by_matkul
+            .get(&course.id_matkul)
+            .or_else(|| self.durations.by_type.get(&course.jenis))
+            .copied()
+            .unwrap_or(1.0);
+        (base as f32 * factor.max(0.0)).round() as u32
+    }
+}
+
+impl SlotConfig {
+    /// Start and end minute of the teaching window for an id_waktu (1 = pagi, 2 = malam)
+    pub fn window(&self, id_waktu: u32) -> (u32, u32) {
+        match id_waktu {
+            2 => self.malam,
+            _ => self.pagi,
+        }
+    }
+
+    pub fn is_pagi(&self, jam_mulai: u32) -> bool {
+        jam_mulai < self.malam.0
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(default)]
+pub struct PenaltyWeights {
+    pub lecturer_conflict: u32,
+    pub kelas_conflict: u32,
+    pub room_conflict: u32,
+    /// Per course whose class is larger than its room
+    pub room_capacity: u32,
+    pub preference: u32,
+    pub exam_rule: u32,
+    /// Per extra campus day a lecturer needs; 0 disables day clustering
+    pub lecturer_days: u32,
+    /// Per day a lecturer with `avoid_split_day` teaches both pagi and malam
+    pub split_day: u32,
+    /// Per hour a kelas's last session of a day ends later than its sessions
+    /// packed from the window start would; 0 disables it
+    pub late_finish: u32,
+    /// Per SKS a lecturer teaches beyond `max_sks_per_hari` on a day or
+    /// `max_sks_per_minggu` in the week
+    pub lecturer_load: u32,
+    /// Per extra block a lecturer's sections of one matkul are split into
+    /// beyond what the daily window forces; 0 disables bundling
+    pub section_bundle: u32,
+}
+
+impl Default for PenaltyWeights {
+    fn default() -> Self {
+        Self {
+            lecturer_conflict: 100,
+            kelas_conflict: 100,
+            room_conflict: 100,
+            room_capacity: 100,
+            preference: 100,
+            exam_rule: 100,
+            lecturer_days: 0,
+            split_day: 100,
+            late_finish: 0,
+            lecturer_load: 100,
+            section_bundle: 0,
+        }
+    }
+}
+
+/// Maps a raw penalty onto a 0–100 quality score as
+/// `100 * half_penalty / (half_penalty + penalty)`: 100 for a conflict-free
+/// schedule meeting every soft constraint, 50 at `half_penalty`, tending to 0
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(default)]
+pub struct QualityScale {
+    pub half_penalty: u32,
+}
+
+impl Default for QualityScale {
+    fn default() -> Self {
+        // Sepuluh pelanggaran dengan bobot default
+        Self { half_penalty: 1000 }
+    }
+}
+
+pub const QUALITY_FORMULA: &str = "100 * half_penalty / (half_penalty + raw_penalty)";
+
+/// Quality score reported next to the raw penalty, with the scale used
+#[derive(Serialize, Debug, Clone, Copy)]
+pub struct QualityScore {
+    pub score: f32,
+    pub raw_penalty: f32,
+    pub half_penalty: u32,
+    pub formula: &'static str,
+}
+
+impl QualityScale {
+    pub fn score(&self, penalty: f32) -> f32 {
+        if !penalty.is_finite() {
+            return 0.0;
+        }
+        let half = self.half_penalty.max(1) as f32;
+        100.0 * half / (half + penalty.max(0.0))
+    }
+
+    pub fn quality(&self, penalty: f32) -> QualityScore {
+        QualityScore {
+            score: self.score(penalty),
+            raw_penalty: penalty,
+            half_penalty: self.half_penalty,
+            formula: QUALITY_FORMULA,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduleMode {
+    /// Jadwal kuliah mingguan (Senin - Jumat)
+    #[default]
+    Weekly,
+    /// Jadwal ujian sekali jalan selama periode ujian
+    Exam,
+}
+
+/// Exam period layout; `hari` in exam mode is the day index within the period
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(default)]
+pub struct ExamConfig {
+    pub days: u32,
+    pub slot_starts: Vec<u32>,
+    pub duration: u32,
+    pub max_exams_per_day: u32,
+    pub min_gap: u32,
+}
+
+impl Default for ExamConfig {
+    fn default() -> Self {
+        Self {
+            days: 10,
+            slot_starts: vec![480, 630, 780, 930],
+            duration: 120,
+            max_exams_per_day: 2,
+            min_gap: 30,
+        }
+    }
+}
+
+/// How the daily SKS cap of a (prodi, semester, kelas, waktu) group is chosen
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PerDaySksRule {
+    /// Kelompok dengan tepat 4 mata kuliah dibatasi 3 SKS per hari, selain itu max_sks_per_day
+    #[default]
+    StrictFourCourse,
+    /// Selalu max_sks_per_day
+    FixedCap,
+}
+
+impl PerDaySksRule {
+    pub fn max_sks(self, group_len: usize, max_sks_per_day: u32) -> u32 {
+        match self {
+            PerDaySksRule::StrictFourCourse if group_len == 4 => 3,
+            _ => max_sks_per_day,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(default)]
+pub struct ConstraintConfig {
+    pub mode: ScheduleMode,
+    pub slots: SlotConfig,
+    pub max_sks_per_day: u32,
+    pub per_day_sks_rule: PerDaySksRule,
+    /// id_kelas -> id_waktu yang diizinkan (mis. kelas eksekutif hanya malam)
+    pub kelas_waktu_policy: HashMap<u32, Vec<u32>>,
+    pub exam: ExamConfig,
+    pub weights: PenaltyWeights,
+    /// How the raw penalty is turned into the reported 0–100 quality score
+    pub quality: QualityScale,
+    /// Ramps hard-constraint weights over the run; None keeps them static
+    pub penalty_schedule: Option<PenaltySchedule>,
+    pub durations: DurationOverrides,
+    pub missing_preferences: MissingPreferences,
+}
+
+/// How a lecturer who teaches but sent no preference row is treated
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MissingPreferences {
+    /// Available in every slot, so never penalized
+    #[default]
+    Available,
+    /// Unavailable in every slot, so every session is a violation
+    Unavailable,
+}
+
+/// Multipliers on `sks * minutes_per_sks`; a per-matkul entry wins over the
+/// per-type one
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
+#[serde(default)]
+pub struct DurationOverrides {
+    pub by_type: HashMap<CourseType, f32>,
+    pub by_matkul: HashMap<u32, f32>,
+}
+
+impl Default for ConstraintConfig {
+    fn default() -> Self {
+        Self {
+            mode: ScheduleMode::Weekly,
+            slots: SlotConfig::default(),
+            max_sks_per_day: 6,
+            per_day_sks_rule: PerDaySksRule::StrictFourCourse,
+            kelas_waktu_policy: HashMap::new(),
+            exam: ExamConfig::default(),
+            weights: PenaltyWeights::default(),
+            quality: QualityScale::default(),
+            penalty_schedule: None,
+            durations: DurationOverrides::default(),
+            missing_preferences: MissingPreferences::default(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PenaltyCurve {
+    Linear,
+    #[default]
+    Geometric,
+}
+
+/// Multiplier on hard-constraint penalties that moves from `start_factor` at
+/// the first iteration to `end_factor` at the last one
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(default)]
+pub struct PenaltySchedule {
+    pub start_factor: f32,
+    pub end_factor: f32,
+    pub curve: PenaltyCurve,
+}
+
+impl Default for PenaltySchedule {
+    fn default() -> Self {
+        Self {
+            start_factor: 0.1,
+            end_factor: 100.0,
+            curve: PenaltyCurve::Geometric,
+        }
+    }
+}
+
+impl PenaltySchedule {
+    pub fn factor(&self, iteration: usize, max_iterations: usize) -> f32 {
+        let t = iteration as f32 / max_iterations.saturating_sub(1).max(1) as f32;
+        let t = t.clamp(0.0, 1.0);
+
+        match self.curve {
+            PenaltyCurve::Linear => self.start_factor + (self.end_factor - self.start_factor) * t,
+            // Geometris butuh faktor positif; selain itu jatuh ke linear
+            PenaltyCurve::Geometric if self.start_factor > 0.0 && self.end_factor > 0.0 => {
+                self.start_factor * (self.end_factor / self.start_factor).powf(t)
+            }
+            PenaltyCurve::Geometric => self.start_factor + (self.end_factor - self.start_factor) * t,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SimulationScenario {
+    /// Dosen tidak bisa mengajar pada hari tertentu (kosong = seluruh minggu)
+    LecturerAbsence {
+        id_dosen: u32,
+        #[serde(default)]
+        hari: Vec<u32>,
+    },
+    /// Ruangan tidak bisa dipakai, mis. renovasi (kosong = seluruh minggu)
+    RoomOutage {
+        ruangan: u32,
+        #[serde(default)]
+        hari: Vec<u32>,
+    },
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SimulationRequest {
+    pub schedule: Vec<OptimizedCourse>,
+    pub time_preferences: Vec<TimePreferenceRequest>,
+    pub config: Option<ConstraintConfig>,
+    #[serde(default)]
+    pub external_bookings: Vec<ExternalBooking>,
+    pub scenario: SimulationScenario,
+}
+
+/// Instance checked by /preferences/impact before optimizing
+#[derive(Debug, Deserialize, Clone)]
+pub struct PreferenceImpactRequest {
+    pub courses: Vec<CourseRequest>,
+    pub time_preferences: Vec<TimePreferenceRequest>,
+    pub config: Option<ConstraintConfig>,
+}
+
+/// Incremental change to a finished job's course list
+#[derive(Debug, Deserialize, Clone)]
+pub struct CourseUpdateRequest {
+    #[serde(default)]
+    pub upsert: Vec<CourseRequest>,
+    #[serde(default)]
+    pub remove: Vec<u32>,
+}
+
+/// Part of a large request uploaded to an open optimization session
+#[derive(Debug, Deserialize, Clone)]
+pub struct SessionChunk {
+    #[serde(default)]
+    pub courses: Vec<CourseRequest>,
+    #[serde(default)]
+    pub time_preferences: Vec<TimePreferenceRequest>,
+    #[serde(default)]
+    pub lecturers: Vec<LecturerRequest>,
+    #[serde(default)]
+    pub external_bookings: Vec<ExternalBooking>,
+}
+
+/// Capacity varied by /simulate/capacity
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CapacityDimension {
+    /// Number of rooms: the first N of `rooms`, or `sum_ruangan`
+    Rooms,
+    /// `exam.days`; weekly schedules always span Senin - Jumat
+    ExamDays,
+}
+
+/// Instance plus the capacity values to try, each with a short run
+#[derive(Debug, Deserialize, Clone)]
+pub struct CapacityRequest {
+    pub request: OptimizationRequest,
+    pub dimension: CapacityDimension,
+    pub values: Vec<u32>,
+    // Iterasi per run; default sengaja pendek
+    pub max_iterations: Option<usize>,
+}
+
+/// Instance size for a dry-run cost estimate
+#[derive(Debug, Deserialize, Clone)]
+pub struct EstimateRequest {
+    pub courses: Vec<CourseRequest>,
+    pub parameters: Option<PsoParameters>,
+    #[serde(default)]
+    pub preset: Option<Preset>,
+}
+
+/// Partial overrides merged into a stored request before it is re-run
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct RerunRequest {
+    #[serde(default)]
+    pub parameters: serde_json::Map<String, serde_json::Value>,
+    #[serde(default)]
+    pub config: serde_json::Map<String, serde_json::Value>,
+}
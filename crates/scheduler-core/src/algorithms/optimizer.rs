@@ -0,0 +1,922 @@
+use std::collections::HashMap;
+#[cfg(feature = "runtime")]
+use std::time::{Duration, Instant};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use super::par::*;
+use super::{cross_list, decode::ScheduleBuilder, ga::Algorithm, float::Float, models::{
+        ConstraintConfig, CourseRequest, InertiaCurve, OptimizedCourse, Particle, PsoParameters, ScheduleChecker, SelectionRule, TerminationReason, DOSEN_TBA, PSO
+}};
+// Hanya untuk run async (PSO::run) dengan event progress
+#[cfg(feature = "runtime")]
+use super::{float::Precision, models::{BestSnapshot, FinishedRun, FitnessDistribution, OptimizationProgress, RunExtension}};
+
+// Seed run tanpa parameters.seed bila fitur runtime (entropi OS) tidak aktif
+#[cfg(not(feature = "runtime"))]
+const DEFAULT_SEED: u64 = 0x5eed;
+
+// Jeda throttle terpanjang per iterasi; nilai fraksi/laju yang sangat kecil
+// tidak boleh membuat Duration meluap
+#[cfg(feature = "runtime")]
+const MAX_THROTTLE_DELAY: Duration = Duration::from_secs(60);
+
+#[cfg(feature = "runtime")]
+fn capped_secs(secs: f32) -> Duration {
+    Duration::try_from_secs_f32(secs).map_or(MAX_THROTTLE_DELAY, |d| d.min(MAX_THROTTLE_DELAY))
+}
+
+impl<F: Float> Particle<F> {
+   
+    pub fn new(dimension: usize, seed: u64, velocity_bound: f32) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+      
+        let position: Vec<F> = (0..dimension)
+            .map(|_| F::random_range(&mut rng, F::ZERO, F::from_f32(1.0)))
+            .collect();
+            
+        let bound = F::from_f32(velocity_bound);
+        let velocity: Vec<F> = (0..dimension)
+            .map(|_| if velocity_bound > 0.0 { F::random_range(&mut rng, F::from_f32(-velocity_bound), bound) } else { F::ZERO })
+            .collect();
+
+        Particle {
+            position,
+            velocity,
+            pbest_position: vec![F::ZERO; dimension], 
+            pbest_fitness: f32::INFINITY,        
+            fitness: f32::INFINITY,              
+            hard_penalty: f32::INFINITY,
+            pbest_hard_penalty: f32::INFINITY,
+            rng,
+        }
+    }
+
+    /// Update velocity using standard PSO formula
+    pub fn update_velocity(
+        &mut self,
+        gbest: &[F],
+        inertia_weight: f32,
+        cognitive_weight: f32,
+        social_weight: f32,
+    ) {
+        let (inertia_weight, cognitive_weight, social_weight) =
+            (F::from_f32(inertia_weight), F::from_f32(cognitive_weight), F::from_f32(social_weight));
+        let coordinates = self.velocity.iter_mut().zip(&self.position).zip(&self.pbest_position).zip(gbest);
+        for (((velocity, &position), &pbest), &gbest) in coordinates {
+            let r1 = F::random(&mut self.rng);
+            let r2 = F::random(&mut self.rng);
+
+            let cognitive = cognitive_weight * r1 * (pbest - position);
+
+            let social = social_weight * r2 * (gbest - position);
+
+            *velocity = inertia_weight * *velocity + cognitive + social;
+        }
+    }
+
+    pub fn update_position(&mut self) {
+        for i in 0..self.position.len() {
+            self.position[i] += self.velocity[i];
+
+        }
+    }
+
+    pub fn update_personal_best(&mut self, selection: SelectionRule) {
+        if selection.is_better((self.hard_penalty, self.fitness), (self.pbest_hard_penalty, self.pbest_fitness)) {
+            self.pbest_fitness = self.fitness;
+            self.pbest_hard_penalty = self.hard_penalty;
+            self.pbest_position = self.position.clone();
+        }
+    }
+}
+
+impl OptimizedCourse {
+    /// Course with no day, time or room assigned yet
+    pub fn from_request(course: &CourseRequest) -> Self {
+        OptimizedCourse {
+            id_jadwal: course.id_jadwal,
+            id_matkul: course.id_matkul,
+            id_dosen: course.id_dosen,
+            id_kelas: course.id_kelas,
+            id_waktu: course.id_waktu,
+            hari: 0,
+            jam_mulai: 0,
+            jam_akhir: 0,
+            ruangan: 0,
+            semester: course.semester,
+            sks: course.sks,
+            prodi: course.prodi,
+            dosen_tba: course.id_dosen == DOSEN_TBA,
+            locked: false,
+        }
+    }
+
+    /// Course at its `locked` slot, if it has one
+    pub fn from_lock(course: &CourseRequest, config: &ConstraintConfig) -> Option<Self> {
+        let lock = course.locked?;
+        Some(OptimizedCourse {
+            id_waktu: config.effective_waktu(course),
+            hari: lock.hari,
+            jam_mulai: lock.jam_mulai,
+            jam_akhir: lock.jam_mulai + config.duration(course),
+            ruangan: lock.ruangan.unwrap_or(0),
+            locked: true,
+            ..OptimizedCourse::from_request(course)
+        })
+    }
+}
+
+impl PSO {
+    pub fn new(
+        courses: Vec<CourseRequest>,
+        checker: ScheduleChecker,
+        parameters: PsoParameters,
+    ) -> Self {
+        let dimension = courses.len() * 2; 
+        // Tanpa runtime tidak ada sumber entropi; pemanggil (mis. WASM) mengisi seed sendiri
+        #[cfg(feature = "runtime")]
+        let seed = parameters.seed.unwrap_or_else(rand::random);
+        #[cfg(not(feature = "runtime"))]
+        let seed = parameters.seed.unwrap_or(DEFAULT_SEED);
+
+        PSO {
+            particles: vec![],
+            sub_swarms: vec![],
+            initial_positions: vec![],
+            global_best_position: vec![0.0; dimension],
+            global_best_fitness: f32::INFINITY,
+            global_best_hard_penalty: f32::INFINITY,
+            best_snapshot: None,
+            improvements: vec![],
+            stopped_at: None,
+            termination: TerminationReason::default(),
+            extension: None,
+            job_id: None,
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            hard_factor: 1.0,
+            inertia: parameters.inertia_weight,
+            inertia_chaos: 0.7,
+            courses,
+            parameters,
+            checker,
+            #[cfg(feature = "runtime")]
+            status_tx: None,
+            #[cfg(feature = "runtime")]
+            cancel: None,
+            #[cfg(feature = "runtime")]
+            params_rx: None,
+            params_changes: vec![],
+            algorithm: Algorithm::default(),
+            diagnostics: vec![],
+            #[cfg(feature = "parallel")]
+            pool: None,
+        }
+    }
+
+    /// Runs the swarm with coordinates of `parameters.precision`
+    #[cfg(feature = "runtime")]
+    pub async fn run(self, run_info: Option<(usize, usize)>, all_best_fitness: &mut Vec<f32>) -> FinishedRun {
+        match self.parameters.precision {
+            Precision::F32 => self.run_at_precision(run_info, all_best_fitness).await,
+            Precision::F64 => self.with_precision::<f64>().run_at_precision(run_info, all_best_fitness).await,
+        }
+    }
+
+    /// The same, not yet started swarm with coordinates of type `G`
+    pub fn with_precision<G: Float>(self) -> PSO<G> {
+        let PSO {
+            particles: _,
+            sub_swarms: _,
+            initial_positions,
+            global_best_position,
+            global_best_fitness,
+            global_best_hard_penalty,
+            best_snapshot: _,
+            improvements,
+            stopped_at,
+            termination,
+            extension,
+            job_id,
+            seed,
+            rng,
+            hard_factor,
+            inertia,
+            inertia_chaos,
+            parameters,
+            courses,
+            checker,
+            #[cfg(feature = "runtime")]
+            status_tx,
+            #[cfg(feature = "runtime")]
+            cancel,
+            #[cfg(feature = "runtime")]
+            params_rx,
+            params_changes,
+            algorithm,
+            diagnostics,
+            #[cfg(feature = "parallel")]
+            pool,
+        } = self;
+
+        PSO {
+            particles: vec![],
+            sub_swarms: vec![],
+            initial_positions,
+            global_best_position: global_best_position.into_iter().map(G::from_f32).collect(),
+            global_best_fitness,
+            global_best_hard_penalty,
+            best_snapshot: None,
+            improvements,
+            stopped_at,
+            termination,
+            extension,
+            job_id,
+            seed,
+            rng,
+            hard_factor,
+            inertia,
+            inertia_chaos,
+            parameters,
+            courses,
+            checker,
+            #[cfg(feature = "runtime")]
+            status_tx,
+            #[cfg(feature = "runtime")]
+            cancel,
+            #[cfg(feature = "runtime")]
+            params_rx,
+            params_changes,
+            algorithm,
+            diagnostics,
+            #[cfg(feature = "parallel")]
+            pool,
+        }
+    }
+
+    /// Maps a position found for `old_courses` onto `new_courses` by id_jadwal;
+    /// courses without a counterpart get fresh random values.
+    pub fn transfer_position(
+        old_courses: &[CourseRequest],
+        old_position: &[f32],
+        new_courses: &[CourseRequest],
+        rng: &mut impl Rng,
+    ) -> Vec<f32> {
+        let old_index: HashMap<u32, usize> = old_courses.iter().enumerate().map(|(i, c)| (c.id_jadwal, i)).collect();
+
+        new_courses
+            .iter()
+            .flat_map(|course| match old_index.get(&course.id_jadwal) {
+                Some(&i) if i * 2 + 1 < old_position.len() => [old_position[i * 2], old_position[i * 2 + 1]],
+                _ => [rng.random_range(0.0..1.0), rng.random_range(0.0..1.0)],
+            })
+            .collect()
+    }
+
+    /// `position` with every coordinate moved by up to `amplitude` either
+    /// way, as the starting point of a follow-up run
+    pub fn perturb_position(position: &[f32], amplitude: f32, rng: &mut impl Rng) -> Vec<f32> {
+        if !amplitude.is_finite() || amplitude <= 0.0 {
+            return position.to_vec();
+        }
+        position.iter().map(|v| v + rng.random_range(-amplitude..amplitude)).collect()
+    }
+
+    pub fn position_to_schedule<P: Float>(
+        position: &[P],
+        courses: &[CourseRequest],
+        config: &ConstraintConfig,
+    ) -> Vec<OptimizedCourse> {
+        ScheduleBuilder::new(courses, config).build(position)
+    }
+}
+
+impl<F: Float> PSO<F> {
+    #[cfg(feature = "runtime")]
+    async fn run_at_precision(mut self, run_info: Option<(usize, usize)>, all_best_fitness: &mut Vec<f32>) -> FinishedRun {
+        let (best_position, fitness) = self.optimize(run_info, all_best_fitness).await;
+        FinishedRun {
+            best_position,
+            fitness,
+            improvements: self.improvements,
+            params_changes: self.params_changes,
+            stopped_at: self.stopped_at,
+            termination: self.termination,
+            extension: self.extension,
+            diagnostics: self.diagnostics,
+            params_rx: self.params_rx,
+        }
+    }
+
+    /// Best position (as f32, the form positions are stored and replayed in)
+    /// and its fitness
+    #[cfg(feature = "runtime")]
+    pub async fn optimize(
+        &mut self,
+        run_info: Option<(usize, usize)>,
+        all_best_fitness: &mut Vec<f32>,
+    ) -> (Vec<f32>, f32) {
+        let start_time = Instant::now();
+        let (current_run, total_runs) = run_info.unwrap_or((0, 0));
+
+        let constructed = self.prepare();
+
+        let mut iterations_done = 0;
+        let mut last_report = None;
+        let mut iteration_limit = self.parameters.max_iterations;
+        let mut iteration = 0;
+
+        loop {
+            if constructed || (iteration >= iteration_limit && !self.extend_run(&mut iteration_limit, &start_time)) {
+                break;
+            }
+
+            if self.cancelled() {
+                println!("⛔ Optimization stopped at iteration {}", iteration);
+                self.stopped_at = Some(iteration);
+                self.termination = TerminationReason::UserStop;
+                break;
+            }
+
+            // Minimal satu iterasi agar selalu ada global best
+            if iteration > 0
+                && self
+                    .parameters
+                    .time_limit_secs
+                    .is_some_and(|limit| start_time.elapsed().as_secs_f32() >= limit)
+            {
+                println!("Time limit reached at iteration {}", iteration);
+                self.termination = TerminationReason::Timeout;
+                break;
+            }
+
+            if self.apply_live_params(iteration, &mut iteration_limit) {
+                continue;
+            }
+
+            let iteration_start = Instant::now();
+
+            #[cfg(feature = "chaos")]
+            self.inject_faults(iteration).await;
+
+            let stop = self.iterate(iteration);
+            iterations_done = iteration + 1;
+            if let Some(reason) = stop {
+                self.termination = reason;
+                break;
+            }
+
+            if self.should_report(iteration + 1, &mut last_report) {
+                self.progress(iteration + 1, &start_time, all_best_fitness, current_run, total_runs, false);
+            }
+
+            if let Some(delay) = self.throttle_delay(iteration_start.elapsed()) {
+                tokio::time::sleep(delay).await;
+            }
+
+            iteration += 1;
+        }
+
+        if let Some(refinement) = self.parameters.refinement.clone() {
+            if !constructed && self.termination != TerminationReason::UserStop && self.in_pool(|this| this.refine(&refinement)) {
+                let fitness = self.reported_fitness();
+                println!("Refinement improved best fitness to {:.6}", fitness);
+                self.improvements.push((iterations_done, fitness));
+            }
+        }
+
+        if let Some(extension) = &mut self.extension {
+            extension.feasible = self.global_best_hard_penalty == 0.0;
+        }
+
+        // Final results
+        let best_fitness = self.reported_fitness();
+        all_best_fitness.push(best_fitness);
+        self.progress(iterations_done, &start_time, all_best_fitness, current_run, total_runs, true);
+
+        println!("Optimization completed - Best fitness: {:.6}", best_fitness);
+        (self.global_best_position.iter().map(|v| v.to_f32()).collect(), best_fitness)
+    }
+
+    /// Resets the run and seeds the swarm. Returns true when the schedule
+    /// was constructed directly and there is nothing left to iterate.
+    pub(super) fn prepare(&mut self) -> bool {
+        self.reset_optimization();
+
+        let constructed = self.construct_directly();
+        if !constructed {
+            self.initialize_swarm();
+        }
+        constructed
+    }
+
+    /// One swarm iteration without channels, timers or sleeps: evaluation,
+    /// global best, particle moves and the convergence checks. Returns why
+    /// the run should end after it, if it should.
+    pub(super) fn iterate(&mut self, iteration: usize) -> Option<TerminationReason> {
+        self.apply_penalty_schedule(iteration);
+        self.apply_inertia_schedule(iteration);
+        let pbest_before = self.parameters.record_diagnostics.then(|| self.pbest_snapshot());
+
+        self.in_pool(|pso| {
+            if pso.is_cooperative() {
+                pso.cooperative_step();
+            } else {
+                pso.evaluate_all_particles();
+                pso.update_global_best();
+            }
+        });
+
+        let best = self.reported_fitness();
+        if self.improvements.last().is_none_or(|&(_, last)| best < last) {
+            self.improvements.push((iteration + 1, best));
+        }
+
+        match self.algorithm {
+            Algorithm::Ga => self.evolve_population(),
+            Algorithm::Pso if !self.is_cooperative() => self.in_pool(Self::update_all_particles),
+            Algorithm::Pso => {}
+        }
+        if let Some(pbest_before) = pbest_before {
+            self.record_diagnostics(iteration + 1, &pbest_before);
+        }
+
+        if self.reported_fitness() < 0.001 {
+            println!("Early stopping: Optimal solution found at iteration {}", iteration);
+            return Some(TerminationReason::TargetFitness);
+        }
+
+        if self.reached_feasible_target(iteration) {
+            println!("Early stopping: feasible schedule within soft threshold at iteration {}", iteration);
+            return Some(TerminationReason::FeasibleTarget);
+        }
+
+        let last_improvement = self.improvements.last().map_or(0, |&(at, _)| at);
+        if self
+            .parameters
+            .max_stagnation_iterations
+            .is_some_and(|limit| iteration + 1 - last_improvement >= limit)
+        {
+            println!("Stagnation: no improvement since iteration {}", last_improvement);
+            return Some(TerminationReason::Stagnation);
+        }
+
+        None
+    }
+
+    /// Runs `step` on the job's own thread pool when it has one, so its
+    /// parallel loops don't compete with other jobs on the global pool
+    pub(super) fn in_pool<R: Send>(&mut self, step: impl FnOnce(&mut Self) -> R + Send) -> R {
+        #[cfg(feature = "parallel")]
+        if let Some(pool) = self.pool.clone() {
+            return pool.install(|| step(self));
+        }
+        step(self)
+    }
+
+    /// Moves the hard-penalty multiplier to this iteration's value and rescales
+    /// stored bests so they stay comparable with newly evaluated particles
+    fn apply_penalty_schedule(&mut self, iteration: usize) {
+        let Some(schedule) = &self.checker.config.penalty_schedule else {
+            return;
+        };
+
+        let factor = schedule.factor(iteration, self.parameters.max_iterations);
+        let delta = factor - self.hard_factor;
+        self.hard_factor = factor;
+
+        let sub_particles = self.sub_swarms.iter_mut().flat_map(|s| s.particles.iter_mut());
+        for particle in self.particles.iter_mut().chain(sub_particles) {
+            if particle.pbest_hard_penalty.is_finite() {
+                particle.pbest_fitness += particle.pbest_hard_penalty * delta;
+            }
+        }
+        if self.global_best_hard_penalty.is_finite() {
+            self.global_best_fitness += self.global_best_hard_penalty * delta;
+        }
+    }
+
+    /// Moves the inertia weight to this iteration's value; without a schedule
+    /// it follows `parameters.inertia_weight`
+    fn apply_inertia_schedule(&mut self, iteration: usize) {
+        let Some(schedule) = &self.parameters.inertia_schedule else {
+            self.inertia = self.parameters.inertia_weight;
+            return;
+        };
+
+        let last_improvement = self.improvements.last().map_or(0, |&(at, _)| at);
+        let stagnation = iteration.saturating_sub(last_improvement);
+        self.inertia = schedule.weight(
+            iteration,
+            self.parameters.max_iterations,
+            self.inertia,
+            &mut self.inertia_chaos,
+            stagnation,
+        );
+    }
+
+    /// Applies tweaks queued by /jobs/:id/params. Returns true when a lowered
+    /// `max_iterations` means the loop should re-check its limit first.
+    #[cfg(feature = "runtime")]
+    fn apply_live_params(&mut self, iteration: usize, iteration_limit: &mut usize) -> bool {
+        let Some(rx) = &mut self.params_rx else {
+            return false;
+        };
+        let mut pending = Vec::new();
+        while let Ok(params) = rx.try_recv() {
+            pending.push(params);
+        }
+
+        let mut weights_changed = false;
+        let mut limit_lowered = false;
+        for params in pending {
+            if let Some(max_iterations) = params.max_iterations {
+                limit_lowered |= max_iterations <= iteration;
+                *iteration_limit = max_iterations;
+                self.parameters.max_iterations = max_iterations;
+            }
+            if let Some(w) = params.inertia_weight {
+                // Bobot manual menggantikan jadwal inersia untuk sisa run
+                self.parameters.inertia_weight = w;
+                self.parameters.inertia_schedule = None;
+            }
+            if let Some(w) = params.cognitive_weight {
+                self.parameters.cognitive_weight = w;
+            }
+            if let Some(w) = params.social_weight {
+                self.parameters.social_weight = w;
+            }
+            if !params.weights.is_empty() {
+                params.apply_weights(&mut self.checker.config.weights);
+                weights_changed = true;
+            }
+            println!("Parameters updated at iteration {}", iteration);
+            self.params_changes.push((iteration, params));
+        }
+
+        if weights_changed && iteration > 0 {
+            self.in_pool(Self::rescore_bests);
+        }
+        limit_lowered
+    }
+
+    /// Re-evaluates personal and global bests after the penalty weights
+    /// changed, so old scores don't beat new ones unfairly
+    #[cfg(feature = "runtime")]
+    fn rescore_bests(&mut self) {
+        let this = &*self;
+        let global = &self.global_best_position;
+        let scored: Vec<Vec<(f32, f32)>> = std::iter::once(&self.particles)
+            .chain(self.sub_swarms.iter().map(|s| &s.particles))
+            .enumerate()
+            .map(|(swarm, particles)| {
+                particles
+                    .par_iter()
+                    .map(|p| {
+                        if !p.pbest_fitness.is_finite() {
+                            return (p.pbest_hard_penalty, p.pbest_fitness);
+                        }
+                        if swarm == 0 {
+                            return this.evaluate_position(&p.pbest_position);
+                        }
+                        // Partikel sub-swarm hanya memegang sebagian dimensi
+                        let mut position = global.clone();
+                        for (&dim, &value) in this.sub_swarms[swarm - 1].dims.iter().zip(&p.pbest_position) {
+                            position[dim] = value;
+                        }
+                        this.evaluate_position(&position)
+                    })
+                    .collect()
+            })
+            .collect();
+        let global_score = self.global_best_fitness.is_finite().then(|| self.evaluate_position(global));
+
+        let swarms = std::iter::once(&mut self.particles).chain(self.sub_swarms.iter_mut().map(|s| &mut s.particles));
+        for (particles, scores) in swarms.zip(scored) {
+            for (particle, (hard, fitness)) in particles.iter_mut().zip(scores) {
+                particle.pbest_hard_penalty = hard;
+                particle.pbest_fitness = fitness;
+            }
+        }
+        if let Some((hard, fitness)) = global_score {
+            self.global_best_hard_penalty = hard;
+            self.global_best_fitness = fitness;
+        }
+    }
+
+    /// With `feasible_soft_threshold`, whether some particle has no hard
+    /// conflicts and a soft penalty within the threshold. A personal best
+    /// that qualifies while the global best doesn't becomes the global best.
+    fn reached_feasible_target(&mut self, iteration: usize) -> bool {
+        let Some(threshold) = self.parameters.feasible_soft_threshold else {
+            return false;
+        };
+        // Tanpa bentrok keras, fitness hanya berisi penalti lunak
+        if self.global_best_hard_penalty == 0.0 && self.global_best_fitness <= threshold {
+            return true;
+        }
+
+        let Some(best) = self
+            .particles
+            .iter()
+            .filter(|p| p.pbest_hard_penalty == 0.0 && p.pbest_fitness <= threshold)
+            .min_by(|a, b| a.pbest_fitness.total_cmp(&b.pbest_fitness))
+        else {
+            return false;
+        };
+        self.global_best_fitness = best.pbest_fitness;
+        self.global_best_hard_penalty = 0.0;
+        self.global_best_position = best.pbest_position.clone();
+
+        let fitness = self.reported_fitness();
+        if self.improvements.last().is_none_or(|&(_, last)| fitness < last) {
+            self.improvements.push((iteration + 1, fitness));
+        }
+        true
+    }
+
+    /// Global best fitness with hard penalties at their configured weight
+    pub(super) fn reported_fitness(&self) -> f32 {
+        if !self.global_best_hard_penalty.is_finite() {
+            return self.global_best_fitness;
+        }
+        self.global_best_fitness + self.global_best_hard_penalty * (1.0 - self.hard_factor)
+    }
+
+    /// With `require_feasible`, raises the iteration limit by one increment
+    /// while hard conflicts remain, up to `max_extension_secs` (default 60s)
+    /// past the start of the run
+    #[cfg(feature = "runtime")]
+    fn extend_run(&mut self, iteration_limit: &mut usize, start_time: &Instant) -> bool {
+        if !self.parameters.require_feasible || self.parameters.max_iterations == 0 {
+            return false;
+        }
+
+        let extension = self.extension.get_or_insert_with(RunExtension::default);
+        extension.feasible = self.global_best_hard_penalty == 0.0;
+
+        let cap = self.parameters.max_extension_secs.unwrap_or(60.0);
+        if extension.feasible || start_time.elapsed().as_secs_f32() >= cap {
+            return false;
+        }
+
+        let step = self.parameters.extension_iterations.unwrap_or(100).max(1);
+        extension.extensions += 1;
+        extension.extra_iterations += step;
+        *iteration_limit += step;
+        println!("Hard conflicts remain, extending run by {} iterations", step);
+        true
+    }
+
+    /// Whether /stop or /jobs/:id/cancel asked this run to end
+    pub(super) fn cancelled(&self) -> bool {
+        #[cfg(feature = "runtime")]
+        return self.cancel.as_ref().is_some_and(|cancel| cancel.is_cancelled());
+        #[cfg(not(feature = "runtime"))]
+        false
+    }
+
+    /// Idle time after an iteration so the run stays under `max_cpu_fraction`
+    /// and `max_iterations_per_second`; the stricter of the two wins.
+    #[cfg(feature = "runtime")]
+    fn throttle_delay(&self, busy: Duration) -> Option<Duration> {
+        let by_fraction = self
+            .parameters
+            .max_cpu_fraction
+            .filter(|f| *f > 0.0 && *f < 1.0)
+            .map(|f| capped_secs(busy.as_secs_f32() * (1.0 - f) / f));
+
+        let by_rate = self
+            .parameters
+            .max_iterations_per_second
+            .filter(|r| *r > 0.0)
+            .map(|r| capped_secs(1.0 / r).saturating_sub(busy));
+
+        by_fraction.max(by_rate).filter(|d| !d.is_zero())
+    }
+
+    /// With at most one session to place every position decodes to the same
+    /// schedule, so that schedule becomes the global best without a swarm
+    fn construct_directly(&mut self) -> bool {
+        let followers = cross_list::leaders(&self.courses);
+        if self.courses.iter().filter(|c| !followers.contains_key(&c.id_jadwal)).count() > 1 {
+            return false;
+        }
+
+        let (hard, fitness) = self.evaluate_position(&self.global_best_position);
+        self.global_best_hard_penalty = hard;
+        self.global_best_fitness = fitness;
+        self.improvements.push((0, self.reported_fitness()));
+        self.termination = TerminationReason::Constructed;
+        true
+    }
+
+    fn reset_optimization(&mut self) {
+        self.global_best_fitness = f32::INFINITY;
+        self.global_best_hard_penalty = f32::INFINITY;
+        self.hard_factor = 1.0;
+        self.improvements.clear();
+        self.stopped_at = None;
+        self.termination = TerminationReason::MaxIterations;
+        self.extension = None;
+        self.diagnostics.clear();
+        self.global_best_position.fill(F::ZERO);
+        self.particles.clear();
+        self.sub_swarms.clear();
+        self.rng = StdRng::seed_from_u64(self.seed);
+        self.inertia = match &self.parameters.inertia_schedule {
+            Some(schedule) => schedule.start,
+            None => self.parameters.inertia_weight,
+        };
+        if self.parameters.inertia_schedule.as_ref().is_some_and(|s| s.curve == InertiaCurve::Chaotic) {
+            self.inertia_chaos = self.rng.random_range(0.05..0.95);
+        }
+    }
+
+    fn initialize_swarm(&mut self) {
+        if self.is_cooperative() {
+            self.initialize_sub_swarms();
+            return;
+        }
+
+        let dimension = self.courses.len() * 2;
+        
+        let rng = &mut self.rng;
+        let velocity_bound = self.parameters.velocity_init.bound(self.parameters.velocity_scale);
+        self.particles = (0..self.parameters.swarm_size)
+            .map(|_| Particle::new(dimension, rng.random(), velocity_bound))
+            .collect();
+
+        // Maksimal separuh swarm diisi posisi awal agar eksplorasi tetap ada
+        let seeded = self.initial_positions.len().min(self.particles.len() / 2);
+        for (particle, position) in self.particles.iter_mut().zip(&self.initial_positions).take(seeded) {
+            if position.len() == dimension {
+                particle.position = position.iter().map(|&v| F::from_f32(v)).collect();
+            }
+        }
+    }
+
+    fn evaluate_all_particles(&mut self) {
+        let courses = self.courses.clone();
+        let checker = self.checker.clone();
+        let config = &checker.config;
+        let selection = self.parameters.selection;
+        let hard_factor = self.hard_factor;
+
+        self.particles.par_iter_mut().for_each(|particle| {
+            let schedule = PSO::position_to_schedule(&particle.position, &courses, config);
+            let (hard, soft) = checker.evaluate_split(&schedule);
+            particle.hard_penalty = hard as f32;
+            particle.fitness = hard as f32 * hard_factor + soft as f32;
+            particle.update_personal_best(selection);
+        });
+    }
+
+    fn update_global_best(&mut self) {
+        let selection = self.parameters.selection;
+
+        for particle in &self.particles {
+            let candidate = (particle.pbest_hard_penalty, particle.pbest_fitness);
+            if selection.is_better(candidate, (self.global_best_hard_penalty, self.global_best_fitness)) {
+                self.global_best_fitness = particle.pbest_fitness;
+                self.global_best_hard_penalty = particle.pbest_hard_penalty;
+                self.global_best_position = particle.pbest_position.clone();
+                self.best_snapshot = None;
+            }
+        }
+    }
+
+    fn update_all_particles(&mut self) {
+        let global_best_position = self.global_best_position.clone();
+        let params = self.parameters.clone();
+        let inertia = self.inertia;
+
+        self.particles.par_iter_mut().for_each(|particle| {
+            particle.update_velocity(
+                &global_best_position,
+                inertia,
+                params.cognitive_weight,
+                params.social_weight,
+            );
+            particle.update_position();
+        });
+    }
+
+    #[cfg(feature = "runtime")]
+    fn progress(
+        &mut self,
+        iteration: usize,
+        start_time: &Instant,
+        all_best_fitness: &[f32],
+        current_run: usize,
+        total_runs: usize,
+        is_finished: bool,
+    ) {
+        // Decode ulang global best hanya untuk event; lewati bila tidak ada yang mendengarkan
+        if self.status_tx.as_ref().is_none_or(|tx| tx.receiver_count() == 0) {
+            return;
+        }
+        // Posisi ikut dibandingkan karena refine/kooperatif/ga mengganti global best di luar update_global_best
+        let improved = !self.best_snapshot.as_ref().is_some_and(|snapshot| snapshot.position == self.global_best_position);
+        if improved {
+            let schedule = PSO::position_to_schedule(&self.global_best_position, &self.courses, &self.checker.config);
+            self.best_snapshot = Some(BestSnapshot {
+                position: self.global_best_position.clone(),
+                conflicts: self.checker.count_conflicts(&schedule),
+                schedule,
+            });
+        }
+        let Some(best) = &self.best_snapshot else {
+            return;
+        };
+
+        let elapsed_time = start_time.elapsed();
+        let timestamp = chrono::Utc::now();
+         let progress = OptimizationProgress {
+            job_id: self.job_id,
+            iteration,
+            elapsed_time,
+            timestamp,
+            started_at: chrono::Duration::from_std(elapsed_time).ok().map(|elapsed| timestamp - elapsed),
+            all_best_fitness: Some(all_best_fitness.to_vec()),
+            best_fitness: self.reported_fitness(),
+            quality_score: self.checker.config.quality.score(self.reported_fitness()),
+            current_run: Some(current_run),
+            total_runs: Some(total_runs),
+            is_finished,
+            job_finished: false,
+            improved,
+            conflict_counts: best.conflicts,
+            schedule: None,
+            fitness_distribution: self.fitness_distribution(iteration),
+            seed: Some(self.seed),
+            stats: self.wants_stats(iteration, is_finished).then(|| self.checker.schedule_stats(&best.schedule)),
+            truncated: false,
+            truncated_fields: Vec::new(),
+        };
+
+        // Penerima bisa saja lepas di antaranya
+        if let Some(tx) = &self.status_tx {
+            let _ = tx.send(progress);
+        }
+    }
+    
+    /// Whether an intermediate progress event goes out for this iteration:
+    /// every `report_every_n_iterations` (or when stats are due), and no
+    /// sooner than `report_min_interval_ms` after the previous one
+    #[cfg(feature = "runtime")]
+    fn should_report(&self, iteration: usize, last_report: &mut Option<Instant>) -> bool {
+        let every = self.parameters.report_every_n_iterations.filter(|&n| n > 0).unwrap_or(1);
+        if !iteration.is_multiple_of(every) && !self.wants_stats(iteration, false) {
+            return false;
+        }
+        if let (Some(ms), Some(last)) = (self.parameters.report_min_interval_ms, *last_report) {
+            if last.elapsed() < Duration::from_millis(ms) {
+                return false;
+            }
+        }
+
+        *last_report = Some(Instant::now());
+        true
+    }
+
+    #[cfg(feature = "runtime")]
+    fn wants_stats(&self, iteration: usize, is_finished: bool) -> bool {
+        match self.parameters.stats_interval {
+            Some(interval) if interval > 0 => is_finished || iteration.is_multiple_of(interval),
+            _ => false,
+        }
+    }
+
+    /// Percentiles over `fitness_sample_size` randomly chosen particles,
+    /// with hard penalties at their configured weight
+    #[cfg(feature = "runtime")]
+    fn fitness_distribution(&self, iteration: usize) -> Option<FitnessDistribution> {
+        let size = self.parameters.fitness_sample_size?.min(self.particles.len());
+        if size == 0 {
+            return None;
+        }
+
+        let mut rng = StdRng::seed_from_u64(self.seed.wrapping_add(iteration as u64));
+        let mut sample: Vec<f32> = rand::seq::index::sample(&mut rng, self.particles.len(), size)
+            .into_iter()
+            .map(|i| &self.particles[i])
+            .map(|p| p.fitness + p.hard_penalty * (1.0 - self.hard_factor))
+            .filter(|f| f.is_finite())
+            .collect();
+        if sample.is_empty() {
+            return None;
+        }
+        sample.sort_by(f32::total_cmp);
+
+        let at = |q: f32| sample[((sample.len() - 1) as f32 * q).round() as usize];
+        Some(FitnessDistribution {
+            sampled: sample.len(),
+            min: sample[0],
+            p25: at(0.25),
+            median: at(0.5),
+            p75: at(0.75),
+            max: sample[sample.len() - 1],
+        })
+    }
+}
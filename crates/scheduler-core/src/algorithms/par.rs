@@ -0,0 +1,24 @@
+//! Parallel iteration over particles and schedules: rayon's parallel
+//! iterators with the `parallel` feature, plain slice iterators on the
+//! calling thread without it (e.g. wasm32 without threads).
+
+#[cfg(feature = "parallel")]
+pub use rayon::prelude::*;
+
+/// Sequential stand-in for rayon's `par_iter`/`par_iter_mut`
+#[cfg(not(feature = "parallel"))]
+pub trait SequentialSlice<T> {
+    fn par_iter(&self) -> std::slice::Iter<'_, T>;
+    fn par_iter_mut(&mut self) -> std::slice::IterMut<'_, T>;
+}
+
+#[cfg(not(feature = "parallel"))]
+impl<T> SequentialSlice<T> for [T] {
+    fn par_iter(&self) -> std::slice::Iter<'_, T> {
+        self.iter()
+    }
+
+    fn par_iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.iter_mut()
+    }
+}
@@ -0,0 +1,82 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::models::{InertiaCurve, InertiaSchedule, PsoParameters};
+
+/// Named parameter sets for callers who don't want to tune the swarm
+/// themselves. Selected with `preset` in a request; explicit `parameters`
+/// take precedence.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum Preset {
+    FastDraft,
+    Balanced,
+    Exhaustive,
+}
+
+/// One preset as listed by GET /presets
+#[derive(Debug, Serialize, Clone)]
+pub struct PresetInfo {
+    pub name: Preset,
+    pub description: &'static str,
+    /// Typical runtime relative to `balanced`
+    pub runtime_multiplier: f32,
+    pub parameters: PsoParameters,
+}
+
+impl Preset {
+    pub const ALL: [Preset; 3] = [Preset::FastDraft, Preset::Balanced, Preset::Exhaustive];
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Preset::FastDraft => "Draf cepat untuk melihat gambaran jadwal; bentrok masih mungkin tersisa",
+            Preset::Balanced => "Pilihan umum: hasil baik dalam waktu wajar",
+            Preset::Exhaustive => "Pencarian panjang untuk jadwal final; terus berjalan selama masih ada bentrok",
+        }
+    }
+
+    // Rasio jumlah evaluasi (swarm_size x max_iterations) terhadap balanced;
+    // early stopping membuat run nyata bisa lebih pendek
+    pub fn runtime_multiplier(self) -> f32 {
+        match self {
+            Preset::FastDraft => 0.15,
+            Preset::Balanced => 1.0,
+            Preset::Exhaustive => 5.0,
+        }
+    }
+
+    pub fn parameters(self) -> PsoParameters {
+        let decay = Some(InertiaSchedule { start: 0.9, end: 0.4, curve: InertiaCurve::LinearDecay });
+        match self {
+            Preset::FastDraft => PsoParameters {
+                swarm_size: 15,
+                max_iterations: 150,
+                inertia_weight: 0.6,
+                max_stagnation_iterations: Some(40),
+                ..PsoParameters::default()
+            },
+            Preset::Balanced => PsoParameters {
+                inertia_schedule: decay,
+                max_stagnation_iterations: Some(150),
+                ..PsoParameters::default()
+            },
+            Preset::Exhaustive => PsoParameters {
+                swarm_size: 50,
+                max_iterations: 1500,
+                inertia_schedule: decay,
+                require_feasible: true,
+                conflict_retries: Some(2),
+                ..PsoParameters::default()
+            },
+        }
+    }
+
+    pub fn info(self) -> PresetInfo {
+        PresetInfo {
+            name: self,
+            description: self.description(),
+            runtime_multiplier: self.runtime_multiplier(),
+            parameters: self.parameters(),
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::{
+    models::{OptimizedCourse, ScheduleMode},
+    repair::ScheduleRepairer,
+};
+use crate::locale::Locale;
+
+/// Change to the input that would let the engine remove some conflicts
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Relaxation {
+    /// Mark a day/window as available in the lecturer's preferences
+    OpenSlot { id_dosen: u32, hari: u32, id_waktu: u32 },
+    /// Add rooms to the room list
+    AddRooms { count: u32 },
+}
+
+#[derive(Serialize)]
+pub struct RelaxationSuggestion {
+    #[serde(flatten)]
+    pub relaxation: Relaxation,
+    /// Clashing courses that could move if only this changed (an upper bound,
+    /// since they may compete for the same opening), or unassigned courses for rooms
+    pub resolves: usize,
+    pub deskripsi: String,
+}
+
+impl ScheduleRepairer {
+    /// Suggestions for a schedule with unavoidable conflicts, most useful first.
+    /// A clashing course counts towards opening a lecturer slot when that slot
+    /// is free of clashes and bookings and only the preference keeps it out.
+    pub fn suggest_relaxations(&self, schedule: &[OptimizedCourse], unassigned_rooms: &[u32]) -> Vec<RelaxationSuggestion> {
+        let mut counts: HashMap<Relaxation, usize> = HashMap::new();
+
+        if self.checker.config.mode == ScheduleMode::Weekly {
+            let mut stuck: Vec<u32> = self
+                .checker
+                .detect_conflicts(schedule)
+                .conflicts
+                .iter()
+                .map(|c| c.jadwal_b)
+                .chain(schedule.iter().filter(|c| self.checker.booking_clash(c).is_some()).map(|c| c.id_jadwal))
+                .collect();
+            stuck.sort_unstable();
+            stuck.dedup();
+
+            // Kuliah yang bisa pindah ke slot bebas dipindahkan dulu, supaya kuliah
+            // berikutnya tidak menghitung slot yang sama dua kali
+            let mut working = schedule.to_vec();
+            for id_jadwal in stuck {
+                let Some(index) = working.iter().position(|c| c.id_jadwal == id_jadwal) else {
+                    continue;
+                };
+                match self.openings(&working[index], &working) {
+                    Ok(moved) => working[index] = moved,
+                    Err(openings) => {
+                        for relaxation in openings {
+                            *counts.entry(relaxation).or_default() += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        let extra_rooms = Self::rooms_needed(schedule, unassigned_rooms);
+        if extra_rooms > 0 {
+            counts.insert(Relaxation::AddRooms { count: extra_rooms }, unassigned_rooms.len());
+        }
+
+        let mut suggestions: Vec<RelaxationSuggestion> = counts
+            .into_iter()
+            .map(|(relaxation, resolves)| RelaxationSuggestion {
+                deskripsi: Self::describe(&relaxation, resolves),
+                relaxation,
+                resolves,
+            })
+            .collect();
+        suggestions.sort_by(|a, b| b.resolves.cmp(&a.resolves).then_with(|| a.relaxation.cmp(&b.relaxation)));
+        suggestions
+    }
+
+    /// A free preferred slot for the course, or else the lecturer slots it
+    /// could move into if the preference allowed it
+    fn openings(&self, course: &OptimizedCourse, schedule: &[OptimizedCourse]) -> Result<OptimizedCourse, Vec<Relaxation>> {
+        let duration = course.jam_akhir.saturating_sub(course.jam_mulai);
+        let (start, end) = self.checker.config.slots.window(course.id_waktu);
+        let step = self.checker.config.slots.minutes_per_sks.max(1);
+
+        let mut openings = Vec::new();
+        for hari in 1..=5 {
+            let mut jam_mulai = start;
+            while jam_mulai + duration <= end {
+                let candidate = OptimizedCourse {
+                    hari,
+                    jam_mulai,
+                    jam_akhir: jam_mulai + duration,
+                    ..course.clone()
+                };
+                let free = self.checker.booking_clash(&candidate).is_none()
+                    && schedule
+                        .iter()
+                        .all(|other| other.id_jadwal == course.id_jadwal || !self.clashes(&candidate, other));
+                if free {
+                    // Sudah ada slot bebas yang disukai: cukup diperbaiki, tidak perlu relaksasi
+                    if self.checker.is_preferred(course.id_dosen, hari, jam_mulai) {
+                        return Ok(candidate);
+                    }
+                    let opening = Relaxation::OpenSlot {
+                        id_dosen: course.id_dosen,
+                        hari,
+                        id_waktu: course.id_waktu,
+                    };
+                    if !openings.contains(&opening) {
+                        openings.push(opening);
+                    }
+                }
+                jam_mulai += step;
+            }
+        }
+        Err(openings)
+    }
+
+    /// Most unassigned courses running at the same moment
+    fn rooms_needed(schedule: &[OptimizedCourse], unassigned_rooms: &[u32]) -> u32 {
+        let unassigned: Vec<&OptimizedCourse> =
+            schedule.iter().filter(|c| unassigned_rooms.contains(&c.id_jadwal)).collect();
+
+        unassigned
+            .iter()
+            .map(|a| {
+                unassigned
+                    .iter()
+                    .filter(|b| b.hari == a.hari && b.jam_mulai <= a.jam_mulai && a.jam_mulai < b.jam_akhir)
+                    .count() as u32
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn describe(relaxation: &Relaxation, resolves: usize) -> String {
+        match relaxation {
+            Relaxation::OpenSlot { id_dosen, hari, id_waktu } => format!(
+                "Buka {} {} untuk dosen {} (menyelesaikan {} konflik).",
+                Locale::Id.day_name(*hari),
+                if *id_waktu == 2 { "malam" } else { "pagi" },
+                id_dosen,
+                resolves
+            ),
+            Relaxation::AddRooms { count } => {
+                format!("Tambah {} ruangan (menempatkan {} kelas tanpa ruangan).", count, resolves)
+            }
+        }
+    }
+}
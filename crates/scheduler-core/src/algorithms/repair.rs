@@ -0,0 +1,357 @@
+use std::{cmp::Reverse, collections::HashMap};
+
+use serde::Serialize;
+
+use super::models::{CourseRequest, OptimizedCourse, ScheduleChecker, SimulationScenario, DOSEN_TBA};
+
+#[derive(Serialize)]
+pub struct BrokenCourse {
+    pub id_jadwal: u32,
+    pub deskripsi: String,
+}
+
+/// Course left out of the schedule so higher-priority courses stay clash-free
+#[derive(Serialize)]
+pub struct UnplacedCourse {
+    pub id_jadwal: u32,
+    pub priority: u8,
+    pub deskripsi: String,
+}
+
+#[derive(Serialize)]
+pub struct RepairResult {
+    pub broken: Vec<BrokenCourse>,
+    pub repaired: Vec<OptimizedCourse>,
+    pub unresolved: Vec<u32>,
+    /// Full schedule after repair, without unresolved courses
+    pub schedule: Vec<OptimizedCourse>,
+}
+
+impl SimulationScenario {
+    fn blocks_day(days: &[u32], hari: u32) -> bool {
+        days.is_empty() || days.contains(&hari)
+    }
+
+    /// Whether a placement is unusable under this scenario
+    pub fn blocks(&self, course: &OptimizedCourse) -> bool {
+        match self {
+            SimulationScenario::LecturerAbsence { id_dosen, hari } => {
+                course.id_dosen == *id_dosen && Self::blocks_day(hari, course.hari)
+            }
+            SimulationScenario::RoomOutage { ruangan, hari } => {
+                course.ruangan == *ruangan && Self::blocks_day(hari, course.hari)
+            }
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            SimulationScenario::LecturerAbsence { id_dosen, .. } => {
+                format!("Dosen {} tidak hadir pada jadwal ini.", id_dosen)
+            }
+            SimulationScenario::RoomOutage { ruangan, .. } => {
+                format!("Ruangan {} tidak dapat digunakan pada jadwal ini.", ruangan)
+            }
+        }
+    }
+}
+
+pub struct ScheduleRepairer {
+    pub(super) checker: ScheduleChecker,
+}
+
+impl ScheduleRepairer {
+    pub fn new(checker: ScheduleChecker) -> Self {
+        Self { checker }
+    }
+
+    /// Finds courses broken by the scenario and greedily moves each one to the
+    /// nearest free slot, keeping every untouched course where it is.
+    pub fn simulate(&self, schedule: &[OptimizedCourse], scenario: &SimulationScenario) -> RepairResult {
+        let (broken_courses, placed): (Vec<_>, Vec<_>) = schedule
+            .iter()
+            .cloned()
+            .partition(|course| scenario.blocks(course));
+
+        let broken = broken_courses
+            .iter()
+            .map(|course| BrokenCourse {
+                id_jadwal: course.id_jadwal,
+                deskripsi: scenario.describe(),
+            })
+            .collect();
+
+        self.place_all(broken, broken_courses, placed, |candidate| scenario.blocks(candidate))
+    }
+
+    /// Applies an updated course list to an existing schedule: unchanged courses
+    /// keep their placement, removed ones are dropped and new or edited ones are
+    /// placed into the nearest free slot.
+    pub fn apply_course_changes(&self, schedule: &[OptimizedCourse], courses: &[CourseRequest]) -> RepairResult {
+        let existing: HashMap<u32, &OptimizedCourse> = schedule.iter().map(|c| (c.id_jadwal, c)).collect();
+
+        let mut placed = Vec::with_capacity(courses.len());
+        let mut pending = Vec::new();
+        let mut broken = Vec::new();
+
+        for course in courses {
+            if let Some(locked) = OptimizedCourse::from_lock(course, &self.checker.config) {
+                placed.push(locked);
+                continue;
+            }
+            match existing.get(&course.id_jadwal) {
+                Some(current) if self.same_course(current, course) => placed.push((*current).clone()),
+                current => {
+                    let mut unplaced = OptimizedCourse::from_request(course);
+                    unplaced.id_waktu = self.checker.config.effective_waktu(course);
+                    unplaced.jam_akhir = self.checker.config.duration(course);
+                    if let Some(current) = current {
+                        // Pertahankan posisi lama sebagai titik awal pencarian
+                        unplaced.hari = current.hari;
+                        unplaced.jam_mulai = current.jam_mulai;
+                        unplaced.jam_akhir += current.jam_mulai;
+                        unplaced.ruangan = current.ruangan;
+                    }
+
+                    broken.push(BrokenCourse {
+                        id_jadwal: course.id_jadwal,
+                        deskripsi: if current.is_some() {
+                            "Data mata kuliah berubah.".to_string()
+                        } else {
+                            "Mata kuliah baru.".to_string()
+                        },
+                    });
+                    pending.push(unplaced);
+                }
+            }
+        }
+
+        self.place_all(broken, pending, placed, |_| false)
+    }
+
+    /// Moves each course to the earliest start in its window (on the
+    /// `minutes_per_sks` grid) that adds no clash, booking clash or
+    /// preference violation. Days, rooms and locked courses are left as they are.
+    pub fn compact(&self, schedule: &mut [OptimizedCourse]) {
+        let step = self.checker.config.slots.minutes_per_sks.max(1);
+        let mut order: Vec<usize> = (0..schedule.len()).collect();
+        order.sort_by_key(|&i| (schedule[i].hari, schedule[i].jam_mulai, schedule[i].id_jadwal));
+
+        for i in order {
+            let course = &schedule[i];
+            if course.locked {
+                continue;
+            }
+            let duration = course.jam_akhir.saturating_sub(course.jam_mulai);
+            let (start, _) = self.checker.config.slots.window(course.id_waktu);
+            let was_preferred = self.checker.is_preferred(course.id_dosen, course.hari, course.jam_mulai);
+
+            let earlier = (start..course.jam_mulai).step_by(step as usize).find_map(|jam_mulai| {
+                let candidate = OptimizedCourse {
+                    jam_mulai,
+                    jam_akhir: jam_mulai + duration,
+                    ..course.clone()
+                };
+                let ok = self.checker.booking_clash(&candidate).is_none()
+                    && (!was_preferred || self.checker.is_preferred(candidate.id_dosen, candidate.hari, jam_mulai))
+                    && schedule
+                        .iter()
+                        .enumerate()
+                        .all(|(j, other)| j == i || !self.clashes(&candidate, other));
+                ok.then_some(candidate)
+            });
+
+            if let Some(candidate) = earlier {
+                schedule[i] = candidate;
+            }
+        }
+    }
+
+    /// Local-search fallback for `require_feasible`: the second course of
+    /// every clash (the first when the second is locked) is moved to a free
+    /// slot. Courses that cannot be moved stay where they were and are listed
+    /// as unresolved.
+    pub fn resolve_conflicts(&self, schedule: &mut Vec<OptimizedCourse>) -> RepairResult {
+        let locked: Vec<u32> = schedule.iter().filter(|c| c.locked).map(|c| c.id_jadwal).collect();
+        let mut moving: Vec<u32> = self
+            .checker
+            .detect_conflicts(schedule)
+            .conflicts
+            .iter()
+            .map(|c| if locked.contains(&c.jadwal_b) { c.jadwal_a } else { c.jadwal_b })
+            .chain(schedule.iter().filter(|c| self.checker.booking_clash(c).is_some()).map(|c| c.id_jadwal))
+            .filter(|id| !locked.contains(id))
+            .collect();
+        moving.sort_unstable();
+        moving.dedup();
+
+        let (pending, placed): (Vec<_>, Vec<_>) = schedule.iter().cloned().partition(|c| moving.contains(&c.id_jadwal));
+        let broken = pending
+            .iter()
+            .map(|c| BrokenCourse {
+                id_jadwal: c.id_jadwal,
+                deskripsi: "Masih bentrok setelah optimasi.".to_string(),
+            })
+            .collect();
+
+        let originals = pending.clone();
+        let mut result = self.place_all(broken, pending, placed, |_| false);
+        result
+            .schedule
+            .extend(originals.into_iter().filter(|c| result.unresolved.contains(&c.id_jadwal)));
+        schedule.clone_from(&result.schedule);
+        result
+    }
+
+    /// Repeatedly removes the lower-priority side of a clash between courses
+    /// of different priority. Clashes within one tier, or whose lower side is
+    /// locked, are left for the caller to report.
+    pub fn unplace_low_priority(&self, schedule: &mut Vec<OptimizedCourse>) -> Vec<UnplacedCourse> {
+        let mut unplaced = Vec::new();
+        if self.checker.priorities.is_empty() {
+            return unplaced;
+        }
+        let locked: Vec<u32> = schedule.iter().filter(|c| c.locked).map(|c| c.id_jadwal).collect();
+
+        loop {
+            let victim = self
+                .checker
+                .detect_conflicts(schedule)
+                .conflicts
+                .iter()
+                .filter_map(|c| {
+                    let (pa, pb) = (self.checker.priority(c.jadwal_a), self.checker.priority(c.jadwal_b));
+                    match pa.cmp(&pb) {
+                        std::cmp::Ordering::Less => Some((pa, c.jadwal_a, c.jadwal_b)),
+                        std::cmp::Ordering::Greater => Some((pb, c.jadwal_b, c.jadwal_a)),
+                        std::cmp::Ordering::Equal => None,
+                    }
+                })
+                .filter(|(_, victim, _)| !locked.contains(victim))
+                .min();
+
+            let Some((priority, id_jadwal, other)) = victim else {
+                return unplaced;
+            };
+            schedule.retain(|c| c.id_jadwal != id_jadwal);
+            unplaced.push(UnplacedCourse {
+                id_jadwal,
+                priority,
+                deskripsi: format!(
+                    "Jadwal {} (prioritas {}) tidak ditempatkan karena bentrok dengan jadwal {} yang lebih prioritas.",
+                    id_jadwal, priority, other
+                ),
+            });
+        }
+    }
+
+    fn same_course(&self, current: &OptimizedCourse, course: &CourseRequest) -> bool {
+        let id_waktu = self.checker.config.effective_waktu(course);
+        let duration = current.jam_akhir.saturating_sub(current.jam_mulai);
+        (current.id_matkul, current.id_dosen, current.id_kelas, current.id_waktu, current.semester, current.sks, current.prodi)
+            == (course.id_matkul, course.id_dosen, course.id_kelas, id_waktu, course.semester, course.sks, course.prodi)
+            && duration == self.checker.config.duration(course)
+    }
+
+    fn place_all(
+        &self,
+        broken: Vec<BrokenCourse>,
+        mut pending: Vec<OptimizedCourse>,
+        mut placed: Vec<OptimizedCourse>,
+        blocked: impl Fn(&OptimizedCourse) -> bool,
+    ) -> RepairResult {
+        let mut rooms: Vec<u32> = placed.iter().chain(&pending).map(|c| c.ruangan).collect();
+        rooms.sort_unstable();
+        rooms.dedup();
+
+        // Mata kuliah dengan SKS terbesar paling sulit dipindah, jadi didahulukan
+        pending.sort_by_key(|c| Reverse(c.sks));
+
+        let mut repaired = Vec::new();
+        let mut unresolved = Vec::new();
+
+        for course in pending {
+            match self.find_slot(&course, &placed, &rooms, &blocked) {
+                Some(moved) => {
+                    placed.push(moved.clone());
+                    repaired.push(moved);
+                }
+                None => unresolved.push(course.id_jadwal),
+            }
+        }
+
+        RepairResult {
+            broken,
+            repaired,
+            unresolved,
+            schedule: placed,
+        }
+    }
+
+    fn find_slot(
+        &self,
+        course: &OptimizedCourse,
+        placed: &[OptimizedCourse],
+        rooms: &[u32],
+        blocked: &impl Fn(&OptimizedCourse) -> bool,
+    ) -> Option<OptimizedCourse> {
+        let duration = course.jam_akhir.saturating_sub(course.jam_mulai);
+        let (start, end) = self.checker.config.slots.window(course.id_waktu);
+        let step = self.checker.config.slots.minutes_per_sks.max(1);
+
+        let mut candidates = Vec::new();
+        for hari in 1..=5 {
+            let mut jam_mulai = start;
+            while jam_mulai + duration <= end {
+                for &ruangan in rooms {
+                    candidates.push(OptimizedCourse {
+                        hari,
+                        jam_mulai,
+                        jam_akhir: jam_mulai + duration,
+                        ruangan,
+                        ..course.clone()
+                    });
+                }
+                jam_mulai += step;
+            }
+        }
+
+        // Perubahan sekecil mungkin: hari sama dulu, lalu jam terdekat, lalu ruangan sama
+        candidates.sort_by_key(|c| {
+            (
+                c.hari != course.hari,
+                c.jam_mulai.abs_diff(course.jam_mulai),
+                c.ruangan != course.ruangan,
+            )
+        });
+
+        candidates.into_iter().find(|candidate| {
+            !blocked(candidate)
+                && self.checker.booking_clash(candidate).is_none()
+                && self.checker.is_preferred(candidate.id_dosen, candidate.hari, candidate.jam_mulai)
+                && placed.iter().all(|other| !self.clashes(candidate, other))
+        })
+    }
+
+    pub(super) fn clashes(&self, a: &OptimizedCourse, b: &OptimizedCourse) -> bool {
+        if a.hari != b.hari
+            || !ScheduleChecker::is_within_break(a, b, self.checker.config.slots.min_break)
+            || self.checker.same_session(a.id_jadwal, b.id_jadwal)
+        {
+            return false;
+        }
+
+        // Jeda minimum hanya berlaku untuk mahasiswa yang sama
+        let same_kelas = (a.prodi, a.semester, a.id_kelas) == (b.prodi, b.semester, b.id_kelas);
+        let shared_students = self.checker.shared_student_group(a.id_jadwal, b.id_jadwal).is_some();
+        if same_kelas || shared_students {
+            return true;
+        }
+
+        let same_dosen = a.id_dosen == b.id_dosen && a.id_dosen != DOSEN_TBA;
+        // Ruangan 0 berarti belum dialokasikan
+        let same_ruangan = a.ruangan != 0 && a.ruangan == b.ruangan;
+
+        ScheduleChecker::is_overlap(a, b) && (same_dosen || same_ruangan)
+    }
+}
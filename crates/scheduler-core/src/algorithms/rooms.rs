@@ -0,0 +1,281 @@
+use std::collections::{HashMap, HashSet};
+
+use super::models::{CourseRequest, CourseType, ExternalBooking, OptimizedCourse, RoomRequest, RoomType, DOSEN_TBA};
+
+/// Most unnamed rooms the deprecated `sum_ruangan` may ask for; larger values
+/// are cut here and rejected by the server
+pub const MAX_SUM_RUANGAN: u32 = 1000;
+
+/// Rooms the allocator may use, with their type and seats, plus what each
+/// course needs from a room. Rooms without a type or capacity suit every
+/// course, and so does any room for courses without a known size.
+#[derive(Debug, Clone, Default)]
+pub struct RoomCatalog {
+    // id ruangan, urut sesuai permintaan
+    ids: Vec<u32>,
+    // ruangan -> kapasitas
+    seats: HashMap<u32, u32>,
+    // ruangan -> jenis
+    kinds: HashMap<u32, RoomType>,
+    // id_jadwal -> jumlah mahasiswa
+    students: HashMap<u32, u32>,
+    // id_jadwal praktikum; hanya boleh di laboratorium
+    praktikum: HashSet<u32>,
+    // id_jadwal -> ruangan yang dikunci pemanggil
+    pinned: HashMap<u32, u32>,
+}
+
+impl RoomCatalog {
+    /// `rooms` when given, otherwise the unnamed rooms 1..=`sum_ruangan`
+    /// (at most `MAX_SUM_RUANGAN`)
+    pub fn new(rooms: &[RoomRequest], sum_ruangan: Option<u32>, courses: &[CourseRequest]) -> Self {
+        let ids = match rooms {
+            [] => (1..=sum_ruangan.unwrap_or(0).min(MAX_SUM_RUANGAN)).collect(),
+            rooms => rooms.iter().map(|r| r.ruangan).collect(),
+        };
+        RoomCatalog {
+            ids,
+            seats: rooms.iter().filter_map(|r| Some((r.ruangan, r.kapasitas?))).collect(),
+            kinds: rooms.iter().filter_map(|r| Some((r.ruangan, r.jenis?))).collect(),
+            students: courses
+                .iter()
+                .filter(|c| c.jumlah_mahasiswa > 0)
+                .map(|c| (c.id_jadwal, c.jumlah_mahasiswa))
+                .collect(),
+            praktikum: courses
+                .iter()
+                .filter(|c| c.jenis == CourseType::Praktikum)
+                .map(|c| c.id_jadwal)
+                .collect(),
+            pinned: courses
+                .iter()
+                .filter_map(|c| Some((c.id_jadwal, c.locked?.ruangan?)))
+                .collect(),
+        }
+    }
+
+    pub fn ids(&self) -> &[u32] {
+        &self.ids
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn contains(&self, ruangan: u32) -> bool {
+        self.ids.contains(&ruangan)
+    }
+
+    /// (jumlah mahasiswa, kapasitas) when the course doesn't fit the room
+    pub fn shortfall(&self, id_jadwal: u32, ruangan: u32) -> Option<(u32, u32)> {
+        let students = *self.students.get(&id_jadwal)?;
+        let seats = *self.seats.get(&ruangan)?;
+        (students > seats).then_some((students, seats))
+    }
+
+    pub fn fits(&self, id_jadwal: u32, ruangan: u32) -> bool {
+        self.shortfall(id_jadwal, ruangan).is_none()
+    }
+
+    /// Praktikum needs a lab; teori may use any room
+    pub fn kind_allowed(&self, id_jadwal: u32, ruangan: u32) -> bool {
+        !self.needs_lab(id_jadwal) || self.kinds.get(&ruangan).is_none_or(|&k| k == RoomType::Lab)
+    }
+
+    fn needs_lab(&self, id_jadwal: u32) -> bool {
+        self.praktikum.contains(&id_jadwal)
+    }
+
+    /// Right type and big enough
+    pub fn suits(&self, id_jadwal: u32, ruangan: u32) -> bool {
+        self.kind_allowed(id_jadwal, ruangan) && self.fits(id_jadwal, ruangan)
+    }
+
+    fn seats(&self, ruangan: u32) -> u32 {
+        self.seats.get(&ruangan).copied().unwrap_or(u32::MAX)
+    }
+
+    fn is_lab(&self, ruangan: u32) -> bool {
+        self.kinds.get(&ruangan) == Some(&RoomType::Lab)
+    }
+}
+
+/// Late-binding room assignment run after PSO has fixed days and times.
+///
+/// Per day, courses are taken in start-time order and given any room that is
+/// free for the whole interval (interval partitioning, which never needs more
+/// rooms than the peak overlap). Among free rooms the one the kelas used last
+/// is preferred so a cohort keeps its room (and before that the room of the
+/// lecturer's previous section of the same matkul that day, so bundled
+/// sections stay put), then the smallest suitable one
+/// (teori rooms before labs for teori courses); if none is big enough the
+/// largest free room of the right type is used. Courses with a locked room
+/// keep it and nobody else is put there at the same time. Returns the
+/// id_jadwal of courses that could not get a room; those keep `ruangan = 0`.
+pub fn assign_rooms(schedule: &mut [OptimizedCourse], rooms: &RoomCatalog, bookings: &[ExternalBooking]) -> Vec<u32> {
+    let mut order: Vec<usize> = (0..schedule.len()).collect();
+    order.sort_by_key(|&i| (schedule[i].hari, schedule[i].jam_mulai, schedule[i].jam_akhir, schedule[i].id_jadwal));
+
+    let mut busy_until: HashMap<(u32, u32), u32> = HashMap::new(); // (hari, ruangan) -> jam selesai
+    let mut kelas_room: HashMap<(u32, u32, u32), u32> = HashMap::new();
+    // (hari, dosen, matkul) -> ruangan seksi sebelumnya, agar seksi berurutan tetap di satu ruangan
+    let mut bundle_room: HashMap<(u32, u32, u32), u32> = HashMap::new();
+    let mut unassigned = Vec::new();
+    let pinned: Vec<&OptimizedCourse> = schedule.iter().filter(|c| rooms.pinned.contains_key(&c.id_jadwal)).collect();
+    // (hari, ruangan, jam mulai, jam selesai) yang sudah dikunci
+    let reserved: Vec<(u32, u32, u32, u32)> = pinned
+        .iter()
+        .map(|c| (c.hari, rooms.pinned[&c.id_jadwal], c.jam_mulai, c.jam_akhir))
+        .collect();
+
+    for i in order {
+        let course = &schedule[i];
+        let kelas = (course.prodi, course.semester, course.id_kelas);
+        let id_jadwal = course.id_jadwal;
+        if let Some(&ruangan) = rooms.pinned.get(&id_jadwal) {
+            kelas_room.insert(kelas, ruangan);
+            schedule[i].ruangan = ruangan;
+            continue;
+        }
+
+        let is_free = |ruangan: u32| {
+            busy_until.get(&(course.hari, ruangan)).is_none_or(|&end| end <= course.jam_mulai)
+                && !bookings.iter().any(|b| {
+                    b.ruangan == ruangan
+                        && b.hari == course.hari
+                        && course.jam_mulai < b.jam_akhir
+                        && b.jam_mulai < course.jam_akhir
+                })
+                && !reserved.iter().any(|&(hari, r, mulai, akhir)| {
+                    r == ruangan && hari == course.hari && course.jam_mulai < akhir && mulai < course.jam_akhir
+                })
+        };
+        let free = || rooms.ids().iter().copied().filter(|&r| is_free(r));
+
+        let bundle = (course.hari, course.id_dosen, course.id_matkul);
+        let preferred = [bundle_room.get(&bundle).filter(|_| course.id_dosen != DOSEN_TBA), kelas_room.get(&kelas)];
+        let room = preferred
+            .into_iter()
+            .flatten()
+            .copied()
+            .find(|&r| is_free(r) && rooms.suits(id_jadwal, r))
+            .or_else(|| {
+                free()
+                    .filter(|&r| rooms.suits(id_jadwal, r))
+                    .min_by_key(|&r| (rooms.is_lab(r) != rooms.needs_lab(id_jadwal), rooms.seats(r)))
+            })
+            .or_else(|| {
+                free()
+                    .filter(|&r| rooms.kind_allowed(id_jadwal, r))
+                    .min_by_key(|&r| std::cmp::Reverse(rooms.seats(r)))
+            });
+
+        match room {
+            Some(ruangan) => {
+                busy_until.insert((course.hari, ruangan), course.jam_akhir);
+                kelas_room.insert(kelas, ruangan);
+                bundle_room.insert(bundle, ruangan);
+                schedule[i].ruangan = ruangan;
+            }
+            None => {
+                unassigned.push(course.id_jadwal);
+                schedule[i].ruangan = 0;
+            }
+        }
+    }
+
+    unassigned
+}
+
+/// Relabels rooms (a permutation of the room ids, so no clash is created
+/// between courses) to agree as much as possible with `previous`. A course
+/// votes for the room it had before, or else the room its kelas used most.
+/// Labels that would hit an external booking, move a locked room or leave a
+/// class in a room of the wrong type or too small for it are never used; if no clean permutation is found the schedule
+/// is left as is. Returns how many courses end up in the same room as before.
+pub fn stabilize_rooms(
+    schedule: &mut [OptimizedCourse],
+    previous: &[OptimizedCourse],
+    rooms: &RoomCatalog,
+    bookings: &[ExternalBooking],
+) -> usize {
+    let by_jadwal: HashMap<u32, u32> = previous
+        .iter()
+        .filter(|c| c.ruangan != 0)
+        .map(|c| (c.id_jadwal, c.ruangan))
+        .collect();
+    let mut kelas_votes: HashMap<(u32, u32, u32), HashMap<u32, usize>> = HashMap::new();
+    for c in previous.iter().filter(|c| c.ruangan != 0) {
+        *kelas_votes.entry((c.prodi, c.semester, c.id_kelas)).or_default().entry(c.ruangan).or_default() += 1;
+    }
+
+    let wanted_room = |c: &OptimizedCourse| {
+        by_jadwal.get(&c.id_jadwal).copied().or_else(|| {
+            kelas_votes
+                .get(&(c.prodi, c.semester, c.id_kelas))
+                .and_then(|votes| votes.iter().max_by_key(|(room, n)| (**n, std::cmp::Reverse(**room))))
+                .map(|(room, _)| *room)
+        })
+    };
+
+    // (ruangan baru, label lama) -> jumlah suara
+    let mut agreement: HashMap<(u32, u32), usize> = HashMap::new();
+    for c in schedule.iter().filter(|c| c.ruangan != 0) {
+        if let Some(old) = wanted_room(c).filter(|&r| rooms.contains(r)) {
+            *agreement.entry((c.ruangan, old)).or_default() += 1;
+        }
+    }
+
+    let label_ok = |from: u32, to: u32| {
+        schedule.iter().filter(|c| c.ruangan == from).all(|c| {
+            !bookings.iter().any(|b| {
+                b.ruangan == to && b.hari == c.hari && c.jam_mulai < b.jam_akhir && b.jam_mulai < c.jam_akhir
+            }) && (rooms.suits(c.id_jadwal, to) || !rooms.suits(c.id_jadwal, from))
+                && rooms.pinned.get(&c.id_jadwal).is_none_or(|&r| r == to)
+        })
+    };
+
+    let mut pairs: Vec<((u32, u32), usize)> = agreement.into_iter().collect();
+    pairs.sort_by_key(|&((from, to), n)| (std::cmp::Reverse(n), from, to));
+
+    let mut mapping: HashMap<u32, u32> = HashMap::new();
+    let mut used: HashSet<u32> = HashSet::new();
+    for ((from, to), _) in pairs {
+        if !mapping.contains_key(&from) && !used.contains(&to) && label_ok(from, to) {
+            mapping.insert(from, to);
+            used.insert(to);
+        }
+    }
+
+    // Ruangan sisa: pertahankan label sendiri bila bisa, selain itu label bebas pertama
+    for &from in rooms.ids() {
+        if mapping.contains_key(&from) {
+            continue;
+        }
+        let to = std::iter::once(from)
+            .chain(rooms.ids().iter().copied())
+            .find(|&to| !used.contains(&to) && label_ok(from, to));
+        match to {
+            Some(to) => {
+                mapping.insert(from, to);
+                used.insert(to);
+            }
+            None => return count_kept(schedule, &wanted_room),
+        }
+    }
+
+    for course in schedule.iter_mut().filter(|c| c.ruangan != 0) {
+        if let Some(&to) = mapping.get(&course.ruangan) {
+            course.ruangan = to;
+        }
+    }
+
+    count_kept(schedule, &wanted_room)
+}
+
+fn count_kept(schedule: &[OptimizedCourse], wanted_room: &impl Fn(&OptimizedCourse) -> Option<u32>) -> usize {
+    schedule
+        .iter()
+        .filter(|c| c.ruangan != 0 && wanted_room(c) == Some(c.ruangan))
+        .count()
+}
@@ -0,0 +1,325 @@
+use std::collections::{BTreeSet, HashMap};
+
+use serde::Serialize;
+
+use super::models::{OptimizedCourse, ScheduleChecker, ScheduleMode, DOSEN_TBA};
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum SoftKind {
+    LecturerDays,
+    SplitDay,
+    LateFinish,
+    LecturerLoad,
+    SectionBundle,
+}
+
+/// Soft objective that was not met; never makes a schedule infeasible
+#[derive(Serialize, Clone)]
+pub struct SoftMessage {
+    pub kind: SoftKind,
+    pub id_dosen: u32,
+    // Prodi kelas yang terdampak; untuk objektif per dosen, semua prodi tempat ia mengajar
+    pub prodi: Vec<u32>,
+    pub penalty: u32,
+    pub deskripsi: String,
+}
+
+#[derive(Serialize)]
+pub struct SoftResult {
+    pub penalty: u32,
+    pub violations: Vec<SoftMessage>,
+}
+
+/// One kelas's teaching day, for the late-finish objective
+struct KelasDay {
+    /// Earliest window start among its sessions
+    start: u32,
+    /// Minutes actually taught
+    busy: u32,
+    end: u32,
+    /// Lecturer of the session ending last
+    id_dosen: u32,
+}
+
+/// A lecturer's week, for the campus-days objective
+#[derive(Default)]
+struct DosenWeek {
+    days: BTreeSet<u32>,
+    minutes: u32,
+    /// Longest daily window among its sessions
+    capacity: u32,
+    prodi: BTreeSet<u32>,
+}
+
+/// SKS a lecturer teaches over one of their limits
+struct LoadExcess {
+    id_dosen: u32,
+    /// None for the weekly limit
+    hari: Option<u32>,
+    sks: u32,
+    max: u32,
+    prodi: BTreeSet<u32>,
+}
+
+impl ScheduleChecker {
+    /// Soft objectives whose weight is zero are skipped entirely
+    pub fn check_soft_constraints(&self, schedule: &[OptimizedCourse]) -> SoftResult {
+        let mut result = SoftResult { penalty: 0, violations: Vec::new() };
+
+        // Hari kampus dosen hanya relevan untuk jadwal mingguan
+        if self.config.weights.lecturer_days > 0 && self.config.mode == ScheduleMode::Weekly {
+            self.check_lecturer_days(schedule, &mut result);
+        }
+
+        if self.config.weights.split_day > 0 && self.config.mode == ScheduleMode::Weekly {
+            self.check_split_days(schedule, &mut result);
+        }
+
+        if self.config.weights.late_finish > 0 && self.config.mode == ScheduleMode::Weekly {
+            self.check_late_finish(schedule, &mut result);
+        }
+
+        if self.config.weights.lecturer_load > 0 && self.config.mode == ScheduleMode::Weekly {
+            self.check_lecturer_load(schedule, &mut result);
+        }
+
+        if self.config.weights.section_bundle > 0 && self.config.mode == ScheduleMode::Weekly {
+            self.check_section_bundles(schedule, &mut result);
+        }
+
+        result
+    }
+
+    /// Penalizes, per kelas and day, the minutes by which the last session
+    /// ends after the earliest possible finish (the day's sessions packed
+    /// back to back from the window start), weighted per hour
+    fn check_late_finish(&self, schedule: &[OptimizedCourse], result: &mut SoftResult) {
+        let mut days: HashMap<(u32, u32, u32, u32), KelasDay> = HashMap::new(); // (prodi, semester, kelas, hari)
+        for course in schedule {
+            let start = self.config.slots.window(course.id_waktu).0;
+            let day = days
+                .entry((course.prodi, course.semester, course.id_kelas, course.hari))
+                .or_insert(KelasDay { start, busy: 0, end: 0, id_dosen: course.id_dosen });
+            day.start = day.start.min(start);
+            day.busy += course.jam_akhir.saturating_sub(course.jam_mulai);
+            if course.jam_akhir > day.end {
+                day.end = course.jam_akhir;
+                day.id_dosen = course.id_dosen;
+            }
+        }
+
+        let mut late: Vec<_> = days
+            .into_iter()
+            .map(|(key, day)| (key, day.end.saturating_sub(day.start + day.busy), day.id_dosen))
+            .filter(|(_, minutes, _)| *minutes > 0)
+            .collect();
+        late.sort_unstable();
+
+        for ((prodi, _, id_kelas, hari), minutes, id_dosen) in late {
+            let penalty = minutes * self.config.weights.late_finish / 60;
+            result.penalty += penalty;
+            result.violations.push(SoftMessage {
+                kind: SoftKind::LateFinish,
+                id_dosen,
+                prodi: vec![prodi],
+                penalty,
+                deskripsi: format!(
+                    "Kelas {} selesai {} menit lebih lambat dari yang mungkin pada hari ke-{}.",
+                    id_kelas, minutes, hari
+                ),
+            });
+        }
+    }
+
+    /// Penalizes the SKS a lecturer teaches beyond their daily and weekly
+    /// limits; the weekly excess comes from the course assignment itself, so
+    /// it only shows up as a fixed cost and in the report
+    fn check_lecturer_load(&self, schedule: &[OptimizedCourse], result: &mut SoftResult) {
+        let mut daily: HashMap<(u32, u32), (u32, BTreeSet<u32>)> = HashMap::new(); // (dosen, hari) -> (sks, prodi)
+        for course in schedule {
+            let Some(pref) = self.time_preferences.get(&course.id_dosen) else {
+                continue;
+            };
+            if pref.max_sks_per_hari.is_none() && pref.max_sks_per_minggu.is_none() {
+                continue;
+            }
+            let day = daily.entry((course.id_dosen, course.hari)).or_default();
+            day.0 += course.sks;
+            day.1.insert(course.prodi);
+        }
+
+        let mut weekly: HashMap<u32, (u32, BTreeSet<u32>)> = HashMap::new();
+        let mut over = Vec::new();
+        for ((id_dosen, hari), (sks, prodi)) in daily {
+            let week = weekly.entry(id_dosen).or_default();
+            week.0 += sks;
+            week.1.extend(prodi.iter().copied());
+            if let Some(max) = self.time_preferences[&id_dosen].max_sks_per_hari.filter(|&max| sks > max) {
+                over.push(LoadExcess { id_dosen, hari: Some(hari), sks, max, prodi });
+            }
+        }
+        for (id_dosen, (sks, prodi)) in weekly {
+            if let Some(max) = self.time_preferences[&id_dosen].max_sks_per_minggu.filter(|&max| sks > max) {
+                over.push(LoadExcess { id_dosen, hari: None, sks, max, prodi });
+            }
+        }
+        over.sort_unstable_by_key(|e| (e.id_dosen, e.hari.is_none(), e.hari));
+
+        for LoadExcess { id_dosen, hari, sks, max, prodi } in over {
+            let penalty = (sks - max) * self.config.weights.lecturer_load;
+            result.penalty += penalty;
+            let deskripsi = match hari {
+                Some(hari) => format!(
+                    "Dosen {} mengajar {} SKS pada hari ke-{}, melebihi batas {} SKS per hari.",
+                    id_dosen, sks, hari, max
+                ),
+                None => format!(
+                    "Dosen {} mengajar {} SKS seminggu, melebihi batas {} SKS per minggu.",
+                    id_dosen, sks, max
+                ),
+            };
+            result.violations.push(SoftMessage {
+                kind: SoftKind::LecturerLoad,
+                id_dosen,
+                prodi: prodi.into_iter().collect(),
+                penalty,
+                deskripsi,
+            });
+        }
+    }
+
+    /// Penalizes every break in a lecturer's sections of the same matkul:
+    /// ideally they run back to back on one day in one room, as far as the
+    /// longest daily window allows. Two sections count as back to back when
+    /// at most `min_break` apart and not in different assigned rooms.
+    fn check_section_bundles(&self, schedule: &[OptimizedCourse], result: &mut SoftResult) {
+        let mut bundles: HashMap<(u32, u32), Vec<&OptimizedCourse>> = HashMap::new(); // (dosen, matkul)
+        for course in schedule.iter().filter(|c| c.id_dosen != DOSEN_TBA) {
+            bundles.entry((course.id_dosen, course.id_matkul)).or_default().push(course);
+        }
+
+        let min_break = self.config.slots.min_break;
+        let mut split: Vec<_> = bundles
+            .into_iter()
+            .filter(|(_, sections)| sections.len() > 1)
+            .filter_map(|(key, mut sections)| {
+                sections.sort_unstable_by_key(|c| (c.hari, c.jam_mulai));
+                let blocks = 1 + sections
+                    .windows(2)
+                    .filter(|w| {
+                        let (a, b) = (w[0], w[1]);
+                        let same_room = a.ruangan == 0 || b.ruangan == 0 || a.ruangan == b.ruangan;
+                        a.hari != b.hari || b.jam_mulai > a.jam_akhir + min_break || !same_room
+                    })
+                    .count() as u32;
+
+                let capacity = sections
+                    .iter()
+                    .map(|c| {
+                        let (start, end) = self.config.slots.window(c.id_waktu);
+                        end.saturating_sub(start)
+                    })
+                    .max()
+                    .unwrap_or(0);
+                let minutes: u32 = sections.iter().map(|c| c.jam_akhir.saturating_sub(c.jam_mulai)).sum::<u32>()
+                    + min_break * (sections.len() as u32 - 1);
+                let min_blocks = minutes.div_ceil(capacity.max(1)).max(1);
+
+                let extra = blocks.saturating_sub(min_blocks);
+                let prodi: BTreeSet<u32> = sections.iter().map(|c| c.prodi).collect();
+                (extra > 0).then_some((key, sections.len(), blocks, min_blocks, extra, prodi))
+            })
+            .collect();
+        split.sort_unstable_by_key(|(key, ..)| *key);
+
+        for ((id_dosen, id_matkul), sections, blocks, min_blocks, extra, prodi) in split {
+            let penalty = extra * self.config.weights.section_bundle;
+            result.penalty += penalty;
+            result.violations.push(SoftMessage {
+                kind: SoftKind::SectionBundle,
+                id_dosen,
+                prodi: prodi.into_iter().collect(),
+                penalty,
+                deskripsi: format!(
+                    "Dosen {} mengajar {} seksi matkul {} dalam {} blok terpisah, bisa {} blok.",
+                    id_dosen, sections, id_matkul, blocks, min_blocks
+                ),
+            });
+        }
+    }
+
+    /// Penalizes each day a lecturer who asked for `avoid_split_day` has to
+    /// teach in both the pagi and malam windows (two commutes)
+    fn check_split_days(&self, schedule: &[OptimizedCourse], result: &mut SoftResult) {
+        let mut windows: HashMap<(u32, u32), (bool, bool, BTreeSet<u32>)> = HashMap::new(); // (dosen, hari) -> (pagi, malam, prodi)
+        for course in schedule {
+            if !self.time_preferences.get(&course.id_dosen).is_some_and(|p| p.avoid_split_day) {
+                continue;
+            }
+            let entry = windows.entry((course.id_dosen, course.hari)).or_default();
+            if self.config.slots.is_pagi(course.jam_mulai) {
+                entry.0 = true;
+            } else {
+                entry.1 = true;
+            }
+            entry.2.insert(course.prodi);
+        }
+
+        let mut split: Vec<((u32, u32), BTreeSet<u32>)> = windows
+            .into_iter()
+            .filter(|(_, (pagi, malam, _))| *pagi && *malam)
+            .map(|(key, (_, _, prodi))| (key, prodi))
+            .collect();
+        split.sort_unstable();
+
+        for ((id_dosen, hari), prodi) in split {
+            result.penalty += self.config.weights.split_day;
+            result.violations.push(SoftMessage {
+                kind: SoftKind::SplitDay,
+                id_dosen,
+                prodi: prodi.into_iter().collect(),
+                penalty: self.config.weights.split_day,
+                deskripsi: format!("Dosen {} mengajar pagi dan malam pada hari ke-{}.", id_dosen, hari),
+            });
+        }
+    }
+
+    /// Penalizes every campus day a lecturer needs beyond the fewest days their
+    /// teaching load could fit in. Availability itself stays with the
+    /// preference check, so clustering never overrides it.
+    fn check_lecturer_days(&self, schedule: &[OptimizedCourse], result: &mut SoftResult) {
+        let mut by_dosen: HashMap<u32, DosenWeek> = HashMap::new();
+        // Dosen TBA bukan satu orang; hari kampusnya tidak bermakna
+        for course in schedule.iter().filter(|c| c.id_dosen != DOSEN_TBA) {
+            let (start, end) = self.config.slots.window(course.id_waktu);
+            let week = by_dosen.entry(course.id_dosen).or_default();
+            week.days.insert(course.hari);
+            week.minutes += course.jam_akhir.saturating_sub(course.jam_mulai);
+            week.capacity = week.capacity.max(end.saturating_sub(start));
+            week.prodi.insert(course.prodi);
+        }
+
+        for (id_dosen, DosenWeek { days, minutes, capacity, prodi }) in by_dosen {
+            let min_days = minutes.div_ceil(capacity.max(1)).max(1);
+            let extra = (days.len() as u32).saturating_sub(min_days);
+            if extra == 0 {
+                continue;
+            }
+
+            result.penalty += extra * self.config.weights.lecturer_days;
+            result.violations.push(SoftMessage {
+                kind: SoftKind::LecturerDays,
+                id_dosen,
+                prodi: prodi.into_iter().collect(),
+                penalty: extra * self.config.weights.lecturer_days,
+                deskripsi: format!(
+                    "Dosen {} mengajar dalam {} hari, bisa dipadatkan menjadi {} hari.",
+                    id_dosen,
+                    days.len(),
+                    min_days
+                ),
+            });
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::models::{OptimizedCourse, ScheduleChecker};
+
+/// (prodi, semester, id_kelas, hari)
+type KelasDay = (u32, u32, u32, u32);
+
+/// Readable quality figures of a schedule, sent as `stats` events
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ScheduleStats {
+    /// Most sessions running at the same moment, i.e. rooms the schedule needs
+    pub rooms_needed: usize,
+    /// Idle minutes between consecutive sessions of a kelas on the same day,
+    /// summed over the week and averaged over kelas
+    pub avg_gap_minutes_per_kelas: f32,
+    /// Longest single idle stretch between two sessions of a kelas
+    #[serde(default)]
+    pub max_gap_minutes: u32,
+    /// Share of sessions whose lecturer prefers the slot, 0–100
+    pub preferences_satisfied_pct: f32,
+}
+
+impl ScheduleChecker {
+    pub fn schedule_stats(&self, schedule: &[OptimizedCourse]) -> ScheduleStats {
+        // Sesi lintas kelas hanya memakai satu ruangan
+        let sessions: Vec<&OptimizedCourse> =
+            schedule.iter().filter(|c| !self.cross_listed.contains_key(&c.id_jadwal)).collect();
+
+        let mut edges: Vec<(u32, u32, i32)> = sessions
+            .iter()
+            .flat_map(|c| [(c.hari, c.jam_akhir, -1), (c.hari, c.jam_mulai, 1)])
+            .collect();
+        // Selesai diproses sebelum mulai pada menit yang sama
+        edges.sort_unstable();
+        let mut running = 0;
+        let mut rooms_needed = 0;
+        for (_, _, delta) in edges {
+            running += delta;
+            rooms_needed = rooms_needed.max(running);
+        }
+
+        let mut by_kelas_day: HashMap<KelasDay, Vec<(u32, u32)>> = HashMap::new();
+        for c in schedule {
+            by_kelas_day.entry((c.prodi, c.semester, c.id_kelas, c.hari)).or_default().push((c.jam_mulai, c.jam_akhir));
+        }
+        let mut gaps: HashMap<(u32, u32, u32), u32> = HashMap::new();
+        let mut max_gap_minutes = 0;
+        for ((prodi, semester, kelas, _), mut times) in by_kelas_day {
+            times.sort_unstable();
+            let total = gaps.entry((prodi, semester, kelas)).or_default();
+            for w in times.windows(2) {
+                let gap = w[1].0.saturating_sub(w[0].1);
+                *total += gap;
+                max_gap_minutes = max_gap_minutes.max(gap);
+            }
+        }
+        let avg_gap_minutes_per_kelas = if gaps.is_empty() {
+            0.0
+        } else {
+            gaps.values().sum::<u32>() as f32 / gaps.len() as f32
+        };
+
+        let satisfied = schedule.iter().filter(|c| self.is_preferred(c.id_dosen, c.hari, c.jam_mulai)).count();
+        let preferences_satisfied_pct = if schedule.is_empty() {
+            100.0
+        } else {
+            satisfied as f32 * 100.0 / schedule.len() as f32
+        };
+
+        ScheduleStats {
+            rooms_needed: rooms_needed.max(0) as usize,
+            avg_gap_minutes_per_kelas,
+            max_gap_minutes,
+            preferences_satisfied_pct,
+        }
+    }
+}
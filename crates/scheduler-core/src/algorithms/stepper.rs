@@ -0,0 +1,105 @@
+use serde::Serialize;
+
+use super::{
+    float::{Float, Precision},
+    models::{TerminationReason, PSO},
+};
+
+/// What one `Optimizer::step` did
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct StepReport {
+    /// Iterations completed so far
+    pub iteration: usize,
+    pub best_fitness: f32,
+    pub improved: bool,
+    /// Set once the run has ended; later steps change nothing
+    pub finished: Option<TerminationReason>,
+}
+
+/// Optimizer driven one iteration at a time, for embedders (CLI, WASM,
+/// tests) that run their own loop. The server uses the async variant,
+/// `PSO::run`, which wraps the same iteration with cancellation, live
+/// parameters, progress events and throttling.
+pub trait Optimizer {
+    fn step(&mut self) -> StepReport;
+
+    /// Best position so far (as f32) and its fitness
+    fn best(&self) -> (Vec<f32>, f32);
+
+    /// Steps until the run ends
+    fn run_to_end(&mut self) -> StepReport {
+        loop {
+            let report = self.step();
+            if report.finished.is_some() {
+                return report;
+            }
+        }
+    }
+}
+
+/// Synchronous PSO run; needs no tokio runtime, channels or cancellation
+/// token, and is all that is built without the `runtime` feature. Stops at
+/// `max_iterations` or an early-stopping criterion, then applies
+/// `refinement`; `time_limit_secs` and `require_feasible` extensions are left
+/// to the caller.
+pub struct PsoStepper<F: Float = f32> {
+    pso: PSO<F>,
+    iteration: usize,
+    finished: Option<TerminationReason>,
+}
+
+impl<F: Float> PsoStepper<F> {
+    pub fn new(mut pso: PSO<F>) -> Self {
+        let finished = pso.prepare().then_some(TerminationReason::Constructed);
+        PsoStepper { pso, iteration: 0, finished }
+    }
+}
+
+impl<F: Float> Optimizer for PsoStepper<F> {
+    fn step(&mut self) -> StepReport {
+        let improvements = self.pso.improvements.len();
+        let was_running = self.finished.is_none();
+
+        if self.finished.is_none() && self.iteration < self.pso.parameters.max_iterations {
+            self.finished = self.pso.iterate(self.iteration);
+            self.iteration += 1;
+        }
+        if self.finished.is_none() && self.iteration >= self.pso.parameters.max_iterations {
+            self.finished = Some(TerminationReason::MaxIterations);
+        }
+        if let Some(reason) = self.finished {
+            // Sama seperti PSO::run: refinement sekali setelah swarm berhenti
+            if let Some(refinement) = self.pso.parameters.refinement.clone().filter(|_| was_running) {
+                if self.pso.in_pool(|pso| pso.refine(&refinement)) {
+                    let fitness = self.pso.reported_fitness();
+                    self.pso.improvements.push((self.iteration, fitness));
+                }
+            }
+            self.pso.termination = reason;
+        }
+
+        StepReport {
+            iteration: self.iteration,
+            best_fitness: self.pso.reported_fitness(),
+            improved: self.pso.improvements.len() > improvements,
+            finished: self.finished,
+        }
+    }
+
+    fn best(&self) -> (Vec<f32>, f32) {
+        (
+            self.pso.global_best_position.iter().map(|v| v.to_f32()).collect(),
+            self.pso.reported_fitness(),
+        )
+    }
+}
+
+impl PSO {
+    /// Synchronous counterpart of `run`, at `parameters.precision`
+    pub fn stepper(self) -> Box<dyn Optimizer + Send> {
+        match self.parameters.precision {
+            Precision::F32 => Box::new(PsoStepper::new(self)),
+            Precision::F64 => Box::new(PsoStepper::new(self.with_precision::<f64>())),
+        }
+    }
+}
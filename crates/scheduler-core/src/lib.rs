@@ -0,0 +1,8 @@
+//! Scheduling engine: request and schedule models, the PSO/GA optimizer,
+//! decoding, constraint checking and repair, with no HTTP or storage. The
+//! default `runtime` feature adds the async `PSO::run` (tokio channels for
+//! progress, cancellation and live parameters) used by the server; without
+//! it the engine is driven synchronously through `PsoStepper`.
+
+pub mod algorithms;
+pub mod locale;
@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+use crate::algorithms::models::OptimizedCourse;
+
+/// Language used for day names in exports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Locale {
+    #[default]
+    Id,
+    En,
+}
+
+const DAYS_ID: [&str; 7] = ["Senin", "Selasa", "Rabu", "Kamis", "Jumat", "Sabtu", "Minggu"];
+const DAYS_EN: [&str; 7] = ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"];
+
+impl Locale {
+    /// Day name for `hari` (1 = Monday); exam days past a week fall back to a number
+    pub fn day_name(self, hari: u32) -> String {
+        let names = match self {
+            Locale::Id => &DAYS_ID,
+            Locale::En => &DAYS_EN,
+        };
+        match hari.checked_sub(1).and_then(|i| names.get(i as usize)) {
+            Some(name) => name.to_string(),
+            None => match self {
+                Locale::Id => format!("Hari ke-{}", hari),
+                Locale::En => format!("Day {}", hari),
+            },
+        }
+    }
+
+    /// Row with the readable day and times next to the numeric fields
+    pub fn verbose(self, course: &OptimizedCourse) -> VerboseCourse<'_> {
+        VerboseCourse {
+            course,
+            day_name: self.day_name(course.hari),
+            jam_mulai_hhmm: format_minutes(course.jam_mulai),
+            jam_akhir_hhmm: format_minutes(course.jam_akhir),
+        }
+    }
+
+    /// "Senin, 08:00–09:20"
+    pub fn format_slot(self, course: &OptimizedCourse) -> String {
+        format!(
+            "{}, {}–{}",
+            self.day_name(course.hari),
+            format_minutes(course.jam_mulai),
+            format_minutes(course.jam_akhir)
+        )
+    }
+}
+
+/// Schedule row returned when a request sets `verbose_output`
+#[derive(Serialize)]
+pub struct VerboseCourse<'a> {
+    #[serde(flatten)]
+    pub course: &'a OptimizedCourse,
+    pub day_name: String,
+    pub jam_mulai_hhmm: String,
+    pub jam_akhir_hhmm: String,
+}
+
+/// Minutes since midnight as HH:MM
+pub fn format_minutes(minutes: u32) -> String {
+    format!("{:02}:{:02}", minutes / 60, minutes % 60)
+}
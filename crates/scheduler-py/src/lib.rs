@@ -0,0 +1,121 @@
+//! Python bindings for the optimizer and the fitness calculator, so
+//! experiments can be driven from notebooks without the HTTP server. The
+//! pyo3 module is only built with the `python` feature:
+//!
+//! ```text
+//! maturin develop --features python
+//! ```
+//!
+//! Requests and schedules cross the boundary as JSON, in the same shape as
+//! the body of POST /optimize and the `schedule` of its result.
+
+#[cfg(feature = "python")]
+mod python;
+
+use scheduler_core::algorithms::{
+    finalize::finalize_schedule,
+    models::{OptimizationRequest, PsoParameters, ScheduleChecker, PSO},
+    presets::Preset,
+    stepper::{Optimizer, StepReport},
+};
+use serde_json::{json, Value};
+
+/// Request with its config overrides folded in, ready to be optimized or
+/// scored
+pub struct Prepared {
+    pub request: OptimizationRequest,
+    pub checker: ScheduleChecker,
+    pub parameters: PsoParameters,
+}
+
+impl Prepared {
+    /// Parses a request; `mode`, `per_day_sks_rule` and
+    /// `kelas_waktu_policy` override `config` as they do on the server
+    pub fn from_json(body: &str) -> Result<Self, String> {
+        let mut request: OptimizationRequest =
+            serde_json::from_str(body).map_err(|e| format!("invalid request: {}", e))?;
+        if request.courses.is_empty() {
+            return Err("courses must contain at least one course".to_string());
+        }
+
+        let mut config = request.config.clone().unwrap_or_default();
+        if let Some(mode) = request.mode.take() {
+            config.mode = mode;
+        }
+        if let Some(rule) = request.per_day_sks_rule.take() {
+            config.per_day_sks_rule = rule;
+        }
+        if let Some(policy) = request.kelas_waktu_policy.take() {
+            config.kelas_waktu_policy = policy;
+        }
+        config.validate().map_err(|e| format!("invalid config: {}", e))?;
+        let parameters = request
+            .parameters
+            .clone()
+            .or_else(|| request.preset.map(Preset::parameters))
+            .unwrap_or_default();
+        request.config = Some(config.clone());
+
+        let checker = ScheduleChecker::for_request(&request, config);
+        Ok(Prepared { request, checker, parameters })
+    }
+
+    /// Synchronous optimizer for the request and the seed it runs with
+    pub fn optimizer(&self) -> (Box<dyn Optimizer + Send>, u64) {
+        let mut pso = PSO::new(self.request.courses.clone(), self.checker.clone(), self.parameters.clone());
+        pso.algorithm = self.request.algorithm;
+        let seed = pso.seed;
+        (pso.stepper(), seed)
+    }
+
+    /// Final schedule of a finished optimizer, shaped like the CLI output
+    pub fn result(&self, optimizer: &dyn Optimizer, report: &StepReport, seed: u64) -> Value {
+        let (position, _) = optimizer.best();
+        let result = finalize_schedule(&self.request, &self.checker, &position);
+        let fitness = self.checker.evaluate(&result.schedule);
+        let (conflicts, preferences, bookings, soft) = self.checker.evaluate_messages(&result.schedule);
+        json!({
+            "fitness": fitness,
+            "quality": self.checker.config.quality.quality(fitness),
+            "seed": seed,
+            "iterations": report.iteration,
+            "termination_reason": report.finished,
+            "schedule": result.schedule,
+            "unplaced": result.unplaced,
+            "unassigned_rooms": result.unassigned_rooms,
+            "message": [conflicts, preferences, bookings, soft],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REQUEST: &str = r#"{
+        "courses": [
+            {"id_jadwal": 1, "id_matkul": 1, "id_dosen": 1, "id_waktu": 1, "id_kelas": 1, "semester": 1, "sks": 2, "prodi": 1},
+            {"id_jadwal": 2, "id_matkul": 2, "id_dosen": 1, "id_waktu": 1, "id_kelas": 1, "semester": 1, "sks": 2, "prodi": 1}
+        ],
+        "time_preferences": [],
+        "parameters": {"swarm_size": 5, "max_iterations": 10, "cognitive_weight": 1.5, "social_weight": 1.5, "inertia_weight": 0.7}
+    }"#;
+
+    #[test]
+    fn from_json_rejects_an_empty_request() {
+        let err = Prepared::from_json(r#"{"courses": [], "time_preferences": []}"#).err().unwrap();
+        assert!(err.contains("at least one course"));
+    }
+
+    #[test]
+    fn result_schedules_every_course() {
+        let prepared = Prepared::from_json(REQUEST).unwrap();
+        let (mut optimizer, seed) = prepared.optimizer();
+        let report = optimizer.run_to_end();
+        assert!(report.finished.is_some());
+
+        let result = prepared.result(optimizer.as_ref(), &report, seed);
+        assert_eq!(result["schedule"].as_array().unwrap().len(), 2);
+        assert_eq!(result["seed"], seed);
+    }
+}
@@ -0,0 +1,147 @@
+use std::sync::Mutex;
+
+use pyo3::{exceptions::PyValueError, prelude::*};
+use scheduler_core::algorithms::{
+    models::{OptimizedCourse, ScheduleChecker},
+    stepper::{Optimizer, StepReport},
+};
+
+use crate::Prepared;
+
+/// `(iteration, best_fitness, improved, finished)` as seen from Python;
+/// `finished` is the termination reason once the run has ended
+type PyStep = (usize, f32, bool, Option<String>);
+
+fn to_py(report: &StepReport) -> PyStep {
+    let finished = report
+        .finished
+        .and_then(|reason| serde_json::to_value(reason).ok())
+        .and_then(|reason| reason.as_str().map(str::to_string));
+    (report.iteration, report.best_fitness, report.improved, finished)
+}
+
+fn value_error(message: String) -> PyErr {
+    PyValueError::new_err(message)
+}
+
+fn parse_schedule(schedule: &str) -> PyResult<Vec<OptimizedCourse>> {
+    serde_json::from_str(schedule).map_err(|e| value_error(format!("invalid schedule: {}", e)))
+}
+
+/// One optimization run stepped from Python:
+///
+/// ```python
+/// opt = scheduler_py.Optimizer(json.dumps(request))
+/// while opt.step()[3] is None: ...
+/// result = json.loads(opt.result())
+/// ```
+#[pyclass(name = "Optimizer")]
+struct PyOptimizer {
+    prepared: Prepared,
+    #[pyo3(get)]
+    seed: u64,
+    // PSO dipakai dari satu thread Python sekaligus; Mutex membuatnya Sync
+    state: Mutex<(Box<dyn Optimizer + Send>, Option<StepReport>)>,
+}
+
+#[pymethods]
+impl PyOptimizer {
+    #[new]
+    fn new(request: &str) -> PyResult<Self> {
+        let prepared = Prepared::from_json(request).map_err(value_error)?;
+        let (optimizer, seed) = prepared.optimizer();
+        Ok(PyOptimizer { prepared, seed, state: Mutex::new((optimizer, None)) })
+    }
+
+    /// Runs one iteration
+    fn step(&self, py: Python<'_>) -> PyStep {
+        py.detach(|| {
+            let mut state = self.state.lock().unwrap();
+            let report = state.0.step();
+            state.1 = Some(report);
+            to_py(&report)
+        })
+    }
+
+    /// Steps until the run ends, without holding the GIL
+    fn run_to_end(&self, py: Python<'_>) -> PyStep {
+        py.detach(|| {
+            let mut state = self.state.lock().unwrap();
+            let report = state.0.run_to_end();
+            state.1 = Some(report);
+            to_py(&report)
+        })
+    }
+
+    /// Best position so far (day and time value per course) and its fitness
+    fn best(&self) -> (Vec<f32>, f32) {
+        self.state.lock().unwrap().0.best()
+    }
+
+    /// Final schedule of the best position as JSON, like scheduler-cli
+    /// writes it; the run is finished first if it hasn't ended yet
+    fn result(&self, py: Python<'_>) -> PyResult<String> {
+        py.detach(|| {
+            let mut state = self.state.lock().unwrap();
+            let report = match state.1.filter(|r| r.finished.is_some()) {
+                Some(report) => report,
+                None => state.0.run_to_end(),
+            };
+            state.1 = Some(report);
+            let result = self.prepared.result(state.0.as_ref(), &report, self.seed);
+            serde_json::to_string(&result).map_err(|e| value_error(e.to_string()))
+        })
+    }
+}
+
+/// Fitness of hand-made or stored schedules under a request's constraints
+#[pyclass(name = "FitnessCalculator")]
+struct PyFitnessCalculator {
+    checker: ScheduleChecker,
+}
+
+#[pymethods]
+impl PyFitnessCalculator {
+    #[new]
+    fn new(request: &str) -> PyResult<Self> {
+        let prepared = Prepared::from_json(request).map_err(value_error)?;
+        Ok(PyFitnessCalculator { checker: prepared.checker })
+    }
+
+    /// Total penalty; 0 is a schedule with no violations
+    fn evaluate(&self, schedule: &str) -> PyResult<f32> {
+        Ok(self.checker.evaluate(&parse_schedule(schedule)?))
+    }
+
+    /// `(hard, soft)` penalty
+    fn evaluate_split(&self, schedule: &str) -> PyResult<(u32, u32)> {
+        Ok(self.checker.evaluate_split(&parse_schedule(schedule)?))
+    }
+
+    /// `[conflicts, preferences, bookings, soft]` as JSON, the `message`
+    /// field of an optimize result
+    fn violations(&self, schedule: &str) -> PyResult<String> {
+        let messages = self.checker.evaluate_messages(&parse_schedule(schedule)?);
+        serde_json::to_string(&messages).map_err(|e| value_error(e.to_string()))
+    }
+}
+
+/// Runs a request to the end and returns the result as JSON
+#[pyfunction]
+fn optimize(py: Python<'_>, request: &str) -> PyResult<String> {
+    let prepared = Prepared::from_json(request).map_err(value_error)?;
+    py.detach(|| {
+        let (mut optimizer, seed) = prepared.optimizer();
+        let report = optimizer.run_to_end();
+        let result = prepared.result(optimizer.as_ref(), &report, seed);
+        serde_json::to_string(&result).map_err(|e| value_error(e.to_string()))
+    })
+}
+
+#[pymodule]
+fn scheduler_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyOptimizer>()?;
+    m.add_class::<PyFitnessCalculator>()?;
+    m.add_function(wrap_pyfunction!(optimize, m)?)?;
+    Ok(())
+}
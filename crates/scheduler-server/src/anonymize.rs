@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use crate::algorithms::models::{ConstraintConfig, OptimizationRequest, OptimizedCourse, DOSEN_TBA};
+
+/// Consistent renumbering of one id space, in order of first appearance
+#[derive(Default)]
+struct IdMap(HashMap<u32, u32>);
+
+impl IdMap {
+    fn map(&mut self, id: u32) -> u32 {
+        let next = self.0.len() as u32 + 1;
+        *self.0.entry(id).or_insert(next)
+    }
+
+    /// Like `map`, but a TBA lecturer stays TBA
+    fn map_dosen(&mut self, id: u32) -> u32 {
+        if id == DOSEN_TBA {
+            DOSEN_TBA
+        } else {
+            self.map(id)
+        }
+    }
+}
+
+#[derive(Default)]
+struct Remapper {
+    jadwal: IdMap,
+    matkul: IdMap,
+    dosen: IdMap,
+    kelas: IdMap,
+    prodi: IdMap,
+    groups: IdMap,
+}
+
+impl Remapper {
+    fn course(&mut self, course: &mut OptimizedCourse) {
+        course.id_jadwal = self.jadwal.map(course.id_jadwal);
+        course.id_matkul = self.matkul.map(course.id_matkul);
+        course.id_dosen = self.dosen.map_dosen(course.id_dosen);
+        course.id_kelas = self.kelas.map(course.id_kelas);
+        course.prodi = self.prodi.map(course.prodi);
+    }
+
+    fn config(&mut self, config: &mut ConstraintConfig) {
+        config.kelas_waktu_policy = std::mem::take(&mut config.kelas_waktu_policy)
+            .into_iter()
+            .map(|(id_kelas, allowed)| (self.kelas.map(id_kelas), allowed))
+            .collect();
+        config.durations.by_matkul = std::mem::take(&mut config.durations.by_matkul)
+            .into_iter()
+            .map(|(id_matkul, factor)| (self.matkul.map(id_matkul), factor))
+            .collect();
+    }
+}
+
+/// Copy of an instance that can be attached to a bug report: jadwal, matkul,
+/// dosen, kelas, prodi and student-group ids are renumbered from 1 and free
+/// text is dropped. Times, SKS, semesters and rooms stay as they are, so the
+/// copy behaves exactly like the original.
+pub fn anonymize(request: &OptimizationRequest) -> OptimizationRequest {
+    let mut request = request.clone();
+    let mut ids = Remapper::default();
+
+    for course in &mut request.courses {
+        course.id_jadwal = ids.jadwal.map(course.id_jadwal);
+        course.id_matkul = ids.matkul.map(course.id_matkul);
+        course.id_dosen = ids.dosen.map_dosen(course.id_dosen);
+        course.id_kelas = ids.kelas.map(course.id_kelas);
+        course.prodi = ids.prodi.map(course.prodi);
+        for group in &mut course.student_groups {
+            *group = ids.groups.map(*group);
+        }
+    }
+    for course in &mut request.courses {
+        for id_jadwal in &mut course.cross_listed_with {
+            *id_jadwal = ids.jadwal.map(*id_jadwal);
+        }
+    }
+
+    // Dosen tanpa mata kuliah tetap diberi nomor baru agar tidak bocor
+    for preference in &mut request.time_preferences {
+        preference.id_dosen = ids.dosen.map_dosen(preference.id_dosen);
+    }
+    for lecturer in &mut request.lecturers {
+        lecturer.id_dosen = ids.dosen.map_dosen(lecturer.id_dosen);
+    }
+    for booking in &mut request.external_bookings {
+        booking.keterangan = None;
+    }
+    for course in request.previous_schedule.iter_mut().flatten() {
+        ids.course(course);
+    }
+    for course in &mut request.initial_schedule {
+        ids.course(course);
+    }
+    if let Some(policy) = request.kelas_waktu_policy.take() {
+        request.kelas_waktu_policy = Some(
+            policy
+                .into_iter()
+                .map(|(id_kelas, allowed)| (ids.kelas.map(id_kelas), allowed))
+                .collect(),
+        );
+    }
+    if let Some(config) = &mut request.config {
+        ids.config(config);
+    }
+
+    request
+}
@@ -0,0 +1,101 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+use crate::{algorithms::models::OptimizedCourse, integrity::Integrity};
+
+pub const ARTIFACT_DIR_ENV: &str = "ARTIFACT_DIR";
+
+/// Final schedule stored under its content hash
+#[derive(Debug, Clone, Serialize)]
+pub struct ArtifactRef {
+    pub sha256: String,
+    /// An identical schedule was already stored, so nothing changed since
+    pub reused: bool,
+}
+
+/// One line of `<dir>/<tenant>/index.ndjson`
+#[derive(Serialize)]
+struct ArtifactMeta<'a> {
+    sha256: &'a str,
+    job_id: u64,
+    created_ms: i64,
+    rows: usize,
+    reused: bool,
+}
+
+/// Writes the canonical CSV of a schedule to `<dir>/<tenant>/<sha256>.csv`
+/// unless that file already exists, and appends its metadata to the
+/// tenant's index
+pub fn store(dir: &Path, tenant: &str, job_id: u64, schedule: &[OptimizedCourse], integrity: &Integrity) -> io::Result<ArtifactRef> {
+    let tenant_dir = dir.join(tenant);
+    fs::create_dir_all(&tenant_dir)?;
+
+    let path = tenant_dir.join(format!("{}.csv", integrity.sha256));
+    let reused = path.exists();
+    if !reused {
+        // Tulis ke file sementara dulu agar pembaca tidak melihat artefak setengah jadi
+        let tmp = tenant_dir.join(format!("{}.csv.tmp", integrity.sha256));
+        fs::write(&tmp, Integrity::canonical_csv(schedule))?;
+        fs::rename(&tmp, &path)?;
+    }
+
+    let meta = ArtifactMeta {
+        sha256: &integrity.sha256,
+        job_id,
+        created_ms: chrono::Utc::now().timestamp_millis(),
+        rows: schedule.len(),
+        reused,
+    };
+    let mut index = OpenOptions::new().create(true).append(true).open(tenant_dir.join("index.ndjson"))?;
+    serde_json::to_writer(&mut index, &meta)?;
+    index.write_all(b"\n")?;
+    index.flush()?;
+
+    Ok(ArtifactRef { sha256: integrity.sha256.clone(), reused })
+}
+
+/// One line of `<dir>/<tenant>/published.ndjson`
+#[derive(Serialize)]
+struct PublishMeta<'a> {
+    sha256: &'a str,
+    job_id: u64,
+    published_ms: i64,
+    /// Published although a quality gate failed
+    overridden: bool,
+}
+
+/// Stores the schedule like `store` and records it as published
+pub fn publish(
+    dir: &Path,
+    tenant: &str,
+    job_id: u64,
+    schedule: &[OptimizedCourse],
+    integrity: &Integrity,
+    overridden: bool,
+) -> io::Result<ArtifactRef> {
+    let artifact = store(dir, tenant, job_id, schedule, integrity)?;
+
+    let meta = PublishMeta {
+        sha256: &artifact.sha256,
+        job_id,
+        published_ms: chrono::Utc::now().timestamp_millis(),
+        overridden,
+    };
+    let mut index = OpenOptions::new().create(true).append(true).open(dir.join(tenant).join("published.ndjson"))?;
+    serde_json::to_writer(&mut index, &meta)?;
+    index.write_all(b"\n")?;
+    index.flush()?;
+
+    Ok(artifact)
+}
+
+/// Path of a stored artifact; None for anything that is not a SHA-256 hex digest
+pub fn path(dir: &Path, tenant: &str, sha256: &str) -> Option<PathBuf> {
+    let valid = sha256.len() == 64 && sha256.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+    valid.then(|| dir.join(tenant).join(format!("{}.csv", sha256)))
+}
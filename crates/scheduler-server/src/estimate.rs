@@ -0,0 +1,143 @@
+use std::time::Instant;
+
+use rand::Rng;
+use serde::Serialize;
+
+use crate::algorithms::models::{
+    ConstraintConfig, CourseRequest, CourseType, Particle, PsoParameters, ScheduleChecker, DEFAULT_PRIORITY, PSO,
+};
+
+// Ukuran instance sintetis untuk kalibrasi saat server start
+const CALIBRATION_SIZES: (usize, usize) = (100, 400);
+const CALIBRATION_MIN_SECS: f64 = 0.05;
+
+/// Cost of one particle evaluation, fitted as `linear * n + quadratic * n^2`
+/// seconds for `n` courses (the clash check is pairwise)
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Calibration {
+    pub linear: f64,
+    pub quadratic: f64,
+    pub threads: usize,
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Self {
+            linear: 2e-7,
+            quadratic: 2e-9,
+            threads: rayon::current_num_threads(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Estimate {
+    pub evaluations: u64,
+    pub estimated_seconds: f64,
+    pub estimated_memory_bytes: u64,
+}
+
+impl Calibration {
+    /// Times decode + evaluate on two synthetic instances
+    pub fn measure() -> Self {
+        let (n1, n2) = CALIBRATION_SIZES;
+        let (t1, t2) = (seconds_per_eval(n1), seconds_per_eval(n2));
+        let (n1, n2) = (n1 as f64, n2 as f64);
+
+        // t = a*n + b*n^2, diselesaikan dari dua titik ukur
+        let quadratic = ((t2 / n2 - t1 / n1) / (n2 - n1)).max(0.0);
+        let linear = (t1 / n1 - quadratic * n1).max(0.0);
+
+        Self {
+            linear,
+            quadratic,
+            threads: rayon::current_num_threads(),
+        }
+    }
+
+    pub fn estimate(&self, courses: usize, parameters: &PsoParameters) -> Estimate {
+        let n = courses as f64;
+        // Ukuran yang tidak masuk akal dijenuhkan, bukan overflow
+        let evaluations = u64::try_from(parameters.swarm_size.saturating_mul(parameters.max_iterations)).unwrap_or(u64::MAX);
+        let parallel = parameters.swarm_size.clamp(1, self.threads.max(1)) as f64;
+        let mut estimated_seconds = evaluations as f64 * (self.linear * n + self.quadratic * n * n) / parallel;
+
+        // Throttle CPU memperpanjang waktu tempuh
+        if let Some(fraction) = parameters.max_cpu_fraction.filter(|f| *f > 0.0 && *f < 1.0) {
+            estimated_seconds /= fraction as f64;
+        }
+        if let Some(rate) = parameters.max_iterations_per_second.filter(|r| *r > 0.0) {
+            estimated_seconds = estimated_seconds.max(parameters.max_iterations as f64 / rate as f64);
+        }
+
+        // position, velocity, pbest per partikel + satu jadwal hasil decode per thread;
+        // overflow dijenuhkan ke u64::MAX sehingga selalu melewati batas memori
+        let courses = u64::try_from(courses).unwrap_or(u64::MAX);
+        let swarm_size = u64::try_from(parameters.swarm_size).unwrap_or(u64::MAX);
+        let dimension = courses.saturating_mul(2);
+        let per_particle = dimension
+            .saturating_mul(3 * parameters.precision.bytes())
+            .saturating_add(std::mem::size_of::<Particle>() as u64);
+        let particles = swarm_size.saturating_mul(per_particle);
+        let schedules = (parallel as u64).saturating_mul(courses).saturating_mul(96);
+        let requests = courses.saturating_mul(std::mem::size_of::<CourseRequest>() as u64);
+
+        Estimate {
+            evaluations,
+            estimated_seconds,
+            estimated_memory_bytes: particles.saturating_add(schedules).saturating_add(requests),
+        }
+    }
+}
+
+fn seconds_per_eval(size: usize) -> f64 {
+    let mut rng = rand::rng();
+    let courses: Vec<CourseRequest> = (0..size as u32)
+        .map(|i| CourseRequest {
+            id_jadwal: i + 1,
+            id_matkul: i + 1,
+            id_dosen: i % 40 + 1,
+            id_waktu: i % 2 + 1,
+            id_kelas: i % 12 + 1,
+            semester: i % 8 + 1,
+            sks: 2 + i % 2,
+            prodi: i % 3 + 1,
+            student_groups: Vec::new(),
+            priority: DEFAULT_PRIORITY,
+            jenis: CourseType::default(),
+            cross_listed_with: Vec::new(),
+            jumlah_mahasiswa: 0,
+            locked: None,
+        })
+        .collect();
+    let position: Vec<f32> = (0..size * 2).map(|_| rng.random_range(0.0..1.0)).collect();
+    let config = ConstraintConfig::default();
+    let checker = ScheduleChecker::new(Vec::new(), config.clone(), Vec::new());
+
+    let start = Instant::now();
+    let mut runs = 0u32;
+    while runs == 0 || start.elapsed().as_secs_f64() < CALIBRATION_MIN_SECS {
+        let schedule = PSO::position_to_schedule(&position, &courses, &config);
+        std::hint::black_box(checker.evaluate(&schedule));
+        runs += 1;
+    }
+
+    start.elapsed().as_secs_f64() / runs as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimate_saturates_huge_evaluation_counts() {
+        let parameters = PsoParameters { swarm_size: 1 << 33, max_iterations: 1 << 33, ..PsoParameters::default() };
+        assert_eq!(Calibration::default().estimate(10, &parameters).evaluations, u64::MAX);
+    }
+
+    #[test]
+    fn estimate_saturates_huge_memory() {
+        let parameters = PsoParameters { swarm_size: usize::MAX, ..PsoParameters::default() };
+        assert_eq!(Calibration::default().estimate(usize::MAX, &parameters).estimated_memory_bytes, u64::MAX);
+    }
+}
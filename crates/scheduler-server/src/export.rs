@@ -0,0 +1,216 @@
+use std::{fmt::Write, sync::Arc};
+
+use chrono::{Datelike, Duration, NaiveDate, Utc, Weekday};
+use futures::stream::Stream;
+
+use crate::{
+    algorithms::models::{OptimizedCourse, ScheduleMode},
+    jobs::JobRecord,
+    locale::Locale,
+};
+
+// Jumlah baris per chunk yang dikirim ke klien
+const CSV_ROWS_PER_CHUNK: usize = 500;
+
+pub const CSV_HEADER: &str =
+    "id_jadwal,id_matkul,id_dosen,id_kelas,id_waktu,hari,jam_mulai,jam_akhir,ruangan,semester,sks,prodi\n";
+
+pub fn write_csv_row(buf: &mut String, course: &OptimizedCourse) {
+    write_csv_fields(buf, course);
+    buf.push('\n');
+}
+
+fn write_csv_fields(buf: &mut String, course: &OptimizedCourse) {
+    let _ = write!(
+        buf,
+        "{},{},{},{},{},{},{},{},{},{},{},{}",
+        course.id_jadwal,
+        course.id_matkul,
+        course.id_dosen,
+        course.id_kelas,
+        course.id_waktu,
+        course.hari,
+        course.jam_mulai,
+        course.jam_akhir,
+        course.ruangan,
+        course.semester,
+        course.sks,
+        course.prodi,
+    );
+}
+
+/// Streams the schedule as CSV in fixed-size chunks instead of building the whole file.
+/// The export adds a localized `waktu` column that the integrity hash leaves out.
+pub fn csv_stream(job: Arc<JobRecord>, locale: Locale) -> impl Stream<Item = Result<String, std::io::Error>> {
+    async_stream::stream! {
+        yield Ok(format!("{},waktu\n", CSV_HEADER.trim_end()));
+
+        for rows in job.schedule.chunks(CSV_ROWS_PER_CHUNK) {
+            let mut buf = String::with_capacity(rows.len() * 72);
+            for course in rows {
+                write_csv_fields(&mut buf, course);
+                // Berisi koma, jadi selalu dikutip
+                let _ = writeln!(buf, ",\"{}\"", locale.format_slot(course));
+            }
+            yield Ok(buf);
+        }
+    }
+}
+
+/// How a stored schedule is laid onto the calendar for the .ics feed
+pub struct IcalOptions {
+    pub job_id: u64,
+    /// First day of lectures; weekly rows start on the first matching weekday
+    /// on or after it, exam days count weekdays from it
+    pub semester_start: NaiveDate,
+    pub weeks: u32,
+    pub tz: &'static IcalZone,
+    pub mode: ScheduleMode,
+    /// Only this lecturer's rows, for a personal feed
+    pub id_dosen: Option<u32>,
+    pub locale: Locale,
+}
+
+/// Time zone the feed's local times are written in. Only zones without
+/// daylight saving are offered, so one STANDARD rule describes them fully.
+pub struct IcalZone {
+    /// IANA zone name, e.g. Asia/Jakarta
+    pub tzid: &'static str,
+    pub abbreviation: &'static str,
+    pub offset_minutes: i32,
+}
+
+pub const ICAL_ZONES: [IcalZone; 5] = [
+    IcalZone { tzid: "Asia/Jakarta", abbreviation: "WIB", offset_minutes: 7 * 60 },
+    IcalZone { tzid: "Asia/Pontianak", abbreviation: "WIB", offset_minutes: 7 * 60 },
+    IcalZone { tzid: "Asia/Makassar", abbreviation: "WITA", offset_minutes: 8 * 60 },
+    IcalZone { tzid: "Asia/Jayapura", abbreviation: "WIT", offset_minutes: 9 * 60 },
+    IcalZone { tzid: "UTC", abbreviation: "UTC", offset_minutes: 0 },
+];
+
+pub fn ical_zone(tzid: &str) -> Option<&'static IcalZone> {
+    ICAL_ZONES.iter().find(|zone| zone.tzid == tzid)
+}
+
+impl IcalZone {
+    // Format TZOFFSETFROM/TZOFFSETTO, mis. +0700
+    fn offset(&self) -> String {
+        let sign = if self.offset_minutes < 0 { '-' } else { '+' };
+        let minutes = self.offset_minutes.unsigned_abs();
+        format!("{}{:02}{:02}", sign, minutes / 60, minutes % 60)
+    }
+}
+
+/// iCalendar feed with one VEVENT per schedule row: weekly rows repeat for
+/// `weeks` weeks, exam rows happen once. Times are local to `tz`, which is
+/// described by a VTIMEZONE so clients don't need their own zone database.
+pub fn ical(job: &JobRecord, options: &IcalOptions) -> String {
+    let stamp = job.times.finished_at.unwrap_or_else(Utc::now).format("%Y%m%dT%H%M%SZ");
+    let (course_label, kelas_label, room_label, dosen_label) = match options.locale {
+        Locale::Id => ("Matkul", "Kelas", "Ruang", "Dosen"),
+        Locale::En => ("Course", "Class", "Room", "Lecturer"),
+    };
+
+    let mut out = String::new();
+    for line in [
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Optimize-schedule-app//optimalizerApp//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(&format!("Jadwal {}", options.job_id))),
+        format!("X-WR-TIMEZONE:{}", options.tz.tzid),
+        "BEGIN:VTIMEZONE".to_string(),
+        format!("TZID:{}", options.tz.tzid),
+        "BEGIN:STANDARD".to_string(),
+        "DTSTART:19700101T000000".to_string(),
+        format!("TZOFFSETFROM:{}", options.tz.offset()),
+        format!("TZOFFSETTO:{}", options.tz.offset()),
+        format!("TZNAME:{}", options.tz.abbreviation),
+        "END:STANDARD".to_string(),
+        "END:VTIMEZONE".to_string(),
+    ] {
+        push_line(&mut out, &line);
+    }
+
+    // UID memakai hash jadwal, bukan hanya job id yang diulang dari 1 setelah restart
+    let schedule_hash = &job.integrity.sha256[..16];
+    for course in &job.schedule {
+        if options.id_dosen.is_some_and(|id| id != course.id_dosen) {
+            continue;
+        }
+        let Some(date) = first_date(course.hari, options.semester_start, options.mode) else {
+            continue;
+        };
+        let at = |minutes: u32| format!("{}T{:02}{:02}00", date.format("%Y%m%d"), minutes / 60, minutes % 60);
+
+        push_line(&mut out, "BEGIN:VEVENT");
+        push_line(&mut out, &format!("UID:jadwal-{}-{}@optimalizerapp", course.id_jadwal, schedule_hash));
+        push_line(&mut out, &format!("DTSTAMP:{}", stamp));
+        push_line(&mut out, &format!("DTSTART;TZID={}:{}", options.tz.tzid, at(course.jam_mulai)));
+        push_line(&mut out, &format!("DTEND;TZID={}:{}", options.tz.tzid, at(course.jam_akhir)));
+        if options.mode == ScheduleMode::Weekly {
+            push_line(&mut out, &format!("RRULE:FREQ=WEEKLY;COUNT={}", options.weeks));
+        }
+        let summary = format!("{} {} - {} {}", course_label, course.id_matkul, kelas_label, course.id_kelas);
+        push_line(&mut out, &format!("SUMMARY:{}", escape_text(&summary)));
+        // Ruangan 0 berarti belum mendapat ruangan; nama dari katalog bila ada
+        if course.ruangan != 0 {
+            let location = job
+                .request
+                .rooms
+                .iter()
+                .find(|room| room.ruangan == course.ruangan)
+                .and_then(|room| room.nama.clone())
+                .unwrap_or_else(|| format!("{} {}", room_label, course.ruangan));
+            push_line(&mut out, &format!("LOCATION:{}", escape_text(&location)));
+        }
+        let description = format!(
+            "{} {}, {} SKS, prodi {}, semester {}",
+            dosen_label, course.id_dosen, course.sks, course.prodi, course.semester
+        );
+        push_line(&mut out, &format!("DESCRIPTION:{}", escape_text(&description)));
+        push_line(&mut out, "END:VEVENT");
+    }
+
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+/// Date of the first meeting: the first `hari` weekday (1 = Senin) on or
+/// after `start`, or for exams the `hari`-th weekday counted from `start`
+fn first_date(hari: u32, start: NaiveDate, mode: ScheduleMode) -> Option<NaiveDate> {
+    match mode {
+        ScheduleMode::Weekly => {
+            let weekday = Weekday::try_from(u8::try_from(hari.checked_sub(1)?).ok()?).ok()?;
+            let offset = (7 + weekday.num_days_from_monday() - start.weekday().num_days_from_monday()) % 7;
+            start.checked_add_signed(Duration::days(offset as i64))
+        }
+        ScheduleMode::Exam => start
+            .iter_days()
+            .filter(|d| !matches!(d.weekday(), Weekday::Sat | Weekday::Sun))
+            .nth(hari.checked_sub(1)? as usize),
+    }
+}
+
+// RFC 5545: koma, titik koma, backslash dan baris baru di nilai teks di-escape
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+// Baris lebih dari 75 oktet dilipat; lanjutan diawali satu spasi
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+use crate::algorithms::models::{OptimizedCourse, ScheduleChecker};
+
+/// Conditions a schedule must meet before it can be published
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QualityGates {
+    pub zero_hard_conflicts: bool,
+    /// Minimum share of sessions in a slot their lecturer prefers, 0–100
+    pub min_preferences_satisfied_pct: Option<f32>,
+    /// Longest idle stretch allowed between two sessions of a kelas
+    pub max_gap_minutes: Option<u32>,
+}
+
+impl Default for QualityGates {
+    fn default() -> Self {
+        Self {
+            zero_hard_conflicts: true,
+            min_preferences_satisfied_pct: None,
+            max_gap_minutes: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GateResult {
+    pub gate: &'static str,
+    pub passed: bool,
+    pub actual: f32,
+    pub limit: f32,
+    pub deskripsi: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GateReport {
+    pub passed: bool,
+    pub gates: Vec<GateResult>,
+}
+
+impl QualityGates {
+    /// Checks every configured gate; unset gates are left out of the report
+    pub fn evaluate(&self, checker: &ScheduleChecker, schedule: &[OptimizedCourse]) -> GateReport {
+        let mut gates = Vec::new();
+
+        if self.zero_hard_conflicts {
+            let (conflicts, _, bookings, _) = checker.evaluate_messages(schedule);
+            let hard = (conflicts.len() + bookings.len()) as f32;
+            gates.push(GateResult {
+                gate: "zero_hard_conflicts",
+                passed: hard == 0.0,
+                actual: hard,
+                limit: 0.0,
+                deskripsi: format!("{} bentrok keras tersisa.", hard),
+            });
+        }
+
+        let stats = checker.schedule_stats(schedule);
+        if let Some(min) = self.min_preferences_satisfied_pct {
+            gates.push(GateResult {
+                gate: "min_preferences_satisfied_pct",
+                passed: stats.preferences_satisfied_pct >= min,
+                actual: stats.preferences_satisfied_pct,
+                limit: min,
+                deskripsi: format!(
+                    "{:.1}% sesi sesuai preferensi dosen (minimal {:.1}%).",
+                    stats.preferences_satisfied_pct, min
+                ),
+            });
+        }
+        if let Some(max) = self.max_gap_minutes {
+            gates.push(GateResult {
+                gate: "max_gap_minutes",
+                passed: stats.max_gap_minutes <= max,
+                actual: stats.max_gap_minutes as f32,
+                limit: max as f32,
+                deskripsi: format!("Jeda terpanjang {} menit (maksimal {} menit).", stats.max_gap_minutes, max),
+            });
+        }
+
+        GateReport {
+            passed: gates.iter().all(|g| g.passed),
+            gates,
+        }
+    }
+}
@@ -0,0 +1,1834 @@
+use axum::{
+    body::Body,
+    extract::{ws::WebSocketUpgrade, Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response, sse::{Event, Sse}},
+    Json,
+};
+use futures::{stream::Stream, FutureExt};
+use rand::{rngs::StdRng, SeedableRng};
+use serde::Deserialize;
+use serde_json::json;
+use log::error;
+use crate::tenant::{ActiveRun, Admin, Busy, Tenant, TenantDefaults, TenantRegistry, TenantState};
+use crate::jobs::{JobRecord, JobStatus, JobTimes};
+use crate::anonymize;
+use crate::artifacts;
+use crate::export;
+use crate::locale::Locale;
+use crate::roster::{self, Roster, RosterBy};
+use crate::hotspots::{self, HotspotReport};
+use crate::payload::{self, EventSizeReport};
+use crate::result::{
+    Feasibility, FragileCourse, OptimizationResult, ProdiReport, QualitySummary, RunStatistics, RunStats, RunSummary, VerboseResult, WarmStart,
+    SUMMARY_SCHEMA_VERSION,
+};
+use crate::integrity::Integrity;
+use crate::estimate::Calibration;
+use crate::runlog::{self, RunEvent, RunLog};
+use crate::tracking;
+use crate::settings::SettingsHandle;
+use crate::scheduler::{ScheduleRequest, ScheduledRun, Scheduler};
+use std::{collections::BTreeMap, panic::AssertUnwindSafe, path::PathBuf, sync::Arc};
+use crate::algorithms::{decode::ScheduleBuilder, finalize::{finalize_schedule, FinalSchedule}, float::Precision, presets::Preset, models::{CapacityDimension, CapacityRequest, ConstraintConfig, CourseRequest, CourseUpdateRequest, EstimateRequest, LiveParams, OptimizationProgress, OptimizationRequest, PreferenceImpactRequest, PsoParameters, RerunRequest, ScheduleChecker, ScheduleMode, SessionChunk, SimulationRequest, TerminationReason, PSO}, repair::ScheduleRepairer, rooms::MAX_SUM_RUANGAN};
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+const WARM_START_MIN_SIMILARITY: f32 = 0.9;
+// Batas atas parameters.conflict_retries
+const MAX_CONFLICT_RETRIES: usize = 10;
+const DEFAULT_RETRY_PERTURBATION: f32 = 0.05;
+// Batas atas num_runs per request
+const MAX_NUM_RUNS: usize = 20;
+// Saran jeda (detik) sebelum mengirim ulang saat engine penuh
+const BUSY_RETRY_AFTER_SECS: u64 = 10;
+
+pub const SHA256_HEADER: &str = "x-schedule-sha256";
+pub const HMAC_HEADER: &str = "x-schedule-hmac-sha256";
+
+#[derive(Clone, Default)]
+pub struct AppState {
+    pub tenants: TenantRegistry,
+    pub signing_key: Option<Arc<[u8]>>,
+    pub calibration: Calibration,
+    pub run_log_dir: Option<Arc<PathBuf>>,
+    pub artifact_dir: Option<Arc<PathBuf>>,
+    pub settings: SettingsHandle,
+    pub scheduler: Scheduler,
+}
+
+#[derive(Deserialize)]
+pub struct StopQuery {
+    job_id: Option<u64>,
+}
+
+/// Cancels the given job, or every running job of the tenant. Jobs that
+/// already finished are unaffected.
+pub async fn stop_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Query(query): Query<StopQuery>,
+) -> Result<Response, StatusCode> {
+    let tenant_state = state.tenants.get(&tenant);
+
+    let mut stopped: Vec<u64> = tenant_state
+        .running
+        .read()
+        .unwrap()
+        .iter()
+        .filter(|(id, _)| query.job_id.is_none_or(|job_id| job_id == **id))
+        .map(|(id, run)| {
+            run.cancel.cancel();
+            *id
+        })
+        .collect();
+    stopped.sort_unstable();
+    if query.job_id.is_some() && stopped.is_empty() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    Ok(Json(json!({ "success": true, "stopped": stopped })).into_response())
+}
+
+/// Queues tweaks for a running job; they take effect at its next iteration
+pub async fn live_params_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(job_id): Path<u64>,
+    Json(params): Json<LiveParams>,
+) -> Result<Response, (StatusCode, String)> {
+    params.validate().map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e))?;
+
+    let tenant_state = state.tenants.get(&tenant);
+    let sent = match tenant_state.running.read().unwrap().get(&job_id) {
+        Some(run) => run.params_tx.send(params).is_ok(),
+        None => false,
+    };
+    if !sent {
+        return Err((StatusCode::NOT_FOUND, format!("Job {} tidak sedang berjalan", job_id)));
+    }
+    Ok(Json(json!({ "success": true, "job_id": job_id })).into_response())
+}
+
+pub async fn get_defaults_handler(
+    State(state): State<AppState>,
+    _admin: Admin,
+    tenant: Tenant,
+) -> Json<TenantDefaults> {
+    Json(state.tenants.get(&tenant).defaults())
+}
+
+pub async fn put_defaults_handler(
+    State(state): State<AppState>,
+    _admin: Admin,
+    tenant: Tenant,
+    Json(defaults): Json<TenantDefaults>,
+) -> Result<Response, (StatusCode, String)> {
+    defaults.config.validate().map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e))?;
+    *state.tenants.get(&tenant).defaults.write().unwrap() = Some(defaults);
+    Ok(Json(json!({ "success": true })).into_response())
+}
+
+pub async fn list_schedules_handler(State(state): State<AppState>, _admin: Admin, tenant: Tenant) -> Json<Vec<ScheduledRun>> {
+    Json(state.scheduler.list(&tenant))
+}
+
+/// Registers a recurring optimize run for the tenant
+pub async fn create_schedule_handler(
+    State(state): State<AppState>,
+    _admin: Admin,
+    tenant: Tenant,
+    Json(req): Json<ScheduleRequest>,
+) -> Result<Response, (StatusCode, String)> {
+    let entry = state
+        .scheduler
+        .register(&tenant, req)
+        .map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e))?;
+
+    Ok(Json(json!({ "success": true, "schedule": entry })).into_response())
+}
+
+pub async fn delete_schedule_handler(
+    State(state): State<AppState>,
+    _admin: Admin,
+    tenant: Tenant,
+    Path(id): Path<u64>,
+) -> Result<Response, StatusCode> {
+    if !state.scheduler.remove(&tenant, id) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    Ok(Json(json!({ "success": true })).into_response())
+}
+
+/// Instance with ids renumbered and free text removed, for bug reports
+pub async fn anonymize_handler(Json(req): Json<OptimizationRequest>) -> Json<OptimizationRequest> {
+    Json(anonymize::anonymize(&req))
+}
+
+/// Anonymized copy of a stored job's request, with its resolved parameters
+/// and config, so a failing run can be reproduced elsewhere
+pub async fn anonymized_job_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(id): Path<u64>,
+) -> Result<Response, StatusCode> {
+    let job = state.tenants.get(&tenant).jobs.get(id).ok_or(StatusCode::NOT_FOUND)?;
+
+    Response::builder()
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::CONTENT_DISPOSITION, format!("attachment; filename=\"instance-{}.json\"", id))
+        .body(Body::from(serde_json::to_vec(&anonymize::anonymize(&job.request)).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// JSON Schema of the optimize request, with the tenant's current
+/// `parameters` and `config` as defaults so forms can be prefilled
+pub async fn schema_handler(State(state): State<AppState>, tenant: Tenant) -> Json<serde_json::Value> {
+    let defaults = state.tenants.get(&tenant).defaults();
+    let mut schema = json!(schemars::schema_for!(OptimizationRequest));
+    if let Some(properties) = schema["properties"].as_object_mut() {
+        for (key, value) in [("parameters", json!(defaults.parameters)), ("config", json!(defaults.config))] {
+            if let Some(property) = properties.get_mut(key).and_then(|p| p.as_object_mut()) {
+                property.insert("default".to_string(), value);
+            }
+        }
+    }
+    Json(schema)
+}
+
+/// Re-reads the service config file; also triggered by SIGHUP
+pub async fn reload_settings_handler(State(state): State<AppState>, _admin: Admin) -> Result<Response, (StatusCode, String)> {
+    let settings = state
+        .settings
+        .reload()
+        .map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e))?;
+
+    Ok(Json(json!({ "success": true, "settings": settings })).into_response())
+}
+
+pub async fn simulate_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Json(req): Json<SimulationRequest>,
+) -> Result<Response, (StatusCode, String)> {
+    let config = req.config.unwrap_or_else(|| state.tenants.get(&tenant).defaults().config);
+    config.validate().map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e))?;
+    let repairer = ScheduleRepairer::new(ScheduleChecker::new(req.time_preferences, config, req.external_bookings));
+    let result = repairer.simulate(&req.schedule, &req.scenario);
+
+    Ok(Json(json!({
+        "success": true,
+        "broken": result.broken,
+        "repair": result.repaired,
+        "unresolved": result.unresolved
+    })).into_response())
+}
+
+// Run pendek per nilai kapasitas; cukup untuk membandingkan antar nilai
+const CAPACITY_DEFAULT_ITERATIONS: usize = 100;
+const CAPACITY_MAX_VALUES: usize = 32;
+
+/// Short runs for each capacity value; blocking, so callers run it off the
+/// async workers
+fn capacity_sweep(
+    request: &OptimizationRequest,
+    parameters: &PsoParameters,
+    config: &ConstraintConfig,
+    dimension: CapacityDimension,
+    values: &[u32],
+) -> Vec<serde_json::Value> {
+    let run = |config: ConstraintConfig| {
+        let checker = ScheduleChecker::for_request(request, config);
+        let mut pso = PSO::new(request.courses.clone(), checker.clone(), parameters.clone());
+        pso.algorithm = request.algorithm;
+        (checker, pso)
+    };
+
+    // Ruangan baru dialokasikan setelah PSO, jadi satu run cukup untuk semua nilai
+    let mut shared = None;
+    let mut results = Vec::with_capacity(values.len());
+    for &value in values {
+        let (checker, position) = match dimension {
+            CapacityDimension::Rooms => {
+                if shared.is_none() {
+                    let (checker, pso) = run(config.clone());
+                    let mut optimizer = pso.stepper();
+                    optimizer.run_to_end();
+                    let position = optimizer.best().0;
+                    shared = Some((checker, position));
+                }
+                shared.clone().unwrap()
+            }
+            CapacityDimension::ExamDays => {
+                let mut config = config.clone();
+                config.exam.days = value;
+                let (checker, pso) = run(config);
+                let mut optimizer = pso.stepper();
+                optimizer.run_to_end();
+                let position = optimizer.best().0;
+                (checker, position)
+            }
+        };
+
+        let (request, checker) = match dimension {
+            CapacityDimension::Rooms => {
+                let request = OptimizationRequest {
+                    rooms: request.rooms.iter().take(value as usize).cloned().collect(),
+                    sum_ruangan: Some(value),
+                    ..request.clone()
+                };
+                let checker = checker.with_rooms(&request.rooms, request.sum_ruangan, &request.courses);
+                (request, checker)
+            }
+            CapacityDimension::ExamDays => (request.clone(), checker),
+        };
+        let result = finalize_schedule(&request, &checker, &position);
+        let (conflicts, _, bookings, _) = checker.evaluate_messages(&result.schedule);
+        results.push(json!({
+            "value": value,
+            "conflicts": conflicts.len() + bookings.len(),
+            "unassigned_rooms": result.unassigned_rooms.len(),
+            "fitness": checker.evaluate(&result.schedule),
+        }));
+    }
+
+    results
+}
+
+/// Sweeps room count or exam days with short runs and reports the conflicts
+/// left at each value, plus the smallest value that leaves none
+pub async fn capacity_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Json(req): Json<CapacityRequest>,
+) -> Result<Response, (StatusCode, String)> {
+    if req.values.is_empty() || req.values.len() > CAPACITY_MAX_VALUES {
+        return Err((StatusCode::BAD_REQUEST, format!("values must list 1 to {} capacities", CAPACITY_MAX_VALUES)));
+    }
+    let defaults = state.tenants.get(&tenant).defaults();
+    let limits = state.settings.get().limits;
+    let mut request = req.request;
+    if request.courses.is_empty() {
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, "courses must contain at least one course".to_string()));
+    }
+    if let Some(max) = limits.max_courses.filter(|&max| request.courses.len() > max) {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!("{} courses exceed the limit of {}", request.courses.len(), max),
+        ));
+    }
+
+    let mut parameters = request
+        .parameters
+        .take()
+        .or_else(|| request.preset.map(Preset::parameters))
+        .unwrap_or(defaults.parameters);
+    parameters.max_iterations = req.max_iterations.unwrap_or(CAPACITY_DEFAULT_ITERATIONS);
+    if let Some(max) = limits.max_swarm_size {
+        parameters.swarm_size = parameters.swarm_size.min(max);
+    }
+    if let Some(max) = limits.max_iterations {
+        parameters.max_iterations = parameters.max_iterations.min(max);
+    }
+    parameters.require_feasible = false;
+    let config = resolve_config(&request, defaults.config)?;
+    request.config = None;
+    if req.dimension == CapacityDimension::ExamDays && config.mode != ScheduleMode::Exam {
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, "exam_days needs mode exam".to_string()));
+    }
+    request.parameters = Some(parameters.clone());
+
+    check_memory(&state, request.courses.len(), &parameters)?;
+    check_sum_ruangan(request.sum_ruangan)?;
+    if req.dimension == CapacityDimension::Rooms && request.rooms.is_empty() {
+        check_sum_ruangan(req.values.iter().copied().max())?;
+    }
+
+    let mut values = req.values;
+    values.sort_unstable();
+    values.dedup();
+
+    // Sapuan dihitung sebagai job: ikut batas job dan tidak memblokir worker tokio
+    let active_run = match start_job(&state, &tenant) {
+        Ok(active_run) => active_run,
+        Err(busy) => return Ok(busy_response(busy)),
+    };
+    let dimension = req.dimension;
+    let sweep_values = values.clone();
+    let sweep_parameters = parameters.clone();
+    let results = tokio::task::spawn_blocking(move || {
+        let results = capacity_sweep(&request, &sweep_parameters, &config, dimension, &sweep_values);
+        drop(active_run);
+        results
+    })
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("capacity sweep failed: {}", e)))?;
+
+    let minimum = values
+        .iter()
+        .zip(&results)
+        .find(|(_, r)| r["conflicts"] == 0 && r["unassigned_rooms"] == 0)
+        .map(|(value, _)| *value);
+
+    Ok(Json(json!({
+        "success": true,
+        "dimension": dimension,
+        "max_iterations": parameters.max_iterations,
+        "results": results,
+        "minimum_sufficient": minimum
+    })).into_response())
+}
+
+/// How constraining each lecturer's preferences are for their load
+pub async fn preference_impact_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Json(req): Json<PreferenceImpactRequest>,
+) -> Result<Response, (StatusCode, String)> {
+    let config = req.config.clone().unwrap_or_else(|| state.tenants.get(&tenant).defaults().config);
+    config.validate().map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e))?;
+    let checker = ScheduleChecker::for_impact(&req, config);
+
+    Ok(Json(json!({
+        "success": true,
+        "lecturers": checker.preference_impact(&req.courses)
+    })).into_response())
+}
+
+/// Predicts runtime and memory of an optimize request without running it
+pub async fn estimate_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Json(req): Json<EstimateRequest>,
+) -> Result<Response, StatusCode> {
+    let parameters = req
+        .parameters
+        .or_else(|| req.preset.map(Preset::parameters))
+        .unwrap_or_else(|| state.tenants.get(&tenant).defaults().parameters);
+    let estimate = state.calibration.estimate(req.courses.len(), &parameters);
+
+    Ok(Json(json!({
+        "success": true,
+        "courses": req.courses.len(),
+        "estimate": estimate,
+        "calibration": state.calibration
+    })).into_response())
+}
+
+#[derive(Deserialize)]
+pub struct PresetsQuery {
+    /// Adds each preset's runtime estimate for this many courses
+    courses: Option<usize>,
+}
+
+/// Named parameter presets, selectable with `preset` in optimize requests
+pub async fn presets_handler(State(state): State<AppState>, Query(query): Query<PresetsQuery>) -> Json<serde_json::Value> {
+    let presets: Vec<_> = Preset::ALL
+        .into_iter()
+        .map(|preset| {
+            let info = preset.info();
+            let estimate = query.courses.map(|courses| state.calibration.estimate(courses, &info.parameters));
+            json!({
+                "name": info.name,
+                "description": info.description,
+                "runtime_multiplier": info.runtime_multiplier,
+                "estimate": estimate,
+                "parameters": info.parameters,
+            })
+        })
+        .collect();
+
+    Json(json!({ "success": true, "presets": presets }))
+}
+
+#[derive(Deserialize)]
+pub struct ExportQuery {
+    locale: Option<Locale>,
+}
+
+pub async fn export_csv_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(id): Path<u64>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, StatusCode> {
+    let job = state.tenants.get(&tenant).jobs.get(id).ok_or(StatusCode::NOT_FOUND)?;
+    let locale = query.locale.unwrap_or(state.settings.get().locale);
+
+    let mut builder = Response::builder()
+        .header(header::CONTENT_TYPE, "text/csv; charset=utf-8")
+        .header(header::CONTENT_DISPOSITION, format!("attachment; filename=\"jadwal-{}.csv\"", id))
+        .header(SHA256_HEADER, &job.integrity.sha256);
+    if let Some(hmac) = &job.integrity.hmac_sha256 {
+        builder = builder.header(HMAC_HEADER, hmac);
+    }
+
+    builder
+        .body(Body::from_stream(export::csv_stream(job, locale)))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[derive(Deserialize)]
+pub struct IcalQuery {
+    semester_start: chrono::NaiveDate,
+    weeks: Option<u32>,
+    tz: Option<String>,
+    id_dosen: Option<u32>,
+    locale: Option<Locale>,
+}
+
+// Satu semester kuliah; dipakai bila weeks tidak diisi
+const DEFAULT_SEMESTER_WEEKS: u32 = 16;
+
+/// The stored schedule as an iCalendar feed for calendar subscriptions;
+/// `id_dosen` narrows it to one lecturer
+pub async fn export_ical_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(id): Path<u64>,
+    Query(query): Query<IcalQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    let job = state
+        .tenants
+        .get(&tenant)
+        .jobs
+        .get(id)
+        .ok_or((StatusCode::NOT_FOUND, format!("job {} not found", id)))?;
+    let weeks = query.weeks.unwrap_or(DEFAULT_SEMESTER_WEEKS);
+    if !(1..=52).contains(&weeks) {
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, "weeks must be between 1 and 52".to_string()));
+    }
+    let tz_name = query.tz.as_deref().unwrap_or("Asia/Jakarta");
+    // Hanya zona yang VTIMEZONE-nya bisa ditulis tanpa basis data zona
+    let tz = export::ical_zone(tz_name).ok_or_else(|| {
+        let supported: Vec<&str> = export::ICAL_ZONES.iter().map(|zone| zone.tzid).collect();
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!("unsupported time zone: {} (supported: {})", tz_name, supported.join(", ")),
+        )
+    })?;
+
+    let options = export::IcalOptions {
+        job_id: id,
+        semester_start: query.semester_start,
+        weeks,
+        tz,
+        mode: job.request.config.as_ref().map(|c| c.mode).unwrap_or_default(),
+        id_dosen: query.id_dosen,
+        locale: query.locale.unwrap_or(state.settings.get().locale),
+    };
+
+    Response::builder()
+        .header(header::CONTENT_TYPE, "text/calendar; charset=utf-8")
+        .header(header::CONTENT_DISPOSITION, format!("attachment; filename=\"jadwal-{}.ics\"", id))
+        .body(Body::from(export::ical(&job, &options)))
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+/// Stored schedule artifact by content hash; HEAD answers "is this exact
+/// schedule already published?" without the body
+pub async fn artifact_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(sha256): Path<String>,
+) -> Result<Response, StatusCode> {
+    let dir = state.artifact_dir.as_ref().ok_or(StatusCode::NOT_FOUND)?;
+    let path = artifacts::path(dir, &tenant.0, &sha256).ok_or(StatusCode::BAD_REQUEST)?;
+    let csv = tokio::fs::read(path).await.map_err(|_| StatusCode::NOT_FOUND)?;
+
+    Response::builder()
+        .header(header::CONTENT_TYPE, "text/csv; charset=utf-8")
+        .header(SHA256_HEADER, sha256)
+        .body(Body::from(csv))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[derive(Deserialize)]
+pub struct PublishQuery {
+    // Terbitkan meski ada quality gate yang gagal
+    #[serde(default, rename = "override")]
+    force: bool,
+}
+
+/// Publishes a job's schedule once the tenant's quality gates pass; 422 with
+/// the gate report otherwise, unless `?override=true`
+pub async fn publish_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(id): Path<u64>,
+    Query(query): Query<PublishQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    let tenant_state = state.tenants.get(&tenant);
+    let job = tenant_state
+        .jobs
+        .get(id)
+        .ok_or((StatusCode::NOT_FOUND, format!("job {} not found", id)))?;
+    let dir = state
+        .artifact_dir
+        .as_ref()
+        .ok_or((StatusCode::SERVICE_UNAVAILABLE, "artifact storage is not configured".to_string()))?;
+
+    let request = &job.request;
+    let checker = ScheduleChecker::for_request(request, request.config.clone().unwrap_or_default());
+    let report = tenant_state.defaults().gates.evaluate(&checker, &job.schedule);
+
+    if !report.passed && !query.force {
+        let body = json!({ "success": false, "job_id": id, "gates": report });
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(body)).into_response());
+    }
+
+    let artifact = artifacts::publish(dir, &tenant.0, id, &job.schedule, &job.integrity, !report.passed).map_err(|e| {
+        error!("Gagal menerbitkan jadwal {}: {}", id, e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "failed to publish schedule".to_string())
+    })?;
+
+    Ok(Json(json!({
+        "success": true,
+        "job_id": id,
+        "artifact": artifact,
+        "overridden": !report.passed,
+        "gates": report,
+    }))
+    .into_response())
+}
+
+/// Stable-schema quality numbers of a stored job for regression scripts
+pub async fn job_summary_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(id): Path<u64>,
+) -> Result<Json<QualitySummary>, (StatusCode, String)> {
+    let job = state
+        .tenants
+        .get(&tenant)
+        .jobs
+        .get(id)
+        .ok_or((StatusCode::NOT_FOUND, format!("job {} not found", id)))?;
+
+    let request = &job.request;
+    let checker = ScheduleChecker::for_request(request, request.config.clone().unwrap_or_default());
+    let fitness = checker.evaluate(&job.schedule);
+    let violations = checker.count_conflicts(&job.schedule);
+    let mut soft_violations = BTreeMap::new();
+    for message in checker.evaluate_messages(&job.schedule).3 {
+        *soft_violations.entry(message.kind).or_insert(0) += 1;
+    }
+    let selected = job.events.iter().rev().find_map(|event| match event {
+        RunEvent::Selected { run, .. } => Some(*run),
+        _ => None,
+    });
+    let termination_reason = job
+        .events
+        .iter()
+        .find_map(|event| match event {
+            RunEvent::RunFinished { run, termination_reason, .. } if Some(*run) == selected => Some(*termination_reason),
+            _ => None,
+        });
+
+    Ok(Json(QualitySummary {
+        schema_version: SUMMARY_SCHEMA_VERSION,
+        job_id: id,
+        seed: request.parameters.as_ref().and_then(|p| p.seed),
+        courses: job.schedule.len(),
+        fitness,
+        quality_score: checker.config.quality.score(fitness),
+        hard_conflicts: violations.lecturer_clash
+            + violations.kelas_clash
+            + violations.room_clash
+            + violations.room_capacity
+            + violations.exam_rule,
+        violations,
+        soft_violations,
+        runtime_ms: job.times.started_at.zip(job.times.finished_at).map(|(start, end)| (end - start).num_milliseconds()),
+        termination_reason,
+    }))
+}
+
+#[derive(Deserialize)]
+pub struct RosterQuery {
+    by: RosterBy,
+    locale: Option<Locale>,
+}
+
+pub async fn roster_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(id): Path<u64>,
+    Query(query): Query<RosterQuery>,
+) -> Result<Json<Vec<Roster>>, StatusCode> {
+    let job = state.tenants.get(&tenant).jobs.get(id).ok_or(StatusCode::NOT_FOUND)?;
+    let locale = query.locale.unwrap_or(state.settings.get().locale);
+    let config = job.request.config.clone().unwrap_or_default();
+
+    Ok(Json(roster::build(&job.schedule, query.by, config.mode, &config.slots, locale)))
+}
+
+pub async fn update_courses_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(id): Path<u64>,
+    Json(update): Json<CourseUpdateRequest>,
+) -> Result<Response, StatusCode> {
+    let tenant_state = state.tenants.get(&tenant);
+    let job = tenant_state.jobs.get(id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let config = job.request.config.clone().unwrap_or_default();
+    // Perbaikan greedy hanya mengenal slot mingguan
+    if config.mode != ScheduleMode::Weekly {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    let mut courses: Vec<CourseRequest> = job
+        .request
+        .courses
+        .iter()
+        .filter(|c| !update.remove.contains(&c.id_jadwal))
+        .filter(|c| !update.upsert.iter().any(|u| u.id_jadwal == c.id_jadwal))
+        .cloned()
+        .collect();
+    let update_event = RunEvent::CoursesUpdated {
+        upsert: update.upsert.iter().map(|c| c.id_jadwal).collect(),
+        remove: update.remove.clone(),
+    };
+    courses.extend(update.upsert);
+    let request = OptimizationRequest {
+        courses,
+        ..job.request.clone()
+    };
+
+    let checker = ScheduleChecker::for_request(&request, config);
+    let result = ScheduleRepairer::new(checker.clone()).apply_course_changes(&job.schedule, &request.courses);
+    let fitness = checker.evaluate(&result.schedule);
+    let integrity = Integrity::compute(&result.schedule, state.signing_key.as_deref());
+
+    let _ = tenant_state.status_tx.send(OptimizationProgress {
+        job_id: Some(id),
+        iteration: 0,
+        elapsed_time: Duration::ZERO,
+        timestamp: chrono::Utc::now(),
+        started_at: None,
+        best_fitness: fitness,
+        quality_score: checker.config.quality.score(fitness),
+        all_best_fitness: None,
+        current_run: None,
+        total_runs: None,
+        is_finished: true,
+        job_finished: true,
+        improved: true,
+        conflict_counts: checker.count_conflicts(&result.schedule),
+        schedule: Some(result.schedule.clone()),
+        fitness_distribution: None,
+        seed: None,
+        stats: Some(checker.schedule_stats(&result.schedule)),
+        truncated: false,
+        truncated_fields: Vec::new(),
+    });
+
+    let response = Json(json!({
+        "success": true,
+        "job_id": id,
+        "fitness": fitness,
+        "quality": checker.config.quality.quality(fitness),
+        "changed": result.broken,
+        "repair": result.repaired,
+        "unresolved": result.unresolved,
+        "schedule": result.schedule,
+        "integrity": integrity,
+    })).into_response();
+
+    // Posisi mata kuliah baru diacak dari seed job agar PATCH yang sama memberi hasil yang sama
+    let seed = job.request.parameters.as_ref().and_then(|p| p.seed).unwrap_or_default();
+    let mut transfer_rng = StdRng::seed_from_u64(seed.wrapping_add(job.events.len() as u64));
+    let replaced = tenant_state.jobs.replace(id, JobRecord {
+        best_position: PSO::transfer_position(&job.request.courses, &job.best_position, &request.courses, &mut transfer_rng),
+        request,
+        schedule: result.schedule,
+        integrity,
+        events: job
+            .events
+            .iter()
+            .cloned()
+            .chain([update_event.clone()])
+            .collect(),
+        times: job.times,
+    });
+    if !replaced {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    if let (Some(dir), Some(started_at)) = (&state.run_log_dir, job.times.started_at) {
+        if let Err(e) = RunLog::reopen(dir, &tenant.0, id, started_at).and_then(|mut log| log.sync(&[update_event])) {
+            error!("Gagal menulis log run {}: {}", id, e);
+        }
+    }
+
+    Ok(response)
+}
+
+/// One WebSocket carrying progress of several tenants/jobs, for dashboards
+/// Multiplexed progress of the tenant's jobs. Browsers can't set headers on
+/// a WebSocket, so the tenant usually comes from `?tenant=`.
+pub async fn progress_ws_handler(State(state): State<AppState>, tenant: Tenant, ws: WebSocketUpgrade) -> Response {
+    let max_bytes = state.settings.get().limits.max_event_bytes.unwrap_or(payload::DEFAULT_MAX_EVENT_BYTES);
+    ws.on_upgrade(move |socket| crate::ws::serve(socket, state.tenants, tenant, max_bytes))
+}
+
+#[derive(Deserialize)]
+pub struct StatusQuery {
+    job_id: Option<u64>,
+}
+
+/// Progress of the tenant's runs as SSE. With `?job_id=` only that job's
+/// events are sent, so clients of concurrent runs don't see each other's
+/// progress, and the stream ends once the job finishes.
+pub async fn status_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Query(query): Query<StatusQuery>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>> + 'static> {
+    let tenant_state = state.tenants.get(&tenant);
+    // Subscribe dulu baru baca cache, supaya tidak ada event yang terlewat
+    let mut rx = tenant_state.status_tx.subscribe();
+    let snapshot = match query.job_id {
+        Some(job_id) => tenant_state.job_progress.read().unwrap().get(&job_id).cloned(),
+        None => tenant_state.last_progress.read().unwrap().clone(),
+    };
+    // Job yang sudah tersimpan tidak akan mengirim event lagi
+    let finished = query.job_id.is_some_and(|job_id| tenant_state.jobs.get(job_id).is_some());
+    let job = match query.job_id {
+        Some(job_id) => json!({
+            "job_id": job_id,
+            "running": tenant_state.running.read().unwrap().contains_key(&job_id),
+        }),
+        None => json!({
+            "latest_job_id": tenant_state.jobs.latest_id(),
+            "running": snapshot.as_ref().is_some_and(|p| !p.job_finished),
+        }),
+    };
+
+    let max_bytes = state.settings.get().limits.max_event_bytes.unwrap_or(payload::DEFAULT_MAX_EVENT_BYTES);
+    let sizes = tenant_state.event_sizes.clone();
+
+    let stream = async_stream::stream! {
+        yield Ok(Event::default().data(job.to_string()).event("job"));
+        if let Some(status) = snapshot {
+            match payload::encode(&status, max_bytes, &sizes, serde_json::to_string) {
+                Some(data) => yield Ok(Event::default().data(data).event("status")),
+                None => error!("Event progres job {:?} tidak bisa diserialisasi", status.job_id),
+            }
+        }
+        if finished {
+            return;
+        }
+
+        loop {
+            let mut status = match rx.recv().await {
+                Ok(status) => status,
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+            if query.job_id.is_some_and(|job_id| status.job_id != Some(job_id)) {
+                continue;
+            }
+            let stats = status.stats.take();
+            let finished = status.job_finished;
+            match payload::encode(&status, max_bytes, &sizes, serde_json::to_string) {
+                Some(data) => yield Ok(Event::default().data(data).event("status")),
+                None => error!("Event progres job {:?} tidak bisa diserialisasi", status.job_id),
+            }
+            if let Some(stats) = stats {
+                let data = json!({ "job_id": status.job_id, "iteration": status.iteration, "stats": stats });
+                yield Ok(Event::default().data(data.to_string()).event("stats"));
+            }
+            if finished && query.job_id.is_some() {
+                break;
+            }
+        }
+    };
+    
+    Sse::new(stream)
+}
+
+/// Count and size of the progress events sent to this tenant's clients
+pub async fn event_sizes_handler(State(state): State<AppState>, tenant: Tenant) -> Json<EventSizeReport> {
+    Json(state.tenants.get(&tenant).event_sizes.report())
+}
+
+/// Opens an upload session; the body is a normal optimize request whose
+/// lists may be empty and are filled by later chunks
+pub async fn open_session_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Json(req): Json<OptimizationRequest>,
+) -> Result<Response, StatusCode> {
+    let session_id = state.tenants.get(&tenant).sessions.open(req);
+    Ok(Json(json!({ "success": true, "session_id": session_id })).into_response())
+}
+
+pub async fn session_chunk_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(id): Path<u64>,
+    Json(chunk): Json<SessionChunk>,
+) -> Result<Response, StatusCode> {
+    let size = state
+        .tenants
+        .get(&tenant)
+        .sessions
+        .append(id, chunk)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(json!({ "success": true, "session_id": id, "size": size })).into_response())
+}
+
+pub async fn start_session_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(id): Path<u64>,
+    query: Query<OptimizeQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    let req = state
+        .tenants
+        .get(&tenant)
+        .sessions
+        .take(id)
+        .ok_or((StatusCode::NOT_FOUND, format!("session {} not found", id)))?;
+    optimize_handler(State(state), tenant, query, Json(req)).await
+}
+
+pub async fn job_events_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(id): Path<u64>,
+) -> Result<Json<Vec<RunEvent>>, StatusCode> {
+    let job = state.tenants.get(&tenant).jobs.get(id).ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(job.events.clone()))
+}
+
+/// Per-iteration swarm diagnostics of every run of a job, recorded when it
+/// was started with `parameters.record_diagnostics`
+pub async fn job_diagnostics_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(id): Path<u64>,
+) -> Result<Response, (StatusCode, String)> {
+    let job = state
+        .tenants
+        .get(&tenant)
+        .jobs
+        .get(id)
+        .ok_or((StatusCode::NOT_FOUND, format!("job {} not found", id)))?;
+    let runs: Vec<_> = job
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            RunEvent::Diagnostics { run, iterations } => Some(json!({ "run": run, "iterations": iterations })),
+            _ => None,
+        })
+        .collect();
+    if runs.is_empty() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            format!("job {} was run without parameters.record_diagnostics", id),
+        ));
+    }
+
+    Ok(Json(json!({ "job_id": id, "runs": runs })).into_response())
+}
+
+#[derive(Deserialize)]
+pub struct HotspotQuery {
+    min_share_pct: Option<f32>,
+}
+
+/// Violations that keep recurring across the tenant's stored runs
+pub async fn hotspots_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Query(query): Query<HotspotQuery>,
+) -> Json<HotspotReport> {
+    let jobs = state.tenants.get(&tenant).jobs.all();
+    Json(hotspots::build(&jobs, query.min_share_pct.unwrap_or(0.0)))
+}
+
+/// Re-derives the final schedule of a run from its event log
+pub async fn replay_handler(
+    State(state): State<AppState>,
+    Json(events): Json<Vec<RunEvent>>,
+) -> Result<Response, (StatusCode, String)> {
+    let (request, position) = runlog::replay_input(&events)
+        .ok_or((StatusCode::UNPROCESSABLE_ENTITY, "events need Started, Selected and its RunFinished".to_string()))?;
+    let config = request.config.clone().unwrap_or_default();
+    config.validate().map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e))?;
+    let checker = ScheduleChecker::for_request(request, config);
+
+    let result = finalize_schedule(request, &checker, position);
+    let fitness = checker.evaluate(&result.schedule);
+    let integrity = Integrity::compute(&result.schedule, state.signing_key.as_deref());
+
+    Ok(Json(json!({
+        "success": true,
+        "fitness": fitness,
+        "quality": checker.config.quality.quality(fitness),
+        "schedule": result.schedule,
+        "integrity": integrity,
+        "unplaced": result.unplaced,
+        "unassigned_rooms": result.unassigned_rooms
+    })).into_response())
+}
+
+/// Re-launches a stored job's request (same courses and preferences) with
+/// optional partial overrides of `parameters` and `config`
+pub async fn rerun_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(id): Path<u64>,
+    query: Query<OptimizeQuery>,
+    overrides: Option<Json<RerunRequest>>,
+) -> Result<Response, (StatusCode, String)> {
+    let job = state
+        .tenants
+        .get(&tenant)
+        .jobs
+        .get(id)
+        .ok_or((StatusCode::NOT_FOUND, format!("job {} not found", id)))?;
+    let Json(overrides) = overrides.unwrap_or_default();
+
+    let mut request =
+        serde_json::to_value(&job.request).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    merge_json(&mut request["parameters"], serde_json::Value::Object(overrides.parameters));
+    merge_json(&mut request["config"], serde_json::Value::Object(overrides.config));
+    let request: OptimizationRequest =
+        serde_json::from_value(request).map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()))?;
+
+    optimize_handler(State(state), tenant, query, Json(request)).await
+}
+
+fn merge_json(target: &mut serde_json::Value, patch: serde_json::Value) {
+    match (target, patch) {
+        (serde_json::Value::Object(target), serde_json::Value::Object(patch)) => {
+            for (key, value) in patch {
+                merge_json(target.entry(key).or_insert(serde_json::Value::Null), value);
+            }
+        }
+        (target, patch) => *target = patch,
+    }
+}
+
+#[derive(Deserialize, Default)]
+pub struct OptimizeQuery {
+    // Tahan request sampai run selesai dan kirim hasilnya langsung
+    #[serde(default)]
+    wait: bool,
+}
+
+/// Submits a run as a background job and answers 202 with its `job_id`
+/// right away; `?wait=true` keeps the old blocking behaviour
+pub async fn optimize_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Query(query): Query<OptimizeQuery>,
+    Json(req): Json<OptimizationRequest>,
+) -> Result<Response, (StatusCode, String)> {
+    let verbose = req.verbose_output.then(|| state.settings.get().locale);
+    let tenant_state = state.tenants.get(&tenant);
+    if !query.wait {
+        // Config yang tidak valid ditolak sebelum job dibuat
+        resolve_config(&req, tenant_state.defaults().config)?;
+        check_sum_ruangan(req.sum_ruangan)?;
+        if let Some(parameters) = &req.parameters {
+            check_retry_perturbation(parameters)?;
+        }
+    }
+
+    // Slot job dipesan saat submit, jadi job yang masih antre ikut dihitung
+    let active_run = match start_job(&state, &tenant) {
+        Ok(active_run) => active_run,
+        Err(busy) => return Ok(busy_response(busy)),
+    };
+    if query.wait {
+        let result = run_optimize_job(&state, &tenant, active_run, req).await?;
+        return Ok(optimize_response(&result, verbose));
+    }
+
+    let job_id = active_run.job_id();
+    tenant_state.tracker.submit(job_id, verbose);
+    tokio::spawn(async move {
+        let outcome = run_optimize_job(&state, &tenant, active_run, req).await;
+        tenant_state.tracker.finish(job_id, outcome);
+    });
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(json!({
+            "success": true,
+            "job_id": job_id,
+            "status_url": format!("/jobs/{}/status", job_id),
+            "result_url": format!("/jobs/{}/result", job_id),
+        })),
+    )
+        .into_response())
+}
+
+/// Reserves a job id and a run slot for it (`TenantRegistry::reserve_run`)
+fn start_job(state: &AppState, tenant: &Tenant) -> Result<ActiveRun, Busy> {
+    let job_id = state.tenants.get(tenant).jobs.reserve_id();
+    state.tenants.reserve_run(tenant, job_id)
+}
+
+/// 429/503 that lists the tenant's active jobs, so a frontend can attach to
+/// one of them instead of resubmitting
+fn busy_response(busy: Busy) -> Response {
+    let Busy { status, queue_length, error, active_jobs } = busy;
+
+    let mut response = (
+        status,
+        Json(json!({
+            "success": false,
+            "error": error,
+            "retry_after": BUSY_RETRY_AFTER_SECS,
+            "queue_length": queue_length,
+            "active_jobs": active_jobs,
+        })),
+    )
+        .into_response();
+    response.headers_mut().insert(header::RETRY_AFTER, BUSY_RETRY_AFTER_SECS.into());
+    response
+}
+
+fn optimize_response(result: &OptimizationResult, verbose: Option<Locale>) -> Response {
+    let mut response = match verbose {
+        Some(locale) => Json(VerboseResult {
+            summary: &result.summary,
+            schedule: result.schedule.iter().map(|course| locale.verbose(course)).collect(),
+        })
+        .into_response(),
+        None => Json(result).into_response(),
+    };
+    response.headers_mut().insert(
+        "content-type",
+        "application/json".parse().unwrap()
+    );
+
+    response
+}
+
+/// State of a job submitted to /optimize, with its latest progress while it runs
+pub async fn job_status_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(id): Path<u64>,
+) -> Result<Response, (StatusCode, String)> {
+    let tenant_state = state.tenants.get(&tenant);
+    let status = tenant_state
+        .tracker
+        .status(id)
+        .ok_or((StatusCode::NOT_FOUND, format!("job {} not found", id)))?;
+    let progress = match status {
+        JobStatus::Running => tenant_state.job_progress.read().unwrap().get(&id).cloned(),
+        _ => None,
+    };
+    let times = tenant_state.tracker.times(id);
+
+    Ok(Json(json!({ "job_id": id, "status": status, "times": times, "progress": progress })).into_response())
+}
+
+/// Result of a finished job, in the same shape /optimize?wait=true returns;
+/// 202 with the status while it is still running
+pub async fn job_result_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(id): Path<u64>,
+) -> Result<Response, (StatusCode, String)> {
+    let (status, result, verbose) = state
+        .tenants
+        .get(&tenant)
+        .tracker
+        .outcome(id)
+        .ok_or((StatusCode::NOT_FOUND, format!("job {} not found", id)))?;
+
+    match (status, result) {
+        (_, Some(result)) => Ok(optimize_response(&result, verbose)),
+        (JobStatus::Failed { code, error }, _) => {
+            Err((StatusCode::from_u16(code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR), error))
+        }
+        (status, None) => Ok((StatusCode::ACCEPTED, Json(json!({ "job_id": id, "status": status }))).into_response()),
+    }
+}
+
+/// Cancels a submitted job; like /stop it still finishes with the best
+/// schedule found so far
+pub async fn job_cancel_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(id): Path<u64>,
+) -> Result<Response, (StatusCode, String)> {
+    let tenant_state = state.tenants.get(&tenant);
+    if let Some(run) = tenant_state.running.read().unwrap().get(&id) {
+        run.cancel.cancel();
+        return Ok(Json(json!({ "success": true, "job_id": id })).into_response());
+    }
+
+    match tenant_state.tracker.status(id) {
+        Some(JobStatus::Running) => Err((StatusCode::CONFLICT, format!("Job {} belum mulai berjalan", id))),
+        Some(_) => Err((StatusCode::CONFLICT, format!("Job {} sudah selesai", id))),
+        None => Err((StatusCode::NOT_FOUND, format!("job {} not found", id))),
+    }
+}
+
+/// First NDJSON line of /optimize/stream
+#[derive(serde::Serialize)]
+struct StreamHeader<'a> {
+    #[serde(flatten)]
+    summary: &'a RunSummary,
+    schedule_rows: usize,
+}
+
+/// Same run as /optimize, answered as NDJSON: one summary line (everything
+/// except `schedule`, plus `schedule_rows`) followed by one line per course,
+/// so a huge schedule is never held as a single JSON value
+pub async fn optimize_stream_handler(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Json(req): Json<OptimizationRequest>,
+) -> Result<Response, (StatusCode, String)> {
+    let active_run = match start_job(&state, &tenant) {
+        Ok(active_run) => active_run,
+        Err(busy) => return Ok(busy_response(busy)),
+    };
+
+    let verbose = req.verbose_output.then(|| state.settings.get().locale);
+    let OptimizationResult { summary, schedule } = run_optimize_job(&state, &tenant, active_run, req).await?;
+    let header = ndjson_line(&StreamHeader { summary: &summary, schedule_rows: schedule.len() });
+
+    let lines = std::iter::once(header)
+        .chain(schedule.into_iter().map(move |course| match verbose {
+            Some(locale) => ndjson_line(&locale.verbose(&course)),
+            None => ndjson_line(&course),
+        }))
+        .map(Ok::<_, std::convert::Infallible>);
+
+    Response::builder()
+        .header(header::CONTENT_TYPE, "application/x-ndjson")
+        .body(Body::from_stream(futures::stream::iter(lines)))
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+fn ndjson_line<T: serde::Serialize>(value: &T) -> Vec<u8> {
+    // Serialisasi struct biasa tidak pernah gagal
+    let mut line = serde_json::to_vec(value).unwrap_or_default();
+    line.push(b'\n');
+    line
+}
+
+/// Runs the optimizer for a request and stores the job, for scheduled runs;
+/// job limits apply as they do to HTTP submissions
+pub async fn run_optimize(
+    state: &AppState,
+    tenant: &Tenant,
+    req: OptimizationRequest,
+) -> Result<OptimizationResult, (StatusCode, String)> {
+    let job_id = state.tenants.get(tenant).jobs.reserve_id();
+    let active_run = state
+        .tenants
+        .reserve_run(tenant, job_id)
+        .map_err(|busy| (busy.status, busy.error.to_string()))?;
+    run_optimize_job(state, tenant, active_run, req).await
+}
+
+/// Request config (or the tenant default) with the request's mode,
+/// per_day_sks_rule and kelas_waktu_policy overrides; 422 when it is unusable
+fn resolve_config(
+    request: &OptimizationRequest,
+    defaults: ConstraintConfig,
+) -> Result<ConstraintConfig, (StatusCode, String)> {
+    let mut config = request.config.clone().unwrap_or(defaults);
+    if let Some(mode) = request.mode {
+        config.mode = mode;
+    }
+    if let Some(rule) = request.per_day_sks_rule {
+        config.per_day_sks_rule = rule;
+    }
+    if let Some(policy) = request.kelas_waktu_policy.clone() {
+        config.kelas_waktu_policy = policy;
+    }
+    config.validate().map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e))?;
+    Ok(config)
+}
+
+/// 422 for a deprecated `sum_ruangan` above `MAX_SUM_RUANGAN`; it expands
+/// into one room per number
+fn check_sum_ruangan(sum_ruangan: Option<u32>) -> Result<(), (StatusCode, String)> {
+    match sum_ruangan {
+        Some(n) if n > MAX_SUM_RUANGAN => Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!("sum_ruangan {} exceeds {}; send rooms instead", n, MAX_SUM_RUANGAN),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// 422 for a `retry_perturbation` that isn't a finite amplitude in (0, 1]
+fn check_retry_perturbation(parameters: &PsoParameters) -> Result<(), (StatusCode, String)> {
+    match parameters.retry_perturbation {
+        Some(amplitude) if !(amplitude.is_finite() && amplitude > 0.0 && amplitude <= 1.0) => Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!("retry_perturbation {} must be in (0, 1]", amplitude),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// 413 when the estimated swarm memory is over `max_memory_bytes`, with the
+/// largest swarm size that would fit
+fn check_memory(state: &AppState, courses: usize, parameters: &PsoParameters) -> Result<(), (StatusCode, String)> {
+    let Some(max) = state.settings.get().limits.max_memory_bytes else {
+        return Ok(());
+    };
+    let memory = |swarm_size| {
+        let parameters = PsoParameters { swarm_size, ..parameters.clone() };
+        state.calibration.estimate(courses, &parameters).estimated_memory_bytes
+    };
+    let needed = memory(parameters.swarm_size);
+    if needed > max {
+        let mut fits = parameters.swarm_size / 2;
+        while fits > 0 && memory(fits) > max {
+            fits /= 2;
+        }
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!(
+                "estimated swarm memory {} MiB exceeds the {} MiB limit; use swarm_size {} or less, or split the courses",
+                needed >> 20,
+                max >> 20,
+                fits
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Appends new events to the job's log; a failed write stops logging the job
+fn sync_run_log(log: &mut Option<RunLog>, job_id: u64, events: &[RunEvent]) {
+    if let Some(Err(e)) = log.as_mut().map(|log| log.sync(events)) {
+        error!("Gagal menulis log run {}: {}", job_id, e);
+        *log = None;
+    }
+}
+
+/// Runs a job in the slot the caller reserved; the slot is freed when the
+/// run ends. A panic inside the run becomes a 500 for this job instead of
+/// unwinding the task that owns it, so background jobs still reach `Failed`.
+async fn run_optimize_job(
+    state: &AppState,
+    tenant: &Tenant,
+    active_run: ActiveRun,
+    req: OptimizationRequest,
+) -> Result<OptimizationResult, (StatusCode, String)> {
+    let job_id = active_run.job_id();
+    let outcome = AssertUnwindSafe(optimize_job(state, tenant, active_run, req)).catch_unwind().await;
+    outcome.unwrap_or_else(|panic| {
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        error!("Job {} panicked: {}", job_id, message);
+        close_job_stream(&state.tenants.get(tenant), job_id);
+        Err((StatusCode::INTERNAL_SERVER_ERROR, format!("job {} failed: {}", job_id, message)))
+    })
+}
+
+// Job yang gagal tidak mengirim event job_finished; stream per job ditutup di sini
+fn close_job_stream(tenant_state: &TenantState, job_id: u64) {
+    let last = tenant_state.job_progress.write().unwrap().remove(&job_id);
+    if let Some(mut progress) = last {
+        progress.is_finished = true;
+        progress.job_finished = true;
+        progress.improved = false;
+        let _ = tenant_state.status_tx.send(progress);
+    }
+}
+
+async fn optimize_job(
+    state: &AppState,
+    tenant: &Tenant,
+    mut active_run: ActiveRun,
+    req: OptimizationRequest,
+) -> Result<OptimizationResult, (StatusCode, String)> {
+    let job_id = active_run.job_id();
+    let tenant_state = state.tenants.get(tenant);
+    tenant_state.tracker.start(job_id);
+    let started_at = chrono::Utc::now();
+    let started_ms = started_at.timestamp_millis();
+    let submitted_at = tenant_state.tracker.times(job_id).map_or(started_at, |t| t.submitted_at);
+    let defaults = tenant_state.defaults();
+    let courses = req.courses.clone();
+    let limits = state.settings.get().limits;
+    if courses.is_empty() {
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, "courses must contain at least one course".to_string()));
+    }
+    if let Some(max) = limits.max_courses.filter(|&max| courses.len() > max) {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!("{} courses exceed the limit of {}", courses.len(), max),
+        ));
+    }
+    let mut parameters = req
+        .parameters
+        .clone()
+        .or_else(|| req.preset.map(Preset::parameters))
+        .unwrap_or(defaults.parameters);
+    if let Some(max) = limits.max_swarm_size {
+        parameters.swarm_size = parameters.swarm_size.min(max);
+    }
+    if let Some(max) = limits.max_iterations {
+        parameters.max_iterations = parameters.max_iterations.min(max);
+    }
+    check_memory(state, courses.len(), &parameters)?;
+    check_sum_ruangan(req.sum_ruangan)?;
+    check_retry_perturbation(&parameters)?;
+    let require_feasible = parameters.require_feasible;
+    let per_prodi_report = req.per_prodi_report;
+    // Disimpan di request agar run bisa diulang persis
+    let seed = *parameters.seed.get_or_insert_with(rand::random);
+    let config = resolve_config(&req, defaults.config)?;
+    let num_runs = req.num_runs.unwrap_or(1).clamp(1, MAX_NUM_RUNS);
+
+    let request = OptimizationRequest {
+        parameters: Some(parameters.clone()),
+        config: Some(config.clone()),
+        mode: None,
+        per_day_sks_rule: None,
+        kelas_waktu_policy: None,
+        ..req
+    };
+    let mut events = vec![RunEvent::Started { request: Box::new(request.clone()), started_at: Some(started_at) }];
+    // Log ditulis sambil job berjalan, jadi run yang terputus tetap tercatat
+    let mut run_log = state.run_log_dir.as_ref().and_then(|dir| {
+        RunLog::create(dir, &tenant.0, job_id, started_at)
+            .map_err(|e| error!("Gagal membuat log run {}: {}", job_id, e))
+            .ok()
+    });
+    sync_run_log(&mut run_log, job_id, &events);
+
+    let status_tx = tenant_state.status_tx.clone();
+
+    // Pool sendiri per job agar run fakultas besar tidak menghabiskan thread run lain
+    let pool = state.settings.get().job_threads.and_then(|threads| {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads.max(1))
+            .thread_name(move |i| format!("job-{}-{}", job_id, i))
+            .build()
+            .map_err(|e| error!("Gagal membuat thread pool job {}: {}", job_id, e))
+            .ok()
+            .map(Arc::new)
+    });
+
+    let preference_warnings = ScheduleChecker::preference_warnings(
+        &courses,
+        &request.time_preferences,
+        &request.lecturers,
+        config.missing_preferences,
+    );
+    let checker = ScheduleChecker::for_request(&request, config.clone());
+
+    // Run lama dengan kumpulan mata kuliah yang hampir sama dipakai sebagai titik awal
+    let warm_start = if request.warm_start_from_history {
+        tenant_state.jobs.most_similar(&courses, WARM_START_MIN_SIMILARITY)
+    } else {
+        None
+    };
+    let mut warm_rng = StdRng::seed_from_u64(seed);
+    // Jadwal berjalan dari pemanggil didahulukan; ujian tidak memakai urutan hari/jam mingguan
+    let current = (!request.initial_schedule.is_empty() && config.mode == ScheduleMode::Weekly)
+        .then(|| ScheduleBuilder::new(&courses, &config).encode(&request.initial_schedule, &mut warm_rng));
+    let initial_positions: Vec<Vec<f32>> = current
+        .into_iter()
+        .chain(warm_start.iter().map(|(_, job, _)| {
+            PSO::transfer_position(&job.request.courses, &job.best_position, &courses, &mut warm_rng)
+        }))
+        .collect();
+
+    let mut best_overall_result: Option<FinalSchedule> = None;
+    let mut best_overall_position = Vec::new();
+    let mut best_run = 0;
+    let mut termination_reason = TerminationReason::default();
+    let mut extension = None;
+    let mut best_overall_fitness = f32::INFINITY;
+    let mut all_best_fitness = Vec::with_capacity(num_runs);
+    let mut run_schedules = Vec::with_capacity(num_runs);
+
+    // Run ulang memakai seed turunan agar tetap bisa diulang persis
+    let max_retries = parameters.conflict_retries.unwrap_or(0).min(MAX_CONFLICT_RETRIES);
+    let mut retry_rng = StdRng::seed_from_u64(seed.wrapping_add(1));
+    let mut run_positions = initial_positions.clone();
+    let mut total_runs = num_runs;
+    let mut i = 0;
+    while i < total_runs {
+        let mut pso = PSO::new(
+            courses.clone(),
+            checker.clone(),
+            PsoParameters { seed: Some(seed.wrapping_add(i as u64)), ..parameters.clone() },
+        );
+        pso.status_tx = Some(status_tx.clone());
+        pso.cancel = Some(active_run.cancel.clone());
+        pso.initial_positions = run_positions.clone();
+        pso.algorithm = request.algorithm;
+        pso.job_id = Some(job_id);
+        pso.pool = pool.clone();
+        pso.params_rx = active_run.params_rx.take();
+
+        let run = pso.run(Some((i, total_runs)), &mut all_best_fitness).await;
+        let (best_position, fitness) = (run.best_position, run.fitness);
+        active_run.params_rx = run.params_rx;
+
+        events.extend(run.improvements.iter().map(|&(iteration, best_fitness)| RunEvent::Improved {
+            run: i,
+            iteration,
+            best_fitness,
+        }));
+        events.extend(run.params_changes.iter().map(|(iteration, params)| RunEvent::ParamsChanged {
+            run: i,
+            iteration: *iteration,
+            params: params.clone(),
+        }));
+        if !run.diagnostics.is_empty() {
+            events.push(RunEvent::Diagnostics { run: i, iterations: run.diagnostics });
+        }
+        if let Some(iteration) = run.stopped_at {
+            events.push(RunEvent::Stopped { run: i, iteration });
+        }
+        events.push(RunEvent::RunFinished {
+            run: i,
+            fitness,
+            termination_reason: run.termination,
+            best_position: best_position.clone(),
+        });
+        sync_run_log(&mut run_log, job_id, &events);
+
+        // Jadwal tiap run disimpan untuk menandai kuliah yang rawan berpindah
+        let finalized = finalize_schedule(&request, &checker, &best_position);
+        run_schedules.push(finalized.schedule.clone());
+        if fitness < best_overall_fitness {
+            best_run = i;
+            termination_reason = run.termination;
+            extension = run.extension;
+            best_overall_fitness = fitness;
+            best_overall_result = Some(finalized);
+            best_overall_position = best_position;
+        }
+
+        let hard_conflicts_left = best_overall_result
+            .as_ref()
+            .is_some_and(|result| checker.evaluate_split(&result.schedule).0 > 0);
+        if run.termination == TerminationReason::UserStop {
+            break;
+        }
+        if i + 1 == total_runs
+            && total_runs < num_runs + max_retries
+            && hard_conflicts_left
+            && run.termination != TerminationReason::UserStop
+        {
+            total_runs += 1;
+            let amplitude = parameters.retry_perturbation.unwrap_or(DEFAULT_RETRY_PERTURBATION);
+            run_positions = vec![PSO::perturb_position(&best_overall_position, amplitude, &mut retry_rng)];
+            println!("Hard conflicts remain after run {}, retrying from a perturbed best", i);
+        }
+        i += 1;
+    }
+
+    drop(active_run);
+
+    // Kosong bila tidak ada run sama sekali
+    let final_schedule = best_overall_result.unwrap_or_default();
+    let other_runs: Vec<&[_]> = run_schedules
+        .iter()
+        .enumerate()
+        .filter(|&(run, _)| run != best_run)
+        .map(|(_, schedule)| schedule.as_slice())
+        .collect();
+    let fragile_courses = FragileCourse::find(&final_schedule.schedule, &other_runs);
+    // Posisi f64 dibulatkan ke f32 sebelum didekode, jadi fitness dihitung ulang
+    if final_schedule.repair.is_some() || parameters.precision != Precision::F32 {
+        best_overall_fitness = checker.evaluate(&final_schedule.schedule);
+    }
+    let conflicts = checker.evaluate_messages(&final_schedule.schedule);
+    let infeasible = !conflicts.0.is_empty() || !conflicts.2.is_empty() || !final_schedule.unassigned_rooms.is_empty();
+    let relaxations = if infeasible {
+        ScheduleRepairer::new(checker.clone()).suggest_relaxations(&final_schedule.schedule, &final_schedule.unassigned_rooms)
+    } else {
+        Vec::new()
+    };
+
+    let schedule = final_schedule.schedule.clone();
+    let integrity = Integrity::compute(&schedule, state.signing_key.as_deref());
+    let times = JobTimes { submitted_at, started_at: Some(started_at), finished_at: Some(chrono::Utc::now()) };
+    events.push(RunEvent::Selected { run: best_run, finished_at: times.finished_at });
+    sync_run_log(&mut run_log, job_id, &events);
+    // Penanda selesai tingkat job: stream per job ditutup di sini, bukan di akhir tiap run
+    let finished_at = times.finished_at.unwrap_or(started_at);
+    let _ = status_tx.send(OptimizationProgress {
+        job_id: Some(job_id),
+        iteration: 0,
+        elapsed_time: (finished_at - started_at).to_std().unwrap_or_default(),
+        timestamp: finished_at,
+        started_at: Some(started_at),
+        best_fitness: best_overall_fitness,
+        quality_score: checker.config.quality.score(best_overall_fitness),
+        all_best_fitness: Some(all_best_fitness.clone()),
+        current_run: Some(best_run),
+        total_runs: Some(total_runs),
+        is_finished: true,
+        job_finished: true,
+        improved: false,
+        conflict_counts: checker.count_conflicts(&final_schedule.schedule),
+        schedule: None,
+        fitness_distribution: None,
+        seed: Some(seed),
+        stats: None,
+        truncated: false,
+        truncated_fields: Vec::new(),
+    });
+    tenant_state.jobs.insert(job_id, JobRecord {
+        request,
+        best_position: best_overall_position,
+        schedule,
+        integrity: integrity.clone(),
+        events,
+        times,
+    });
+    let artifact = state.artifact_dir.as_ref().and_then(|dir| {
+        artifacts::store(dir, &tenant.0, job_id, &final_schedule.schedule, &integrity)
+            .map_err(|e| error!("Gagal menyimpan artefak jadwal {}: {}", job_id, e))
+            .ok()
+    });
+
+    let feasible = conflicts.0.is_empty() && conflicts.2.is_empty();
+    let FinalSchedule { schedule, unplaced, repair, unassigned_rooms, rooms_kept } = final_schedule;
+    let (repaired, unresolved) = repair.map(|r| (r.repaired, r.unresolved)).unzip();
+    let per_prodi = per_prodi_report.then(|| ProdiReport::split(&schedule, &conflicts));
+
+    let result = OptimizationResult {
+        summary: RunSummary {
+            success: true,
+            job_id,
+            fitness: best_overall_fitness,
+            quality: checker.config.quality.quality(best_overall_fitness),
+            run_statistics: RunStatistics::of(&all_best_fitness),
+            all_best_fitness,
+            retries: total_runs - num_runs,
+            seed,
+            times,
+            integrity,
+            artifact,
+            termination_reason,
+            converged: termination_reason.converged(),
+            partial: termination_reason.partial(),
+            stats: RunStats { conflict_counts: checker.count_conflicts(&schedule) },
+            unplaced,
+            feasibility: require_feasible.then_some(Feasibility { extension, repaired, unresolved, feasible }),
+            unassigned_rooms,
+            rooms_kept,
+            preference_warnings,
+            relaxations,
+            warm_start: warm_start.map(|(job_id, _, similarity)| WarmStart { job_id, similarity }),
+            fragile_courses,
+            message: conflicts,
+            per_prodi,
+        },
+        schedule,
+    };
+    if let (Some(config), Some(job)) = (state.settings.get().tracking, tenant_state.jobs.get(job_id)) {
+        tracking::spawn(config, &tenant.0, started_ms, job, &result);
+    }
+    Ok(result)
+}
+// Jalur gagal run lewat fault injection (fitur chaos)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::{Limits, Settings};
+    use serde_json::Value;
+
+    fn state(limits: Limits) -> AppState {
+        let settings = SettingsHandle::fixed(Settings { limits, ..Settings::default() });
+        AppState { tenants: TenantRegistry::new(settings.clone()), settings, ..AppState::default() }
+    }
+
+    fn tenant() -> Tenant {
+        Tenant("test".to_string())
+    }
+
+    // Tiga mata kuliah kecil; `parameters` ditimpa oleh field dari `extra`
+    fn request(extra: Value) -> OptimizationRequest {
+        let mut parameters = json!({
+            "swarm_size": 4,
+            "max_iterations": 5,
+            "cognitive_weight": 1.5,
+            "social_weight": 1.5,
+            "inertia_weight": 0.7,
+            "seed": 1
+        });
+        parameters.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+        let courses: Vec<Value> = (1..=3)
+            .map(|id| json!({
+                "id_jadwal": id, "id_matkul": id, "id_dosen": id, "id_waktu": 1,
+                "id_kelas": 1, "semester": 1, "sks": 2, "prodi": 1
+            }))
+            .collect();
+        serde_json::from_value(json!({ "courses": courses, "time_preferences": [], "parameters": parameters })).unwrap()
+    }
+
+    async fn submit(state: &AppState, req: OptimizationRequest) -> (StatusCode, Value) {
+        let response = optimize_handler(State(state.clone()), tenant(), Query(OptimizeQuery::default()), Json(req))
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[cfg(feature = "chaos")]
+    async fn wait_done(state: &AppState, job_id: u64) -> JobStatus {
+        let tracker = state.tenants.get(&tenant()).tracker;
+        for _ in 0..500 {
+            match tracker.status(job_id) {
+                Some(JobStatus::Running) | None => tokio::time::sleep(Duration::from_millis(10)).await,
+                Some(status) => return status,
+            }
+        }
+        panic!("job {} still running", job_id);
+    }
+
+    #[tokio::test]
+    async fn tenant_at_its_job_limit_gets_429_with_its_active_jobs() {
+        let state = state(Limits { max_concurrent_jobs: Some(1), ..Limits::default() });
+        let held = state.tenants.reserve_run(&tenant(), 7).unwrap();
+
+        let (status, body) = submit(&state, request(json!({}))).await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(body["active_jobs"], json!([7]));
+        assert_eq!(body["queue_length"], 1);
+
+        drop(held);
+        let (status, _) = submit(&state, request(json!({}))).await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+    }
+
+    #[tokio::test]
+    async fn full_engine_gets_503_even_for_an_idle_tenant() {
+        let state = state(Limits { max_total_jobs: Some(1), ..Limits::default() });
+        let _held = state.tenants.reserve_run(&Tenant("other".to_string()), 1).unwrap();
+
+        let (status, body) = submit(&state, request(json!({}))).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["active_jobs"], json!([]));
+        assert_eq!(body["queue_length"], 1);
+    }
+
+    #[tokio::test]
+    async fn job_does_not_count_against_the_limit_it_is_admitted_under() {
+        let state = state(Limits { max_concurrent_jobs: Some(1), max_total_jobs: Some(1), ..Limits::default() });
+        let tenant_state = state.tenants.get(&tenant());
+        let job_id = tenant_state.jobs.reserve_id();
+        tenant_state.tracker.submit(job_id, None);
+
+        // Job lain tetap ditolak, job itu sendiri boleh jalan
+        let busy = state.tenants.reserve_run(&tenant(), job_id + 1).err().unwrap();
+        assert_eq!(busy.status, StatusCode::TOO_MANY_REQUESTS);
+        let active_run = state.tenants.reserve_run(&tenant(), job_id).unwrap();
+        assert!(run_optimize_job(&state, &tenant(), active_run, request(json!({}))).await.is_ok());
+        assert!(tenant_state.running.read().unwrap().is_empty());
+    }
+
+    #[test]
+    fn retry_perturbation_must_be_a_finite_amplitude_up_to_one() {
+        for amplitude in [f32::NAN, f32::INFINITY, 0.0, -0.1, 1.5] {
+            let parameters = PsoParameters { retry_perturbation: Some(amplitude), ..request(json!({})).parameters.unwrap() };
+            assert!(check_retry_perturbation(&parameters).is_err(), "{} accepted", amplitude);
+        }
+        for amplitude in [None, Some(0.05), Some(1.0)] {
+            let parameters = PsoParameters { retry_perturbation: amplitude, ..request(json!({})).parameters.unwrap() };
+            assert!(check_retry_perturbation(&parameters).is_ok());
+        }
+    }
+
+    #[cfg(feature = "chaos")]
+    #[tokio::test]
+    async fn panicking_run_fails_the_job_and_frees_its_slot() {
+        let state = state(Limits { max_concurrent_jobs: Some(1), ..Limits::default() });
+        let (status, body) = submit(&state, request(json!({ "faults": { "panic_at": 0 } }))).await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        let job_id = body["job_id"].as_u64().unwrap();
+
+        match wait_done(&state, job_id).await {
+            JobStatus::Failed { code, error } => {
+                assert_eq!(code, 500);
+                assert!(error.contains("injected panic"), "{}", error);
+            }
+            other => panic!("expected a failed job, got {:?}", other),
+        }
+        let tenant_state = state.tenants.get(&tenant());
+        assert!(tenant_state.active_jobs().is_empty());
+        assert!(!tenant_state.job_progress.read().unwrap().contains_key(&job_id));
+
+        // Slot yang dipegang job gagal sudah bebas
+        let (status, body) = submit(&state, request(json!({}))).await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(wait_done(&state, body["job_id"].as_u64().unwrap()).await, JobStatus::Finished);
+    }
+
+    #[cfg(feature = "chaos")]
+    #[tokio::test]
+    async fn slow_run_stays_running_and_holds_its_slot_until_it_finishes() {
+        let state = state(Limits { max_concurrent_jobs: Some(1), ..Limits::default() });
+        let (_, body) = submit(&state, request(json!({ "faults": { "slow_iteration_ms": 40 } }))).await;
+        let job_id = body["job_id"].as_u64().unwrap();
+
+        let tenant_state = state.tenants.get(&tenant());
+        assert_eq!(tenant_state.tracker.status(job_id), Some(JobStatus::Running));
+        assert_eq!(tenant_state.active_jobs(), [job_id]);
+        let (status, busy) = submit(&state, request(json!({}))).await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(busy["active_jobs"], json!([job_id]));
+
+        assert_eq!(wait_done(&state, job_id).await, JobStatus::Finished);
+        assert!(tenant_state.active_jobs().is_empty());
+        assert!(tenant_state.running.read().unwrap().is_empty());
+    }
+
+    #[cfg(feature = "chaos")]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn parallel_submits_admit_one_job_against_a_limit_of_one() {
+        let state = state(Limits { max_concurrent_jobs: Some(1), ..Limits::default() });
+        let submits: Vec<_> = (0..16)
+            .map(|_| {
+                let state = state.clone();
+                tokio::spawn(async move { submit(&state, request(json!({ "faults": { "slow_iteration_ms": 40 } }))).await })
+            })
+            .collect();
+        let mut accepted = Vec::new();
+        for handle in submits {
+            let (status, body) = handle.await.unwrap();
+            match status {
+                StatusCode::ACCEPTED => accepted.push(body["job_id"].as_u64().unwrap()),
+                status => assert_eq!(status, StatusCode::TOO_MANY_REQUESTS),
+            }
+        }
+
+        assert_eq!(accepted.len(), 1, "accepted {:?}", accepted);
+        assert_eq!(wait_done(&state, accepted[0]).await, JobStatus::Finished);
+    }
+
+    #[cfg(feature = "chaos")]
+    #[tokio::test]
+    async fn closed_channels_do_not_fail_the_job() {
+        let state = state(Limits::default());
+        let (_, body) = submit(&state, request(json!({ "faults": { "close_channels_at": 0 } }))).await;
+        let job_id = body["job_id"].as_u64().unwrap();
+
+        assert_eq!(wait_done(&state, job_id).await, JobStatus::Finished);
+        let tenant_state = state.tenants.get(&tenant());
+        assert!(tenant_state.running.read().unwrap().is_empty());
+        assert!(tenant_state.jobs.get(job_id).is_some());
+    }
+}
@@ -0,0 +1,100 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::Arc,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    algorithms::{checker::ConflictKind, models::ScheduleChecker},
+    jobs::JobRecord,
+};
+
+/// What keeps going wrong for a hotspot's entity
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum HotspotKind {
+    /// Ruangan clashing with an external booking or too small for its class
+    RoomOverbooked,
+    /// Dosen scheduled twice at once
+    LecturerClash,
+    /// Kelas or student group scheduled twice at once
+    KelasClash,
+    /// Dosen placed outside their time preferences
+    PreferenceViolated,
+    /// Dosen missing a soft objective (campus days, split days)
+    SoftViolated,
+}
+
+#[derive(Serialize)]
+pub struct Hotspot {
+    pub kind: HotspotKind,
+    /// id_dosen, id_kelas or ruangan depending on `kind`
+    pub id: u32,
+    /// Stored runs in which it happened at least once
+    pub runs: usize,
+    /// `runs` as a share of all stored runs, 0–100
+    pub share_pct: f32,
+    /// Violations summed over those runs
+    pub occurrences: usize,
+}
+
+#[derive(Serialize)]
+pub struct HotspotReport {
+    pub runs_analyzed: usize,
+    pub hotspots: Vec<Hotspot>,
+}
+
+/// Re-checks every stored schedule against its own request and counts, per
+/// entity, how many runs had the violation. Hotspots below `min_share_pct`
+/// are dropped; the rest are sorted by share, most chronic first.
+pub fn build(jobs: &[Arc<JobRecord>], min_share_pct: f32) -> HotspotReport {
+    let mut counts: BTreeMap<(HotspotKind, u32), (usize, usize)> = BTreeMap::new();
+
+    for job in jobs {
+        let request = &job.request;
+        let checker = ScheduleChecker::for_request(request, request.config.clone().unwrap_or_default());
+        let by_jadwal: HashMap<u32, _> = job.schedule.iter().map(|c| (c.id_jadwal, c)).collect();
+        let (conflicts, preferences, bookings, soft) = checker.evaluate_messages(&job.schedule);
+
+        let mut found: Vec<(HotspotKind, u32)> = Vec::new();
+        for conflict in &conflicts {
+            let Some(course) = by_jadwal.get(&conflict.jadwal_a) else {
+                continue;
+            };
+            match conflict.kind {
+                ConflictKind::Lecturer => found.push((HotspotKind::LecturerClash, course.id_dosen)),
+                ConflictKind::Kelas | ConflictKind::StudentGroup => found.push((HotspotKind::KelasClash, course.id_kelas)),
+                ConflictKind::RoomCapacity => found.push((HotspotKind::RoomOverbooked, course.ruangan)),
+                ConflictKind::ExamRule => {}
+            }
+        }
+        found.extend(bookings.iter().map(|b| (HotspotKind::RoomOverbooked, b.ruangan)));
+        found.extend(preferences.iter().map(|p| (HotspotKind::PreferenceViolated, p.id_dosen)));
+        found.extend(soft.iter().map(|s| (HotspotKind::SoftViolated, s.id_dosen)));
+
+        let distinct: BTreeSet<(HotspotKind, u32)> = found.iter().copied().collect();
+        for key in found {
+            counts.entry(key).or_default().1 += 1;
+        }
+        for key in distinct {
+            counts.entry(key).or_default().0 += 1;
+        }
+    }
+
+    let total = jobs.len();
+    let mut hotspots: Vec<Hotspot> = counts
+        .into_iter()
+        .map(|((kind, id), (runs, occurrences))| Hotspot {
+            kind,
+            id,
+            runs,
+            share_pct: runs as f32 * 100.0 / total as f32,
+            occurrences,
+        })
+        .filter(|h| h.share_pct >= min_share_pct)
+        .collect();
+    hotspots.sort_by(|a, b| b.runs.cmp(&a.runs).then(b.occurrences.cmp(&a.occurrences)));
+
+    HotspotReport { runs_analyzed: total, hotspots }
+}
@@ -0,0 +1,50 @@
+use std::fmt::Write;
+
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::{algorithms::models::OptimizedCourse, export};
+
+pub const SIGNING_KEY_ENV: &str = "SCHEDULE_SIGNING_KEY";
+
+/// Hash (and optional HMAC) over the canonical CSV form of a schedule:
+/// the export header followed by rows sorted by id_jadwal.
+#[derive(Debug, Clone, Serialize)]
+pub struct Integrity {
+    pub sha256: String,
+    pub hmac_sha256: Option<String>,
+}
+
+impl Integrity {
+    pub fn compute(schedule: &[OptimizedCourse], signing_key: Option<&[u8]>) -> Self {
+        let canonical = Self::canonical_csv(schedule);
+
+        let sha256 = to_hex(&Sha256::digest(canonical.as_bytes()));
+        let hmac_sha256 = signing_key.map(|key| {
+            let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+            mac.update(canonical.as_bytes());
+            to_hex(&mac.finalize().into_bytes())
+        });
+
+        Self { sha256, hmac_sha256 }
+    }
+
+    pub fn canonical_csv(schedule: &[OptimizedCourse]) -> String {
+        let mut sorted: Vec<&OptimizedCourse> = schedule.iter().collect();
+        sorted.sort_by_key(|c| (c.id_jadwal, c.hari, c.jam_mulai));
+
+        let mut buf = String::from(export::CSV_HEADER);
+        for course in sorted {
+            export::write_csv_row(&mut buf, course);
+        }
+        buf
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::with_capacity(bytes.len() * 2), |mut out, b| {
+        let _ = write!(out, "{:02x}", b);
+        out
+    })
+}
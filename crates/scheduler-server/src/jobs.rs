@@ -0,0 +1,212 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock,
+    },
+};
+
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::{
+    algorithms::models::{CourseRequest, OptimizationRequest, OptimizedCourse},
+    integrity::Integrity,
+    locale::Locale,
+    result::OptimizationResult,
+    runlog::RunEvent,
+};
+
+// Hasil lama dibuang agar memori server tidak terus bertambah
+const MAX_STORED_JOBS: usize = 100;
+
+/// Finished run kept around so it can be exported or updated later
+#[derive(Debug)]
+pub struct JobRecord {
+    /// Request with parameters and config already resolved
+    pub request: OptimizationRequest,
+    /// Best particle position, aligned with `request.courses`
+    pub best_position: Vec<f32>,
+    pub schedule: Vec<OptimizedCourse>,
+    pub integrity: Integrity,
+    pub events: Vec<RunEvent>,
+    pub times: JobTimes,
+}
+
+/// Wall-clock moments of a job in UTC, serialized as RFC 3339, to line runs
+/// up with database snapshots and logs
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct JobTimes {
+    pub submitted_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl JobTimes {
+    pub fn submitted_now() -> Self {
+        JobTimes { submitted_at: Utc::now(), started_at: None, finished_at: None }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct JobStore {
+    next_id: Arc<AtomicU64>,
+    jobs: Arc<RwLock<BTreeMap<u64, Arc<JobRecord>>>>,
+}
+
+impl JobStore {
+    /// Hands out a job id up front so progress events of a running job can
+    /// carry it; the record is stored later with `insert`
+    pub fn reserve_id(&self) -> u64 {
+        self.next_id.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    pub fn insert(&self, id: u64, record: JobRecord) {
+        let mut jobs = self.jobs.write().unwrap();
+        jobs.insert(id, Arc::new(record));
+        while jobs.len() > MAX_STORED_JOBS {
+            jobs.pop_first();
+        }
+    }
+
+    /// Replaces a stored job; returns false if it was evicted in the meantime
+    pub fn replace(&self, id: u64, record: JobRecord) -> bool {
+        match self.jobs.write().unwrap().get_mut(&id) {
+            Some(slot) => {
+                *slot = Arc::new(record);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stored job whose course set is most similar (Jaccard over course
+    /// signatures) to `courses`, if it reaches `min_similarity`
+    pub fn most_similar(&self, courses: &[CourseRequest], min_similarity: f32) -> Option<(u64, Arc<JobRecord>, f32)> {
+        let wanted = course_signatures(courses);
+        if wanted.is_empty() {
+            return None;
+        }
+
+        self.jobs
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(id, job)| {
+                let stored = course_signatures(&job.request.courses);
+                let shared = wanted.intersection(&stored).count();
+                let similarity = shared as f32 / wanted.union(&stored).count() as f32;
+                (*id, job.clone(), similarity)
+            })
+            .filter(|(_, _, similarity)| *similarity >= min_similarity)
+            .max_by(|a, b| a.2.total_cmp(&b.2).then(a.0.cmp(&b.0)))
+    }
+
+    pub fn latest_id(&self) -> Option<u64> {
+        self.jobs.read().unwrap().last_key_value().map(|(id, _)| *id)
+    }
+
+    pub fn get(&self, id: u64) -> Option<Arc<JobRecord>> {
+        self.jobs.read().unwrap().get(&id).cloned()
+    }
+
+    /// Every stored job, oldest first
+    pub fn all(&self) -> Vec<Arc<JobRecord>> {
+        self.jobs.read().unwrap().values().cloned().collect()
+    }
+}
+
+/// Lifecycle of a job submitted to /optimize
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum JobStatus {
+    Running,
+    /// Also reached by cancelled runs, which keep their best schedule so far
+    Finished,
+    Failed { code: u16, error: String },
+}
+
+/// Background job and, once it is done, its outcome
+struct TrackedJob {
+    status: JobStatus,
+    result: Option<Arc<OptimizationResult>>,
+    /// Locale for the verbose view when the request set `verbose_output`
+    verbose: Option<Locale>,
+    times: JobTimes,
+}
+
+/// Jobs submitted to /optimize, polled through /jobs/:id/status and
+/// /jobs/:id/result
+#[derive(Clone, Default)]
+pub struct JobTracker {
+    jobs: Arc<RwLock<BTreeMap<u64, TrackedJob>>>,
+}
+
+impl JobTracker {
+    pub fn submit(&self, id: u64, verbose: Option<Locale>) {
+        let mut jobs = self.jobs.write().unwrap();
+        jobs.insert(id, TrackedJob { status: JobStatus::Running, result: None, verbose, times: JobTimes::submitted_now() });
+        // Yang dibuang hanya job yang sudah selesai; job berjalan tetap bisa dipantau
+        while jobs.len() > MAX_STORED_JOBS {
+            let Some(oldest) = jobs.iter().find(|(_, job)| !matches!(job.status, JobStatus::Running)).map(|(id, _)| *id) else {
+                break;
+            };
+            jobs.remove(&oldest);
+        }
+    }
+
+    /// Marks a submitted job as picked up; unknown ids (blocking runs) are ignored
+    pub fn start(&self, id: u64) {
+        if let Some(job) = self.jobs.write().unwrap().get_mut(&id) {
+            job.times.started_at = Some(Utc::now());
+        }
+    }
+
+    pub fn finish(&self, id: u64, outcome: Result<OptimizationResult, (StatusCode, String)>) {
+        if let Some(job) = self.jobs.write().unwrap().get_mut(&id) {
+            job.times.finished_at = Some(Utc::now());
+            match outcome {
+                Ok(result) => {
+                    job.status = JobStatus::Finished;
+                    job.times = result.summary.times;
+                    job.result = Some(Arc::new(result));
+                }
+                Err((code, error)) => job.status = JobStatus::Failed { code: code.as_u16(), error },
+            }
+        }
+    }
+
+    /// Ids of submitted jobs that have not finished yet
+    pub fn running(&self) -> Vec<u64> {
+        let jobs = self.jobs.read().unwrap();
+        jobs.iter().filter(|(_, job)| matches!(job.status, JobStatus::Running)).map(|(id, _)| *id).collect()
+    }
+
+    pub fn status(&self, id: u64) -> Option<JobStatus> {
+        self.jobs.read().unwrap().get(&id).map(|job| job.status.clone())
+    }
+
+    pub fn times(&self, id: u64) -> Option<JobTimes> {
+        self.jobs.read().unwrap().get(&id).map(|job| job.times)
+    }
+
+    /// Status, result once finished and the verbose locale of a job
+    pub fn outcome(&self, id: u64) -> Option<(JobStatus, Option<Arc<OptimizationResult>>, Option<Locale>)> {
+        let jobs = self.jobs.read().unwrap();
+        let job = jobs.get(&id)?;
+        Some((job.status.clone(), job.result.clone(), job.verbose))
+    }
+}
+
+fn course_signatures(courses: &[CourseRequest]) -> HashSet<u64> {
+    courses
+        .iter()
+        .map(|c| {
+            let mut hasher = DefaultHasher::new();
+            (c.id_jadwal, c.id_matkul, c.id_dosen, c.id_waktu, c.id_kelas, c.semester, c.sks, c.prodi).hash(&mut hasher);
+            hasher.finish()
+        })
+        .collect()
+}
@@ -0,0 +1,140 @@
+mod handlers;
+mod jobs;
+mod anonymize;
+mod export;
+mod integrity;
+mod tenant;
+mod sessions;
+mod estimate;
+mod runlog;
+mod settings;
+mod scheduler;
+mod ws;
+mod result;
+mod tracking;
+mod roster;
+mod artifacts;
+mod hotspots;
+mod gates;
+mod payload;
+
+// Mesin penjadwalan ada di scheduler-core; modul server tetap memakai crate::algorithms
+use scheduler_core::{algorithms, locale};
+
+use axum::{
+    http::{header, HeaderName, Method, HeaderValue},
+    routing::{delete, get, patch, post, put},
+    Router,
+};
+use std::time::Duration;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use handlers::{AppState, anonymize_handler, anonymized_job_handler, artifact_handler, capacity_handler, create_schedule_handler, delete_schedule_handler, estimate_handler, event_sizes_handler, export_csv_handler, export_ical_handler, get_defaults_handler, hotspots_handler, job_cancel_handler, job_diagnostics_handler, job_events_handler, job_result_handler, job_status_handler, job_summary_handler, list_schedules_handler, live_params_handler, open_session_handler, optimize_handler, optimize_stream_handler, preference_impact_handler, presets_handler, progress_ws_handler, publish_handler, put_defaults_handler, reload_settings_handler, replay_handler, roster_handler, rerun_handler, schema_handler, session_chunk_handler, simulate_handler, start_session_handler, status_handler, stop_handler, update_courses_handler};
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    
+    let settings = settings::SettingsHandle::from_env().expect("failed to load service config");
+    let state = AppState {
+        tenants: tenant::TenantRegistry::new(settings.clone()),
+        settings: settings.clone(),
+        signing_key: std::env::var(integrity::SIGNING_KEY_ENV)
+            .ok()
+            .map(|key| key.into_bytes().into()),
+        calibration: estimate::Calibration::measure(),
+        run_log_dir: std::env::var(runlog::RUN_LOG_DIR_ENV)
+            .ok()
+            .map(|dir| std::path::PathBuf::from(dir).into()),
+        artifact_dir: std::env::var(artifacts::ARTIFACT_DIR_ENV)
+            .ok()
+            .map(|dir| std::path::PathBuf::from(dir).into()),
+        scheduler: scheduler::Scheduler::default(),
+    };
+    
+    let cors = CorsLayer::new()
+        .allow_origin(AllowOrigin::predicate({
+            let settings = settings.clone();
+            move |origin: &HeaderValue, _| origin.to_str().is_ok_and(|o| settings.allows_origin(o))
+        }))
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::PATCH, Method::DELETE, Method::OPTIONS])
+        .allow_headers([
+            header::CONTENT_TYPE,
+            header::ACCEPT,
+            HeaderName::from_static(tenant::TENANT_HEADER),
+            HeaderName::from_static(tenant::API_KEY_HEADER),
+            HeaderName::from_static(tenant::ADMIN_KEY_HEADER),
+        ])
+        .expose_headers([
+            header::CONTENT_TYPE,
+            header::RETRY_AFTER,
+            HeaderName::from_static(handlers::SHA256_HEADER),
+            HeaderName::from_static(handlers::HMAC_HEADER),
+        ])
+        .allow_credentials(true)
+        .max_age(Duration::from_secs(3600));
+    
+    let app = Router::new()
+        .route("/optimize", post(optimize_handler))
+        .route("/optimize/stream", post(optimize_stream_handler))
+        .route("/optimize/sessions", post(open_session_handler))
+        .route("/optimize/sessions/:id/chunks", put(session_chunk_handler))
+        .route("/optimize/sessions/:id/start", post(start_session_handler))
+        .route("/status", get(status_handler))
+        .route("/status/sizes", get(event_sizes_handler))
+        .route("/ws/progress", get(progress_ws_handler))
+        .route("/stop", post(stop_handler))
+        .route("/simulate", post(simulate_handler))
+        .route("/simulate/capacity", post(capacity_handler))
+        .route("/estimate", post(estimate_handler))
+        .route("/presets", get(presets_handler))
+        .route("/preferences/impact", post(preference_impact_handler))
+        .route("/schema", get(schema_handler))
+        .route("/anonymize", post(anonymize_handler))
+        .route("/jobs/:id/status", get(job_status_handler))
+        .route("/jobs/:id/result", get(job_result_handler))
+        .route("/jobs/:id/cancel", post(job_cancel_handler))
+        .route("/jobs/:id/params", post(live_params_handler))
+        .route("/jobs/:id/export.csv", get(export_csv_handler))
+        .route("/jobs/:id/export.ics", get(export_ical_handler))
+        .route("/jobs/:id/roster", get(roster_handler))
+        .route("/jobs/:id/courses", patch(update_courses_handler))
+        .route("/jobs/:id/events", get(job_events_handler))
+        .route("/jobs/:id/diagnostics", get(job_diagnostics_handler))
+        .route("/jobs/:id/summary.json", get(job_summary_handler))
+        .route("/jobs/:id/anonymized", get(anonymized_job_handler))
+        .route("/jobs/:id/publish", post(publish_handler))
+        .route("/artifacts/:sha256", get(artifact_handler))
+        .route("/analytics/hotspots", get(hotspots_handler))
+        .route("/replay", post(replay_handler))
+        .route("/history/:id/rerun", post(rerun_handler))
+        .route("/admin/defaults", get(get_defaults_handler).put(put_defaults_handler))
+        .route("/admin/reload", post(reload_settings_handler))
+        .route("/admin/schedules", get(list_schedules_handler).post(create_schedule_handler))
+        .route("/admin/schedules/:id", delete(delete_schedule_handler))
+        .layer(cors)
+        .with_state(state.clone());
+
+    tokio::spawn(scheduler::run_loop(state));
+    
+    #[cfg(unix)]
+    tokio::spawn(reload_on_sighup(settings));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:8080").await.unwrap();
+    println!("Server running on http://127.0.0.1:8080");
+    axum::serve(listener, app).await.unwrap();
+}
+/// SIGHUP reloads the config file without dropping in-flight runs
+#[cfg(unix)]
+async fn reload_on_sighup(settings: settings::SettingsHandle) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let Ok(mut hangup) = signal(SignalKind::hangup()) else {
+        return;
+    };
+    while hangup.recv().await.is_some() {
+        match settings.reload() {
+            Ok(_) => println!("Config reloaded"),
+            Err(e) => log::error!("Config reload failed: {}", e),
+        }
+    }
+}
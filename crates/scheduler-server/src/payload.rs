@@ -0,0 +1,103 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::Serialize;
+
+use crate::algorithms::models::OptimizationProgress;
+
+// Batas bawaan satu event progres bila limits.max_event_bytes kosong
+pub const DEFAULT_MAX_EVENT_BYTES: usize = 256 * 1024;
+
+// Field opsional yang dibuang, terbesar dulu; fungsi bernilai true bila field terisi
+type Elision = (&'static str, fn(&mut OptimizationProgress) -> bool);
+
+/// Size accounting of the progress events a tenant sent to SSE and
+/// WebSocket clients
+#[derive(Debug, Default)]
+pub struct EventSizes {
+    events: AtomicU64,
+    bytes: AtomicU64,
+    largest: AtomicU64,
+    truncated: AtomicU64,
+    dropped: AtomicU64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EventSizeReport {
+    pub events: u64,
+    pub bytes: u64,
+    pub largest_bytes: u64,
+    /// Events sent with bulky fields elided
+    pub truncated: u64,
+    /// Events that could not be serialized even without them
+    pub dropped: u64,
+}
+
+impl EventSizes {
+    fn record(&self, len: usize, truncated: bool) {
+        self.events.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(len as u64, Ordering::Relaxed);
+        self.largest.fetch_max(len as u64, Ordering::Relaxed);
+        if truncated {
+            self.truncated.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn report(&self) -> EventSizeReport {
+        EventSizeReport {
+            events: self.events.load(Ordering::Relaxed),
+            bytes: self.bytes.load(Ordering::Relaxed),
+            largest_bytes: self.largest.load(Ordering::Relaxed),
+            truncated: self.truncated.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Serializes a progress event with `frame` (the event itself or an envelope
+/// around it). When the result is over `max_bytes` or fails, the optional
+/// fields are elided one by one, largest first, and the event is marked
+/// `truncated` with the names under `truncated_fields`. None when even the
+/// bare event cannot be serialized.
+pub fn encode<E>(
+    progress: &OptimizationProgress,
+    max_bytes: usize,
+    sizes: &EventSizes,
+    frame: impl Fn(&OptimizationProgress) -> Result<String, E>,
+) -> Option<String> {
+    if let Some(data) = frame(progress).ok().filter(|data| data.len() <= max_bytes) {
+        sizes.record(data.len(), false);
+        return Some(data);
+    }
+
+    let elisions: [Elision; 4] = [
+        ("schedule", |p| p.schedule.take().is_some()),
+        ("all_best_fitness", |p| p.all_best_fitness.take().is_some()),
+        ("stats", |p| p.stats.take().is_some()),
+        ("fitness_distribution", |p| p.fitness_distribution.take().is_some()),
+    ];
+    let mut trimmed = progress.clone();
+    trimmed.truncated = true;
+    let mut data = None;
+    for (field, elide) in elisions {
+        if !elide(&mut trimmed) {
+            continue;
+        }
+        trimmed.truncated_fields.push(field);
+        data = frame(&trimmed).ok();
+        if data.as_ref().is_some_and(|data| data.len() <= max_bytes) {
+            break;
+        }
+    }
+
+    // Field inti tetap dikirim meski masih di atas batas
+    match data.or_else(|| frame(&trimmed).ok()) {
+        Some(data) => {
+            sizes.record(data.len(), true);
+            Some(data)
+        }
+        None => {
+            sizes.dropped.fetch_add(1, Ordering::Relaxed);
+            None
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+use crate::{
+    algorithms::{
+        checker::{PreferenceWarning, ScheduleMessages},
+        models::{ConflictCounts, OptimizedCourse, QualityScore, RunExtension, TerminationReason},
+        relax::RelaxationSuggestion,
+        repair::UnplacedCourse,
+        soft::SoftKind,
+    },
+    artifacts::ArtifactRef,
+    integrity::Integrity,
+    jobs::JobTimes,
+    locale::VerboseCourse,
+};
+
+/// Body returned by /optimize. Everything except the schedule lives in
+/// `summary`, which the NDJSON variant sends ahead of the rows.
+#[derive(Serialize)]
+pub struct OptimizationResult {
+    #[serde(flatten)]
+    pub summary: RunSummary,
+    pub schedule: Vec<OptimizedCourse>,
+}
+
+/// Body returned by /optimize when the request sets `verbose_output`
+#[derive(Serialize)]
+pub struct VerboseResult<'a> {
+    #[serde(flatten)]
+    pub summary: &'a RunSummary,
+    pub schedule: Vec<VerboseCourse<'a>>,
+}
+
+#[derive(Serialize)]
+pub struct RunSummary {
+    pub success: bool,
+    pub job_id: u64,
+    pub fitness: f32,
+    /// `fitness` on a 0–100 scale, 100 meaning no penalty at all
+    pub quality: QualityScore,
+    pub all_best_fitness: Vec<f32>,
+    /// Spread of the best fitness over the runs of `num_runs` (and retries)
+    pub run_statistics: RunStatistics,
+    /// Follow-up runs started by `conflict_retries` because hard conflicts remained
+    pub retries: usize,
+    pub seed: u64,
+    /// submitted_at, started_at and finished_at in UTC
+    #[serde(flatten)]
+    pub times: JobTimes,
+    pub integrity: Integrity,
+    /// Only set when `ARTIFACT_DIR` is configured
+    pub artifact: Option<ArtifactRef>,
+    pub termination_reason: TerminationReason,
+    pub converged: bool,
+    pub partial: bool,
+    pub stats: RunStats,
+    pub unplaced: Vec<UnplacedCourse>,
+    /// Only set when `require_feasible` was requested
+    pub feasibility: Option<Feasibility>,
+    pub unassigned_rooms: Vec<u32>,
+    pub rooms_kept: Option<usize>,
+    pub preference_warnings: Vec<PreferenceWarning>,
+    pub relaxations: Vec<RelaxationSuggestion>,
+    pub warm_start: Option<WarmStart>,
+    /// Courses that most other runs placed elsewhere; empty for a single run
+    pub fragile_courses: Vec<FragileCourse>,
+    /// Conflicts, preference violations, booking clashes and soft violations
+    pub message: ScheduleMessages,
+    /// Only set when the request sets `per_prodi_report`
+    pub per_prodi: Option<Vec<ProdiReport>>,
+}
+
+/// One prodi's slice of the result, for its coordinator to review
+#[derive(Serialize)]
+pub struct ProdiReport {
+    pub prodi: u32,
+    /// Part of the run's penalty caused by this prodi; a violation shared
+    /// with other prodi counts for an even share
+    pub fitness_contribution: f32,
+    pub schedule: Vec<OptimizedCourse>,
+    /// Violations involving at least one of this prodi's rows
+    pub message: ScheduleMessages,
+}
+
+impl ProdiReport {
+    /// Groups the schedule and its violations by prodi, ordered by prodi
+    pub fn split(schedule: &[OptimizedCourse], messages: &ScheduleMessages) -> Vec<ProdiReport> {
+        fn report(reports: &mut BTreeMap<u32, ProdiReport>, prodi: u32) -> &mut ProdiReport {
+            reports.entry(prodi).or_insert_with(|| ProdiReport {
+                prodi,
+                fitness_contribution: 0.0,
+                schedule: Vec::new(),
+                message: Default::default(),
+            })
+        }
+
+        let mut reports = BTreeMap::new();
+        for course in schedule {
+            report(&mut reports, course.prodi).schedule.push(course.clone());
+        }
+
+        let (conflicts, preferences, bookings, soft) = messages;
+        for message in conflicts {
+            let share = message.penalty as f32 / message.prodi.len().max(1) as f32;
+            for &prodi in &message.prodi {
+                let report = report(&mut reports, prodi);
+                report.fitness_contribution += share;
+                report.message.0.push(message.clone());
+            }
+        }
+        for message in preferences {
+            let report = report(&mut reports, message.prodi);
+            report.fitness_contribution += message.penalty as f32;
+            report.message.1.push(message.clone());
+        }
+        for message in bookings {
+            let report = report(&mut reports, message.prodi);
+            report.fitness_contribution += message.penalty as f32;
+            report.message.2.push(message.clone());
+        }
+        for message in soft {
+            let share = message.penalty as f32 / message.prodi.len().max(1) as f32;
+            for &prodi in &message.prodi {
+                let report = report(&mut reports, prodi);
+                report.fitness_contribution += share;
+                report.message.3.push(message.clone());
+            }
+        }
+
+        reports.into_values().collect()
+    }
+}
+
+// Naikkan bila field summary.json berubah secara tidak kompatibel
+pub const SUMMARY_SCHEMA_VERSION: u32 = 1;
+
+/// Compact quality summary of a stored job, served as /jobs/:id/summary.json
+/// for scripted comparisons between engine versions. Fields are only ever
+/// added; anything else bumps `schema_version`.
+#[derive(Serialize)]
+pub struct QualitySummary {
+    pub schema_version: u32,
+    pub job_id: u64,
+    pub seed: Option<u64>,
+    pub courses: usize,
+    pub fitness: f32,
+    /// `fitness` on the 0–100 scale
+    pub quality_score: f32,
+    /// Lecturer, kelas, room capacity, exam rule and booking clashes together
+    pub hard_conflicts: u32,
+    pub violations: ConflictCounts,
+    pub soft_violations: BTreeMap<SoftKind, u32>,
+    /// From start to finish of the optimization, without queueing
+    pub runtime_ms: Option<i64>,
+    pub termination_reason: Option<TerminationReason>,
+}
+
+#[derive(Serialize)]
+pub struct RunStatistics {
+    pub runs: usize,
+    pub best: f32,
+    pub worst: f32,
+    pub mean: f32,
+    /// Population standard deviation; 0 for a single run
+    pub std_dev: f32,
+}
+
+impl RunStatistics {
+    pub fn of(fitness: &[f32]) -> Self {
+        let runs = fitness.len();
+        if runs == 0 {
+            return RunStatistics { runs, best: 0.0, worst: 0.0, mean: 0.0, std_dev: 0.0 };
+        }
+        let mean = fitness.iter().map(|&f| f as f64).sum::<f64>() / runs as f64;
+        let variance = fitness.iter().map(|&f| (f as f64 - mean).powi(2)).sum::<f64>() / runs as f64;
+
+        RunStatistics {
+            runs,
+            best: fitness.iter().copied().fold(f32::INFINITY, f32::min),
+            worst: fitness.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+            mean: mean as f32,
+            std_dev: variance.sqrt() as f32,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct RunStats {
+    pub conflict_counts: ConflictCounts,
+}
+
+#[derive(Serialize)]
+pub struct Feasibility {
+    pub extension: Option<RunExtension>,
+    pub repaired: Option<Vec<OptimizedCourse>>,
+    pub unresolved: Option<Vec<u32>>,
+    pub feasible: bool,
+}
+
+/// Course of the selected schedule that a majority of the other runs put
+/// on a different day or start time, so it is likely to move when the
+/// input changes. Rooms are ignored; they are relabeled after every run.
+#[derive(Serialize)]
+pub struct FragileCourse {
+    pub id_jadwal: u32,
+    /// Other runs that placed it differently
+    pub moved_in: usize,
+    /// Other runs compared against
+    pub runs: usize,
+}
+
+impl FragileCourse {
+    /// Courses of `selected` whose placement differs in more than half of `others`
+    pub fn find(selected: &[OptimizedCourse], others: &[&[OptimizedCourse]]) -> Vec<FragileCourse> {
+        if others.is_empty() {
+            return Vec::new();
+        }
+        let placements: Vec<HashMap<u32, (u32, u32)>> = others
+            .iter()
+            .map(|schedule| schedule.iter().map(|c| (c.id_jadwal, (c.hari, c.jam_mulai))).collect())
+            .collect();
+
+        selected
+            .iter()
+            .filter_map(|course| {
+                // Kuliah yang tidak terjadwal di run lain juga dihitung berpindah
+                let moved_in = placements
+                    .iter()
+                    .filter(|placement| placement.get(&course.id_jadwal) != Some(&(course.hari, course.jam_mulai)))
+                    .count();
+                (moved_in * 2 > others.len()).then_some(FragileCourse {
+                    id_jadwal: course.id_jadwal,
+                    moved_in,
+                    runs: others.len(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Earlier job whose best position seeded part of the swarm
+#[derive(Serialize)]
+pub struct WarmStart {
+    pub job_id: u64,
+    pub similarity: f32,
+}
@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    algorithms::models::{OptimizedCourse, ScheduleMode, SlotConfig},
+    locale::Locale,
+};
+
+/// Entity a roster is built for
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RosterBy {
+    Dosen,
+    Kelas,
+    Ruangan,
+}
+
+impl RosterBy {
+    fn key(self, course: &OptimizedCourse) -> u32 {
+        match self {
+            RosterBy::Dosen => course.id_dosen,
+            RosterBy::Kelas => course.id_kelas,
+            RosterBy::Ruangan => course.ruangan,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Roster {
+    pub id: u32,
+    pub days: Vec<RosterDay>,
+}
+
+#[derive(Serialize)]
+pub struct RosterDay {
+    pub hari: u32,
+    pub nama_hari: String,
+    /// Sorted by start time
+    pub sessions: Vec<OptimizedCourse>,
+    /// Gaps inside the pagi and malam windows
+    pub free: Vec<FreeSlot>,
+}
+
+#[derive(Serialize)]
+pub struct FreeSlot {
+    pub id_waktu: u32,
+    pub jam_mulai: u32,
+    pub jam_akhir: u32,
+}
+
+/// Weekly rosters, one per dosen/kelas/ruangan in ascending id order.
+/// Courses without a room are left out of the ruangan view.
+pub fn build(schedule: &[OptimizedCourse], by: RosterBy, mode: ScheduleMode, slots: &SlotConfig, locale: Locale) -> Vec<Roster> {
+    // Mode ujian bisa lebih dari lima hari
+    let days = match mode {
+        ScheduleMode::Weekly => 5,
+        ScheduleMode::Exam => schedule.iter().map(|c| c.hari).max().unwrap_or(0),
+    };
+
+    let mut entities: BTreeMap<u32, Vec<&OptimizedCourse>> = BTreeMap::new();
+    for course in schedule {
+        let id = by.key(course);
+        if matches!(by, RosterBy::Ruangan) && id == 0 {
+            continue;
+        }
+        entities.entry(id).or_default().push(course);
+    }
+
+    entities
+        .into_iter()
+        .map(|(id, courses)| Roster {
+            id,
+            days: (1..=days)
+                .map(|hari| {
+                    let mut sessions: Vec<OptimizedCourse> =
+                        courses.iter().filter(|c| c.hari == hari).map(|c| (*c).clone()).collect();
+                    sessions.sort_by_key(|c| (c.jam_mulai, c.jam_akhir, c.id_jadwal));
+                    RosterDay {
+                        hari,
+                        nama_hari: locale.day_name(hari),
+                        free: free_slots(&sessions, slots),
+                        sessions,
+                    }
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Parts of each window not covered by a session; `sessions` must be sorted
+fn free_slots(sessions: &[OptimizedCourse], slots: &SlotConfig) -> Vec<FreeSlot> {
+    let mut free = Vec::new();
+    for (id_waktu, (start, end)) in [(1, slots.pagi), (2, slots.malam)] {
+        let mut cursor = start;
+        for course in sessions.iter().filter(|c| c.jam_akhir > start && c.jam_mulai < end) {
+            if course.jam_mulai > cursor {
+                free.push(FreeSlot { id_waktu, jam_mulai: cursor, jam_akhir: course.jam_mulai });
+            }
+            cursor = cursor.max(course.jam_akhir);
+        }
+        if cursor < end {
+            free.push(FreeSlot { id_waktu, jam_mulai: cursor, jam_akhir: end });
+        }
+    }
+    free
+}
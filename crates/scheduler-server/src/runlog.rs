@@ -0,0 +1,106 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::algorithms::{
+    diagnostics::IterationDiagnostics,
+    models::{LiveParams, OptimizationRequest, TerminationReason},
+};
+
+pub const RUN_LOG_DIR_ENV: &str = "RUN_LOG_DIR";
+
+/// One entry of a run's append-only log. Replaying `started` and the
+/// selected `run_finished` reproduces the final schedule exactly.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum RunEvent {
+    /// Request with parameters and config already resolved
+    Started {
+        request: Box<OptimizationRequest>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        started_at: Option<DateTime<Utc>>,
+    },
+    Improved { run: usize, iteration: usize, best_fitness: f32 },
+    Stopped { run: usize, iteration: usize },
+    /// Live tweak from /jobs/:id/params; replays start from the original request
+    ParamsChanged { run: usize, iteration: usize, params: LiveParams },
+    RunFinished {
+        run: usize,
+        fitness: f32,
+        #[serde(default)]
+        termination_reason: TerminationReason,
+        best_position: Vec<f32>,
+    },
+    /// Per-iteration swarm diagnostics, with `record_diagnostics`
+    Diagnostics { run: usize, iterations: Vec<IterationDiagnostics> },
+    /// Run whose result was kept
+    Selected {
+        run: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        finished_at: Option<DateTime<Utc>>,
+    },
+    /// Incremental PATCH applied after the run; not replayed
+    CoursesUpdated { upsert: Vec<u32>, remove: Vec<u32> },
+}
+
+/// Request and winning position recovered from a log
+pub fn replay_input(events: &[RunEvent]) -> Option<(&OptimizationRequest, &[f32])> {
+    let request = events.iter().find_map(|e| match e {
+        RunEvent::Started { request, .. } => Some(request.as_ref()),
+        _ => None,
+    })?;
+    let selected = events.iter().rev().find_map(|e| match e {
+        RunEvent::Selected { run, .. } => Some(*run),
+        _ => None,
+    })?;
+    let position = events.iter().find_map(|e| match e {
+        RunEvent::RunFinished { run, best_position, .. } if *run == selected => Some(best_position.as_slice()),
+        _ => None,
+    })?;
+
+    Some((request, position))
+}
+
+/// Append-only NDJSON log of one job at
+/// `<dir>/<tenant>-<job_id>-<started_at>.ndjson`. The start time keeps
+/// names unique across restarts, which reuse job ids.
+pub struct RunLog {
+    file: File,
+    written: usize,
+}
+
+impl RunLog {
+    fn path(dir: &Path, tenant: &str, job_id: u64, started_at: DateTime<Utc>) -> PathBuf {
+        dir.join(format!("{}-{}-{}.ndjson", tenant, job_id, started_at.format("%Y%m%dT%H%M%S%.3fZ")))
+    }
+
+    /// New log for a job that is starting; fails if the file already exists
+    pub fn create(dir: &Path, tenant: &str, job_id: u64, started_at: DateTime<Utc>) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .create_new(true)
+            .append(true)
+            .open(Self::path(dir, tenant, job_id, started_at))?;
+        Ok(RunLog { file, written: 0 })
+    }
+
+    /// Existing log of a finished job, for events added later (PATCH)
+    pub fn reopen(dir: &Path, tenant: &str, job_id: u64, started_at: DateTime<Utc>) -> io::Result<Self> {
+        let file = OpenOptions::new().append(true).open(Self::path(dir, tenant, job_id, started_at))?;
+        Ok(RunLog { file, written: 0 })
+    }
+
+    /// Appends the events not written yet; `events` only ever grows
+    pub fn sync(&mut self, events: &[RunEvent]) -> io::Result<()> {
+        for event in events.get(self.written..).unwrap_or_default() {
+            serde_json::to_writer(&mut self.file, event)?;
+            self.file.write_all(b"\n")?;
+            self.written += 1;
+        }
+        self.file.flush()
+    }
+}
@@ -0,0 +1,252 @@
+use std::{
+    collections::BTreeMap,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock,
+    },
+    time::Duration,
+};
+
+use chrono::{DateTime, Local};
+use log::error;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    algorithms::models::OptimizationRequest,
+    handlers::{run_optimize, AppState},
+    tenant::Tenant,
+};
+
+// Seberapa sering jadwal yang jatuh tempo diperiksa
+const TICK: Duration = Duration::from_secs(30);
+// Webhook yang lambat tidak boleh menahan task run
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// What a recurring run optimizes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunSource {
+    /// Fixed request stored with the schedule
+    Request(Box<OptimizationRequest>),
+    /// The tenant's most recent job, re-run as stored
+    LatestJob,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScheduleRequest {
+    /// Standard 5-field cron (`min hour dom mon dow`) or 6/7 fields with seconds
+    pub cron: String,
+    pub source: RunSource,
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Receives a JSON POST after every run, successful or not
+    #[serde(default)]
+    pub webhook: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduledRun {
+    pub id: u64,
+    pub tenant: String,
+    pub cron: String,
+    pub label: Option<String>,
+    pub webhook: Option<String>,
+    #[serde(skip)]
+    pub source: RunSource,
+    /// A previous run is still optimizing; due times are skipped until it ends
+    pub running: bool,
+    pub next_run: Option<DateTime<Local>>,
+    pub last_run: Option<DateTime<Local>>,
+    pub last_job_id: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+/// Recurring optimize runs registered by admins, across all tenants
+#[derive(Clone, Default)]
+pub struct Scheduler {
+    next_id: Arc<AtomicU64>,
+    entries: Arc<RwLock<BTreeMap<u64, ScheduledRun>>>,
+}
+
+fn parse_cron(expr: &str) -> Result<cron::Schedule, String> {
+    let expr = expr.trim();
+    // Crate cron butuh kolom detik; ekspresi 5 kolom dianggap detik ke-0
+    let full = if expr.split_whitespace().count() == 5 {
+        format!("0 {}", expr)
+    } else {
+        expr.to_string()
+    };
+    cron::Schedule::from_str(&full).map_err(|e| format!("invalid cron expression: {}", e))
+}
+
+fn next_after(expr: &str, after: DateTime<Local>) -> Option<DateTime<Local>> {
+    parse_cron(expr).ok()?.after(&after).next()
+}
+
+impl Scheduler {
+    pub fn register(&self, tenant: &Tenant, req: ScheduleRequest) -> Result<ScheduledRun, String> {
+        let next_run = parse_cron(&req.cron)?.after(&Local::now()).next();
+        if let Some(url) = &req.webhook {
+            let parsed = reqwest::Url::parse(url).map_err(|e| format!("invalid webhook url: {}", e))?;
+            if !matches!(parsed.scheme(), "http" | "https") {
+                return Err("webhook url must be http or https".to_string());
+            }
+        }
+        let entry = ScheduledRun {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed) + 1,
+            tenant: tenant.0.clone(),
+            cron: req.cron,
+            label: req.label,
+            webhook: req.webhook,
+            source: req.source,
+            running: false,
+            next_run,
+            last_run: None,
+            last_job_id: None,
+            last_error: None,
+        };
+
+        self.entries.write().unwrap().insert(entry.id, entry.clone());
+        Ok(entry)
+    }
+
+    pub fn list(&self, tenant: &Tenant) -> Vec<ScheduledRun> {
+        self.entries
+            .read()
+            .unwrap()
+            .values()
+            .filter(|e| e.tenant == tenant.0)
+            .cloned()
+            .collect()
+    }
+
+    pub fn remove(&self, tenant: &Tenant, id: u64) -> bool {
+        let mut entries = self.entries.write().unwrap();
+        if entries.get(&id).is_some_and(|e| e.tenant == tenant.0) {
+            entries.remove(&id);
+            return true;
+        }
+        false
+    }
+
+    /// Entries due at `now`; their next run is advanced right away and
+    /// entries whose previous run is still going are left out, so a slow
+    /// run is never started twice
+    fn take_due(&self, now: DateTime<Local>) -> Vec<ScheduledRun> {
+        let mut entries = self.entries.write().unwrap();
+        entries
+            .values_mut()
+            .filter(|e| e.next_run.is_some_and(|at| at <= now))
+            .filter_map(|e| {
+                e.next_run = next_after(&e.cron, now);
+                if e.running {
+                    return None;
+                }
+                e.running = true;
+                Some(e.clone())
+            })
+            .collect()
+    }
+
+    fn record(&self, id: u64, at: DateTime<Local>, result: Result<u64, String>) {
+        if let Some(entry) = self.entries.write().unwrap().get_mut(&id) {
+            entry.running = false;
+            entry.last_run = Some(at);
+            match result {
+                Ok(job_id) => {
+                    entry.last_job_id = Some(job_id);
+                    entry.last_error = None;
+                }
+                Err(e) => entry.last_error = Some(e),
+            }
+        }
+    }
+}
+
+/// Background loop started from main; every due run gets its own task and
+/// goes through the normal optimize path so results land in the tenant's
+/// job history
+pub async fn run_loop(state: AppState) {
+    let mut interval = tokio::time::interval(TICK);
+    loop {
+        interval.tick().await;
+
+        for entry in state.scheduler.take_due(Local::now()) {
+            tokio::spawn(run_and_record(state.clone(), entry));
+        }
+    }
+}
+
+async fn run_and_record(state: AppState, entry: ScheduledRun) {
+    let started = Local::now();
+    let result = run_once(&state, &entry).await;
+    match &result {
+        Ok(job_id) => println!(
+            "Scheduled run {} ({}) finished as job {}",
+            entry.id,
+            entry.label.as_deref().unwrap_or(&entry.cron),
+            job_id
+        ),
+        Err(e) => error!("Scheduled run {} failed: {}", entry.id, e),
+    }
+    if let Some(url) = &entry.webhook {
+        if let Err(e) = notify(url, &entry, started, &result).await {
+            error!("Webhook for scheduled run {} failed: {}", entry.id, e);
+        }
+    }
+    state.scheduler.record(entry.id, started, result);
+}
+
+// Notifikasi hanya dicatat di log bila gagal; run tetap tercatat
+async fn notify(url: &str, entry: &ScheduledRun, started: DateTime<Local>, result: &Result<u64, String>) -> Result<(), String> {
+    let (job_id, error) = match result {
+        Ok(job_id) => (Some(*job_id), None),
+        Err(e) => (None, Some(e.as_str())),
+    };
+    let body = json!({
+        "schedule_id": entry.id,
+        "tenant": entry.tenant,
+        "label": entry.label,
+        "success": result.is_ok(),
+        "job_id": job_id,
+        "result_url": job_id.map(|id| format!("/jobs/{}/result", id)),
+        "error": error,
+        "started_at": started.to_rfc3339(),
+        "finished_at": Local::now().to_rfc3339(),
+    });
+
+    let response = reqwest::Client::new()
+        .post(url)
+        .timeout(WEBHOOK_TIMEOUT)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("webhook answered {}", response.status()));
+    }
+    Ok(())
+}
+
+async fn run_once(state: &AppState, entry: &ScheduledRun) -> Result<u64, String> {
+    let tenant = Tenant(entry.tenant.clone());
+    let request = match &entry.source {
+        RunSource::Request(request) => request.as_ref().clone(),
+        RunSource::LatestJob => {
+            let jobs = &state.tenants.get(&tenant).jobs;
+            let job = jobs
+                .latest_id()
+                .and_then(|id| jobs.get(id))
+                .ok_or("tenant has no stored job to re-run")?;
+            job.request.clone()
+        }
+    };
+
+    let result = run_optimize(state, &tenant, request)
+        .await
+        .map_err(|(status, message)| format!("optimize failed with {}: {}", status, message))?;
+
+    Ok(result.summary.job_id)
+}
@@ -0,0 +1,78 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock,
+    },
+};
+
+use serde::Serialize;
+
+use crate::algorithms::models::{OptimizationRequest, SessionChunk};
+
+// Sesi yang tidak pernah di-start dibuang paling awal
+const MAX_OPEN_SESSIONS: usize = 20;
+
+/// Number of items collected so far in a session
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct SessionSize {
+    pub courses: usize,
+    pub time_preferences: usize,
+    pub external_bookings: usize,
+}
+
+/// Requests assembled chunk by chunk before a run is started
+#[derive(Clone, Default)]
+pub struct SessionStore {
+    next_id: Arc<AtomicU64>,
+    sessions: Arc<RwLock<BTreeMap<u64, OptimizationRequest>>>,
+}
+
+impl SessionStore {
+    pub fn open(&self, request: OptimizationRequest) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+
+        let mut sessions = self.sessions.write().unwrap();
+        sessions.insert(id, request);
+        while sessions.len() > MAX_OPEN_SESSIONS {
+            sessions.pop_first();
+        }
+
+        id
+    }
+
+    /// Adds a chunk; items with an id already in the session replace the old
+    /// entry, so a chunk retried by a proxy is not counted twice
+    pub fn append(&self, id: u64, chunk: SessionChunk) -> Option<SessionSize> {
+        let mut sessions = self.sessions.write().unwrap();
+        let request = sessions.get_mut(&id)?;
+
+        request
+            .courses
+            .retain(|c| !chunk.courses.iter().any(|n| n.id_jadwal == c.id_jadwal));
+        request.courses.extend(chunk.courses);
+
+        request
+            .time_preferences
+            .retain(|p| !chunk.time_preferences.iter().any(|n| n.id_dosen == p.id_dosen));
+        request.time_preferences.extend(chunk.time_preferences);
+
+        request
+            .lecturers
+            .retain(|l| !chunk.lecturers.iter().any(|n| n.id_dosen == l.id_dosen));
+        request.lecturers.extend(chunk.lecturers);
+
+        request.external_bookings.extend(chunk.external_bookings);
+
+        Some(SessionSize {
+            courses: request.courses.len(),
+            time_preferences: request.time_preferences.len(),
+            external_bookings: request.external_bookings.len(),
+        })
+    }
+
+    /// Removes the session and hands back the assembled request
+    pub fn take(&self, id: u64) -> Option<OptimizationRequest> {
+        self.sessions.write().unwrap().remove(&id)
+    }
+}
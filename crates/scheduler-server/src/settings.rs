@@ -0,0 +1,117 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{locale::Locale, tenant::TenantDefaults, tracking::TrackingConfig};
+
+pub const CONFIG_PATH_ENV: &str = "SCHEDULER_CONFIG";
+
+/// Caps applied to every optimize request
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Limits {
+    pub max_courses: Option<usize>,
+    pub max_swarm_size: Option<usize>,
+    pub max_iterations: Option<usize>,
+    /// Runs whose estimated swarm memory exceeds this are rejected
+    pub max_memory_bytes: Option<u64>,
+    /// Jobs one tenant may have optimizing at once; more are refused with 429
+    pub max_concurrent_jobs: Option<usize>,
+    /// Jobs optimizing at once over all tenants; more are refused with 503
+    pub max_total_jobs: Option<usize>,
+    /// Progress events larger than this lose their bulky optional fields;
+    /// `payload::DEFAULT_MAX_EVENT_BYTES` when unset
+    pub max_event_bytes: Option<usize>,
+    /// Tenants served at once; `tenant::DEFAULT_MAX_TENANTS` when unset
+    pub max_tenants: Option<usize>,
+}
+
+/// Service-wide settings read from the JSON file at `SCHEDULER_CONFIG`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Base defaults for tenants that have not set their own
+    pub defaults: TenantDefaults,
+    pub cors_origins: Vec<String>,
+    pub limits: Limits,
+    /// Day names in exports unless a request asks for another `locale`
+    pub locale: Locale,
+    /// MLflow server that receives every finished job, if any
+    pub tracking: Option<TrackingConfig>,
+    /// Rayon threads given to each running job in its own pool; None shares
+    /// the global pool between all jobs
+    pub job_threads: Option<usize>,
+    /// API key per tenant id; once any is set only listed tenants are
+    /// served, each with its own key
+    #[serde(skip_serializing)]
+    pub tenant_keys: HashMap<String, String>,
+    /// Key for the /admin routes; without it they stay open only while no
+    /// tenant keys are set
+    #[serde(skip_serializing)]
+    pub admin_key: Option<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            defaults: TenantDefaults::default(),
+            cors_origins: vec!["http://localhost:3000".to_string()],
+            limits: Limits::default(),
+            locale: Locale::default(),
+            tracking: None,
+            job_threads: None,
+            tenant_keys: HashMap::new(),
+            admin_key: None,
+        }
+    }
+}
+
+/// Shared, reloadable settings. A reload only affects runs started after it.
+#[derive(Clone, Default)]
+pub struct SettingsHandle {
+    path: Option<Arc<PathBuf>>,
+    current: Arc<RwLock<Settings>>,
+}
+
+impl SettingsHandle {
+    pub fn from_env() -> Result<Self, String> {
+        let handle = Self {
+            path: std::env::var(CONFIG_PATH_ENV).ok().map(|p| Arc::new(PathBuf::from(p))),
+            current: Arc::default(),
+        };
+        handle.reload()?;
+        Ok(handle)
+    }
+
+    /// Settings that are never reloaded from a file, for tests
+    #[cfg(test)]
+    pub fn fixed(settings: Settings) -> Self {
+        Self { path: None, current: Arc::new(RwLock::new(settings)) }
+    }
+
+    /// Re-reads the file; on error the previous settings stay in effect
+    pub fn reload(&self) -> Result<Settings, String> {
+        let Some(path) = &self.path else {
+            return Ok(self.get());
+        };
+
+        let text = fs::read_to_string(path.as_ref()).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let settings: Settings = serde_json::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+        settings.defaults.config.validate().map_err(|e| format!("{}: defaults.config: {}", path.display(), e))?;
+        *self.current.write().unwrap() = settings.clone();
+        Ok(settings)
+    }
+
+    pub fn get(&self) -> Settings {
+        self.current.read().unwrap().clone()
+    }
+
+    pub fn allows_origin(&self, origin: &str) -> bool {
+        self.current.read().unwrap().cors_origins.iter().any(|o| o == origin || o == "*")
+    }
+}
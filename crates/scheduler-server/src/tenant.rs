@@ -0,0 +1,359 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Query},
+    http::{request::Parts, StatusCode},
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, mpsc};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    algorithms::models::{ConstraintConfig, LiveParams, OptimizationProgress, PsoParameters},
+    gates::QualityGates,
+    handlers::AppState,
+    jobs::{JobStore, JobTracker},
+    payload::EventSizes,
+    sessions::SessionStore,
+    settings::SettingsHandle,
+};
+
+pub const TENANT_HEADER: &str = "x-tenant-id";
+pub const API_KEY_HEADER: &str = "x-api-key";
+pub const ADMIN_KEY_HEADER: &str = "x-admin-key";
+pub const DEFAULT_TENANT: &str = "default";
+// Batas tenant di memori bila limits.max_tenants tidak diisi
+pub const DEFAULT_MAX_TENANTS: usize = 100;
+
+/// Tenant taken from the `x-tenant-id` header, `default` when absent.
+/// EventSource cannot set headers, so `?tenant=` and `?api_key=` are
+/// accepted as a fallback. With `tenant_keys` configured the tenant must be
+/// listed and send its key in `x-api-key`.
+#[derive(Debug, Clone)]
+pub struct Tenant(pub String);
+
+#[derive(Deserialize)]
+struct TenantQuery {
+    tenant: Option<String>,
+    api_key: Option<String>,
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for Tenant {
+    type Rejection = (StatusCode, &'static str);
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let query = Query::<TenantQuery>::try_from_uri(&parts.uri).ok().map(|Query(q)| q);
+        let (from_query, key_from_query) = match query {
+            Some(q) => (q.tenant, q.api_key),
+            None => (None, None),
+        };
+
+        let id = match parts.headers.get(TENANT_HEADER) {
+            Some(value) => value
+                .to_str()
+                .map_err(|_| (StatusCode::BAD_REQUEST, "invalid tenant id"))?
+                .to_string(),
+            None => from_query.unwrap_or_else(|| DEFAULT_TENANT.to_string()),
+        };
+
+        let valid = !id.is_empty()
+            && id.len() <= 64
+            && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err((StatusCode::BAD_REQUEST, "invalid tenant id"));
+        }
+
+        let settings = state.settings.get();
+        if !settings.tenant_keys.is_empty() {
+            let key = match parts.headers.get(API_KEY_HEADER) {
+                Some(value) => value.to_str().ok().map(str::to_string),
+                None => key_from_query,
+            };
+            let authorized = settings
+                .tenant_keys
+                .get(&id)
+                .zip(key)
+                .is_some_and(|(expected, key)| keys_match(expected, &key));
+            if !authorized {
+                return Err((StatusCode::UNAUTHORIZED, "missing or invalid api key"));
+            }
+        }
+
+        let tenant = Tenant(id);
+        let max_tenants = settings.limits.max_tenants.unwrap_or(DEFAULT_MAX_TENANTS);
+        if !state.tenants.admit(&tenant, max_tenants) {
+            return Err((StatusCode::FORBIDDEN, "tenant limit reached"));
+        }
+        Ok(tenant)
+    }
+}
+
+/// Caller of an /admin route, checked against `admin_key` in the settings
+/// via the `x-admin-key` header
+#[derive(Debug, Clone, Copy)]
+pub struct Admin;
+
+#[async_trait]
+impl FromRequestParts<AppState> for Admin {
+    type Rejection = (StatusCode, &'static str);
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let settings = state.settings.get();
+        match &settings.admin_key {
+            Some(expected) => {
+                let key = parts.headers.get(ADMIN_KEY_HEADER).and_then(|value| value.to_str().ok());
+                if key.is_some_and(|key| keys_match(expected, key)) {
+                    Ok(Admin)
+                } else {
+                    Err((StatusCode::UNAUTHORIZED, "missing or invalid admin key"))
+                }
+            }
+            // Tanpa kunci sama sekali server dianggap lokal/pengembangan
+            None if settings.tenant_keys.is_empty() => Ok(Admin),
+            None => Err((StatusCode::FORBIDDEN, "admin key not configured")),
+        }
+    }
+}
+
+// Perbandingan tanpa keluar lebih awal, agar waktu respons tidak membocorkan prefiks kunci
+fn keys_match(expected: &str, given: &str) -> bool {
+    expected.len() == given.len()
+        && expected.bytes().zip(given.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// Applied when a request omits `parameters` or `config`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TenantDefaults {
+    pub parameters: PsoParameters,
+    pub config: ConstraintConfig,
+    /// Checked by /jobs/:id/publish
+    pub gates: QualityGates,
+}
+
+/// Handles of a job that is still optimizing; dropped when it finishes, so a
+/// late /stop can't leak into the next run
+#[derive(Clone)]
+pub struct RunHandle {
+    pub cancel: CancellationToken,
+    pub params_tx: mpsc::UnboundedSender<LiveParams>,
+}
+
+/// Registration of a running job; unregisters and cancels it when dropped,
+/// including when the request is abandoned mid-run
+pub struct ActiveRun {
+    job_id: u64,
+    running: Arc<RwLock<HashMap<u64, RunHandle>>>,
+    pub cancel: CancellationToken,
+    pub params_rx: Option<mpsc::UnboundedReceiver<LiveParams>>,
+}
+
+impl ActiveRun {
+    pub fn job_id(&self) -> u64 {
+        self.job_id
+    }
+}
+
+impl Drop for ActiveRun {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+        self.running.write().unwrap().remove(&self.job_id);
+    }
+}
+
+/// Channels owned by a single tenant, so faculties never see each other's runs
+#[derive(Clone)]
+pub struct TenantState {
+    pub status_tx: broadcast::Sender<OptimizationProgress>,
+    /// Jobs still optimizing, keyed by job id, for /stop and /jobs/:id/params
+    pub running: Arc<RwLock<HashMap<u64, RunHandle>>>,
+    /// Set via /admin/defaults; otherwise the service settings apply
+    pub defaults: Arc<RwLock<Option<TenantDefaults>>>,
+    settings: SettingsHandle,
+    pub jobs: JobStore,
+    /// Background jobs submitted to /optimize
+    pub tracker: JobTracker,
+    pub sessions: SessionStore,
+    /// Latest progress event, replayed to SSE clients when they connect
+    pub last_progress: Arc<RwLock<Option<OptimizationProgress>>>,
+    /// Latest progress per job still running, so concurrent runs each keep
+    /// their own snapshot
+    pub job_progress: Arc<RwLock<HashMap<u64, OptimizationProgress>>>,
+    pub event_sizes: Arc<EventSizes>,
+}
+
+impl TenantState {
+    fn new(settings: SettingsHandle) -> Self {
+        let (status_tx, _) = broadcast::channel::<OptimizationProgress>(1024);
+
+        let last_progress: Arc<RwLock<Option<OptimizationProgress>>> = Arc::default();
+        let job_progress: Arc<RwLock<HashMap<u64, OptimizationProgress>>> = Arc::default();
+        let mut rx = status_tx.subscribe();
+        let (cache, per_job) = (last_progress.clone(), job_progress.clone());
+        tokio::spawn(async move {
+            loop {
+                match rx.recv().await {
+                    Ok(progress) => {
+                        if let Some(job_id) = progress.job_id {
+                            let mut per_job = per_job.write().unwrap();
+                            if progress.job_finished {
+                                per_job.remove(&job_id);
+                            } else {
+                                per_job.insert(job_id, progress.clone());
+                            }
+                        }
+                        *cache.write().unwrap() = Some(progress);
+                    }
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+
+        Self {
+            status_tx,
+            running: Arc::default(),
+            defaults: Arc::default(),
+            settings,
+            jobs: JobStore::default(),
+            tracker: JobTracker::default(),
+            sessions: SessionStore::default(),
+            last_progress,
+            job_progress,
+            event_sizes: Arc::default(),
+        }
+    }
+
+    /// Token and live-parameter channel for a job that is about to run;
+    /// only called through `TenantRegistry::reserve_run`, which checks the
+    /// job limits first
+    fn start_run(&self, job_id: u64) -> ActiveRun {
+        let cancel = CancellationToken::new();
+        let (params_tx, params_rx) = mpsc::unbounded_channel();
+        self.running
+            .write()
+            .unwrap()
+            .insert(job_id, RunHandle { cancel: cancel.clone(), params_tx });
+        ActiveRun {
+            job_id,
+            running: self.running.clone(),
+            cancel,
+            params_rx: Some(params_rx),
+        }
+    }
+
+    /// Jobs still optimizing, background and blocking ones alike
+    pub fn active_jobs(&self) -> Vec<u64> {
+        let mut ids = self.tracker.running();
+        ids.extend(self.running.read().unwrap().keys());
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+
+    pub fn defaults(&self) -> TenantDefaults {
+        self.defaults
+            .read()
+            .unwrap()
+            .clone()
+            .unwrap_or_else(|| self.settings.get().defaults)
+    }
+}
+
+/// Why a new job can't start now
+#[derive(Debug)]
+pub struct Busy {
+    pub status: StatusCode,
+    pub queue_length: usize,
+    pub error: &'static str,
+    /// The tenant's own running jobs, so a frontend can attach to one
+    pub active_jobs: Vec<u64>,
+}
+
+#[derive(Clone, Default)]
+pub struct TenantRegistry {
+    tenants: Arc<RwLock<HashMap<String, TenantState>>>,
+    settings: SettingsHandle,
+    // Menghitung dan mendaftarkan job dalam satu critical section
+    admission: Arc<Mutex<()>>,
+}
+
+impl TenantRegistry {
+    pub fn new(settings: SettingsHandle) -> Self {
+        Self {
+            tenants: Arc::default(),
+            settings,
+            admission: Arc::default(),
+        }
+    }
+
+    /// Registers `job_id` as running unless the tenant is at
+    /// `max_concurrent_jobs` (429) or the service at `max_total_jobs` (503).
+    /// Counting and registering happen under one lock, so concurrent
+    /// submissions can't all slip under a limit. A job already tracked under
+    /// `job_id` doesn't count against the limits itself.
+    pub fn reserve_run(&self, tenant: &Tenant, job_id: u64) -> Result<ActiveRun, Busy> {
+        let _admission = self.admission.lock().unwrap();
+        let limits = self.settings.get().limits;
+        let tenant_state = self.get(tenant);
+
+        let mut active_jobs = tenant_state.active_jobs();
+        let own = active_jobs.contains(&job_id);
+        active_jobs.retain(|&id| id != job_id);
+        let total = self.active_job_count() - usize::from(own);
+
+        let (status, queue_length, error) = if limits.max_concurrent_jobs.is_some_and(|max| active_jobs.len() >= max) {
+            (StatusCode::TOO_MANY_REQUESTS, active_jobs.len(), "Batas job bersamaan untuk tenant ini tercapai")
+        } else if limits.max_total_jobs.is_some_and(|max| total >= max) {
+            (StatusCode::SERVICE_UNAVAILABLE, total, "Engine sedang penuh")
+        } else {
+            return Ok(tenant_state.start_run(job_id));
+        };
+        Err(Busy { status, queue_length, error, active_jobs })
+    }
+
+    pub fn get(&self, tenant: &Tenant) -> TenantState {
+        if let Some(state) = self.tenants.read().unwrap().get(&tenant.0) {
+            return state.clone();
+        }
+
+        self.tenants
+            .write()
+            .unwrap()
+            .entry(tenant.0.clone())
+            .or_insert_with(|| TenantState::new(self.settings.clone()))
+            .clone()
+    }
+
+    /// Creates the tenant unless `max_tenants` are already served; known
+    /// tenants are always admitted
+    pub fn admit(&self, tenant: &Tenant, max_tenants: usize) -> bool {
+        if self.tenants.read().unwrap().contains_key(&tenant.0) {
+            return true;
+        }
+
+        let mut tenants = self.tenants.write().unwrap();
+        if tenants.len() >= max_tenants && !tenants.contains_key(&tenant.0) {
+            return false;
+        }
+        tenants
+            .entry(tenant.0.clone())
+            .or_insert_with(|| TenantState::new(self.settings.clone()));
+        true
+    }
+
+    /// State of a tenant that has already been used, without creating one
+    pub fn existing(&self, tenant: &Tenant) -> Option<TenantState> {
+        self.tenants.read().unwrap().get(&tenant.0).cloned()
+    }
+
+    /// Jobs still optimizing over all tenants
+    pub fn active_job_count(&self) -> usize {
+        self.tenants.read().unwrap().values().map(|state| state.active_jobs().len()).sum()
+    }
+}
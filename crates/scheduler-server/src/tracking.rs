@@ -0,0 +1,189 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use log::error;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    jobs::JobRecord,
+    result::OptimizationResult,
+    runlog::RunEvent,
+};
+
+// Batas log-batch MLflow per request
+const MAX_METRICS_PER_BATCH: usize = 1000;
+const MAX_PARAMS_PER_BATCH: usize = 100;
+
+/// MLflow tracking server that receives one run per job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackingConfig {
+    /// Base URL of the tracking server, e.g. `http://localhost:5000`
+    pub endpoint: String,
+    #[serde(default = "default_experiment")]
+    pub experiment_id: String,
+    /// Sent as a bearer token when set
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+fn default_experiment() -> String {
+    "0".to_string()
+}
+
+/// Everything logged for one job, captured before the request returns
+struct TrackedRun {
+    tenant: String,
+    job_id: u64,
+    started_ms: i64,
+    params: Vec<(String, String)>,
+    metrics: Vec<(String, f64, usize)>,
+    tags: Vec<(String, String)>,
+    artifact: Vec<u8>,
+}
+
+/// Logs the job to the tracking server in the background; failures only
+/// end up in the server log
+pub fn spawn(config: TrackingConfig, tenant: &str, started_ms: i64, job: Arc<JobRecord>, result: &OptimizationResult) {
+    let summary = &result.summary;
+
+    // Parameter sudah di-resolve, termasuk seed
+    let mut params = Vec::new();
+    if let Some(parameters) = &job.request.parameters {
+        flatten("", &to_value(parameters), &mut params);
+    }
+    if let Some(config) = &job.request.config {
+        flatten("weights.", &to_value(&config.weights), &mut params);
+    }
+
+    // Best fitness hanya berubah saat membaik, jadi cukup dicatat pada iterasi itu
+    let mut metrics: Vec<(String, f64, usize)> = job
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            RunEvent::Improved { run, iteration, best_fitness } => {
+                Some((format!("run{}.best_fitness", run), *best_fitness as f64, *iteration))
+            }
+            _ => None,
+        })
+        .collect();
+    metrics.push(("fitness".to_string(), summary.fitness as f64, 0));
+    metrics.push(("quality_score".to_string(), summary.quality.score as f64, 0));
+    if let Value::Object(counts) = json!(summary.stats.conflict_counts) {
+        for (name, value) in counts {
+            metrics.push((format!("conflicts.{}", name), value.as_f64().unwrap_or(0.0), 0));
+        }
+    }
+
+    let run = TrackedRun {
+        tenant: tenant.to_string(),
+        job_id: summary.job_id,
+        started_ms,
+        params,
+        metrics,
+        tags: vec![
+            ("tenant".to_string(), tenant.to_string()),
+            ("job_id".to_string(), summary.job_id.to_string()),
+            ("termination_reason".to_string(), json!(summary.termination_reason).as_str().unwrap_or_default().to_string()),
+        ],
+        artifact: serde_json::to_vec_pretty(result).unwrap_or_default(),
+    };
+
+    tokio::spawn(async move {
+        if let Err(e) = log_run(&config, &run).await {
+            error!("Gagal mengirim job {} ({}) ke tracking server: {}", run.job_id, run.tenant, e);
+        }
+    });
+}
+
+/// Like `json!`, but via text so f32 fields log as `0.7`, not `0.699999988079071`
+fn to_value(value: &impl Serialize) -> Value {
+    serde_json::to_string(value)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+/// `{"a": {"b": 1}}` becomes `a.b = 1`
+fn flatten(prefix: &str, value: &Value, out: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(fields) => {
+            for (name, value) in fields {
+                flatten(&format!("{}{}.", prefix, name), value, out);
+            }
+        }
+        Value::Null => {}
+        Value::String(s) => out.push((prefix.trim_end_matches('.').to_string(), s.clone())),
+        other => out.push((prefix.trim_end_matches('.').to_string(), other.to_string())),
+    }
+}
+
+async fn log_run(config: &TrackingConfig, run: &TrackedRun) -> Result<(), String> {
+    let client = reqwest::Client::new();
+    let base = config.endpoint.trim_end_matches('/');
+    let post = |path: &'static str, body: Value| call(&client, config, path, body);
+
+    let created = post(
+        "runs/create",
+        json!({
+            "experiment_id": config.experiment_id,
+            "run_name": format!("{}-job-{}", run.tenant, run.job_id),
+            "start_time": run.started_ms,
+            "tags": key_values(&run.tags),
+        }),
+    )
+    .await?;
+    let info = &created["run"]["info"];
+    let run_id = info["run_id"].as_str().ok_or("runs/create tanpa run_id")?.to_string();
+
+    let now = Utc::now().timestamp_millis();
+    for params in run.params.chunks(MAX_PARAMS_PER_BATCH) {
+        post("runs/log-batch", json!({ "run_id": run_id, "params": key_values(params) })).await?;
+    }
+    for metrics in run.metrics.chunks(MAX_METRICS_PER_BATCH) {
+        let metrics: Vec<Value> = metrics
+            .iter()
+            .map(|(key, value, step)| json!({ "key": key, "value": value, "timestamp": now, "step": step }))
+            .collect();
+        post("runs/log-batch", json!({ "run_id": run_id, "metrics": metrics })).await?;
+    }
+
+    // Hanya server dengan artifact proxy (mlflow-artifacts:/) yang bisa menerima upload
+    match info["artifact_uri"].as_str().and_then(|uri| uri.strip_prefix("mlflow-artifacts:/")) {
+        Some(path) => {
+            let mut request = client
+                .put(format!("{}/api/2.0/mlflow-artifacts/artifacts/{}/result.json", base, path.trim_matches('/')))
+                .body(run.artifact.clone());
+            if let Some(token) = &config.token {
+                request = request.bearer_auth(token);
+            }
+            let response = request.send().await.map_err(|e| e.to_string())?;
+            if !response.status().is_success() {
+                return Err(format!("artifact upload {}", response.status()));
+            }
+        }
+        None => error!("Tracking server tidak memakai artifact proxy; result.json job {} tidak diunggah", run.job_id),
+    }
+
+    post("runs/update", json!({ "run_id": run_id, "status": "FINISHED", "end_time": now })).await?;
+    Ok(())
+}
+
+async fn call(client: &reqwest::Client, config: &TrackingConfig, path: &str, body: Value) -> Result<Value, String> {
+    let base = config.endpoint.trim_end_matches('/');
+    let mut request = client.post(format!("{}/api/2.0/mlflow/{}", base, path)).json(&body);
+    if let Some(token) = &config.token {
+        request = request.bearer_auth(token);
+    }
+    let response = request.send().await.map_err(|e| e.to_string())?;
+    let status = response.status();
+    let body: Value = response.json().await.unwrap_or_default();
+    if !status.is_success() {
+        return Err(format!("{} {}: {}", path, status, body));
+    }
+    Ok(body)
+}
+
+fn key_values(pairs: &[(String, String)]) -> Vec<Value> {
+    pairs.iter().map(|(key, value)| json!({ "key": key, "value": value })).collect()
+}
@@ -0,0 +1,168 @@
+use std::collections::HashSet;
+
+use axum::extract::ws::{Message, WebSocket};
+use log::error;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::{sync::mpsc, task::JoinHandle};
+
+use crate::{
+    algorithms::models::OptimizationProgress,
+    payload,
+    tenant::{Tenant, TenantRegistry, TenantState},
+};
+
+// Batas job yang diikuti per socket; dashboard memantau sekitar 10 run
+const MAX_SUBSCRIBED_JOBS: usize = 32;
+
+/// Client message on the multiplexed progress socket. The socket belongs to
+/// the tenant of the upgrade request; `tenant` may only repeat it.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum ClientMessage {
+    /// Follow these jobs as well; an empty `job_ids` means every job
+    Subscribe {
+        #[serde(default)]
+        tenant: Option<String>,
+        #[serde(default)]
+        job_ids: Vec<u64>,
+    },
+    /// Stop following these jobs; an empty `job_ids` stops everything
+    Unsubscribe {
+        #[serde(default)]
+        tenant: Option<String>,
+        #[serde(default)]
+        job_ids: Vec<u64>,
+    },
+}
+
+/// Which of the tenant's jobs the socket follows
+enum Subscription {
+    None,
+    All,
+    Jobs(HashSet<u64>),
+}
+
+/// Forwards progress of the subscribed jobs over one socket, each event
+/// tagged with its tenant and job id. Only tenants that already exist can
+/// be followed, so a socket never creates tenant state.
+pub async fn serve(mut socket: WebSocket, tenants: TenantRegistry, tenant: Tenant, max_bytes: usize) {
+    let (tx, mut rx) = mpsc::channel::<String>(256);
+    let mut subscription = Subscription::None;
+    let mut forwarder: Option<JoinHandle<()>> = None;
+
+    loop {
+        tokio::select! {
+            incoming = socket.recv() => {
+                let Some(Ok(message)) = incoming else { break };
+                let Message::Text(text) = message else { continue };
+
+                let update = serde_json::from_str::<ClientMessage>(&text)
+                    .map_err(|e| e.to_string())
+                    .and_then(|message| apply(&mut subscription, message, &tenant));
+                let reply = match update {
+                    Ok(()) => match tenants.existing(&tenant) {
+                        Some(tenant_state) => {
+                            if let Some(old) = forwarder.take() {
+                                old.abort();
+                            }
+                            forwarder = forward(&tenant_state, &tenant, &subscription, max_bytes, tx.clone());
+                            None
+                        }
+                        None => Some(format!("tenant {} belum memiliki job", tenant.0)),
+                    },
+                    Err(e) => Some(e),
+                };
+                if let Some(error) = reply {
+                    if socket.send(Message::Text(json!({ "error": error }).to_string())).await.is_err() {
+                        break;
+                    }
+                }
+            }
+            Some(event) = rx.recv() => {
+                if socket.send(Message::Text(event)).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    if let Some(handle) = forwarder {
+        handle.abort();
+    }
+}
+
+fn apply(subscription: &mut Subscription, message: ClientMessage, tenant: &Tenant) -> Result<(), String> {
+    let (ClientMessage::Subscribe { tenant: named, .. } | ClientMessage::Unsubscribe { tenant: named, .. }) = &message;
+    if named.as_ref().is_some_and(|named| *named != tenant.0) {
+        return Err(format!("socket ini milik tenant {}; buka socket lain untuk tenant berbeda", tenant.0));
+    }
+
+    match message {
+        ClientMessage::Subscribe { job_ids, .. } if job_ids.is_empty() => *subscription = Subscription::All,
+        ClientMessage::Subscribe { job_ids, .. } => {
+            let jobs: HashSet<u64> = match subscription {
+                Subscription::All => return Ok(()),
+                Subscription::Jobs(jobs) => jobs.iter().copied().chain(job_ids).collect(),
+                Subscription::None => job_ids.into_iter().collect(),
+            };
+            // Langganan lama tetap berlaku bila batas terlampaui
+            if jobs.len() > MAX_SUBSCRIBED_JOBS {
+                return Err(format!("paling banyak {} job per socket, diminta {}", MAX_SUBSCRIBED_JOBS, jobs.len()));
+            }
+            *subscription = Subscription::Jobs(jobs);
+        }
+        ClientMessage::Unsubscribe { job_ids, .. } if job_ids.is_empty() => *subscription = Subscription::None,
+        ClientMessage::Unsubscribe { job_ids, .. } => {
+            if let Subscription::Jobs(jobs) = subscription {
+                jobs.retain(|id| !job_ids.contains(id));
+                if jobs.is_empty() {
+                    *subscription = Subscription::None;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn forward(
+    tenant_state: &TenantState,
+    tenant: &Tenant,
+    subscription: &Subscription,
+    max_bytes: usize,
+    tx: mpsc::Sender<String>,
+) -> Option<JoinHandle<()>> {
+    let job_ids = match subscription {
+        Subscription::None => return None,
+        Subscription::All => HashSet::new(),
+        Subscription::Jobs(jobs) => jobs.clone(),
+    };
+    let mut status_rx = tenant_state.status_tx.subscribe();
+    let sizes = tenant_state.event_sizes.clone();
+    let tenant = tenant.0.clone();
+
+    Some(tokio::spawn(async move {
+        loop {
+            let progress = match status_rx.recv().await {
+                Ok(progress) => progress,
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            };
+            if !job_ids.is_empty() && !progress.job_id.is_some_and(|id| job_ids.contains(&id)) {
+                continue;
+            }
+
+            let frame = |progress: &OptimizationProgress| {
+                serde_json::to_string(&json!({ "tenant": tenant, "job_id": progress.job_id, "progress": progress }))
+            };
+            match payload::encode(&progress, max_bytes, &sizes, frame) {
+                Some(text) => {
+                    if tx.send(text).await.is_err() {
+                        break;
+                    }
+                }
+                None => error!("Event progres job {:?} tidak bisa diserialisasi", progress.job_id),
+            }
+        }
+    }))
+}
@@ -1,3 +0,0 @@
-pub mod optimizer;
-pub mod models;
-pub mod checker;
\ No newline at end of file
@@ -1,3 +0,0 @@
-pub mod particle;
-pub mod optimizer;
-pub mod fitness;
\ No newline at end of file